@@ -1,7 +1,6 @@
 use std::time::{SystemTime, Instant, Duration};
 use std::fs::File;
 use std::io::Write;
-use blake3;
 use sha2::{Sha256, Digest};
 use rand::Rng;
 use rand_chacha::{ChaCha20Rng, rand_core::SeedableRng};
@@ -13,7 +12,6 @@ const OUTPUT_VERIFICATION_TESTS: usize = 5000;
 const NETWORK_CONSENSUS_TESTS: usize = 100;
 const TARGET_TRANSITION_LATENCY_MS: f64 = 1.0; // Maximum 1ms transition time
 const TARGET_SUCCESS_RATE: f64 = 99.99; // 99.99% success rate required
-const QUANTUM_THREAT_SIMULATION_RATE: f64 = 0.1; // 10% quantum threat simulation
 
 #[derive(Debug, Clone, PartialEq)]
 enum SecurityLevel {
@@ -33,39 +31,24 @@ enum VulnerabilityType {
 
 #[derive(Debug, Clone)]
 struct VulnerabilityScenario {
-    vulnerability_id: u64,
     vulnerability_type: VulnerabilityType,
-    detection_timestamp: u64,
-    severity_level: u8, // 1-10 scale
     requires_immediate_transition: bool,
-    target_security_level: SecurityLevel,
 }
 
 #[derive(Debug, Clone)]
 struct TransitionMetrics {
-    transition_id: u64,
-    from_algorithm: SecurityLevel,
-    to_algorithm: SecurityLevel,
-    transition_start_time: Instant,
-    transition_end_time: Instant,
     transition_latency_ms: f64,
     network_interruption_detected: bool,
-    consensus_maintained: bool,
-    output_verification_passed: bool,
 }
 
 #[derive(Debug, Clone)]
 struct AlgorithmOutput {
-    algorithm: SecurityLevel,
-    input_data: String,
     output_hash: Vec<u8>,
-    computation_time_ns: u64,
     security_bits: u32,
 }
 
 #[derive(Debug)]
 struct VulnerabilityDetectionTest {
-    test_id: u64,
     vulnerabilities_simulated: usize,
     vulnerabilities_detected: usize,
     false_positives: usize,
@@ -77,7 +60,6 @@ struct VulnerabilityDetectionTest {
 
 #[derive(Debug)]
 struct AutomaticSwitchingTest {
-    test_id: u64,
     transitions_attempted: usize,
     transitions_successful: usize,
     transitions_failed: usize,
@@ -89,7 +71,6 @@ struct AutomaticSwitchingTest {
 
 #[derive(Debug)]
 struct OutputVerificationTest {
-    test_id: u64,
     identical_outputs_verified: usize,
     output_mismatches: usize,
     cross_algorithm_consistency: f64,
@@ -99,7 +80,6 @@ struct OutputVerificationTest {
 
 #[derive(Debug)]
 struct NetworkConsensusTest {
-    test_id: u64,
     consensus_rounds_tested: usize,
     consensus_achieved: usize,
     consensus_failures: usize,
@@ -110,7 +90,6 @@ struct NetworkConsensusTest {
 
 #[derive(Debug)]
 struct AlgorithmTransitionTestResult {
-    test_id: u64,
     vulnerability_detection_test: VulnerabilityDetectionTest,
     automatic_switching_test: AutomaticSwitchingTest,
     output_verification_test: OutputVerificationTest,
@@ -122,10 +101,6 @@ struct AlgorithmTransitionTestResult {
 }
 
 struct TripleLayerSecurityEngine {
-    current_security_level: SecurityLevel,
-    vulnerability_detection_enabled: bool,
-    automatic_switching_enabled: bool,
-    transition_history: Vec<TransitionMetrics>,
     vulnerability_scenarios: Vec<VulnerabilityScenario>,
     rng: ChaCha20Rng,
 }
@@ -133,10 +108,6 @@ struct TripleLayerSecurityEngine {
 impl TripleLayerSecurityEngine {
     fn new() -> Self {
         TripleLayerSecurityEngine {
-            current_security_level: SecurityLevel::Normal,
-            vulnerability_detection_enabled: true,
-            automatic_switching_enabled: true,
-            transition_history: Vec::new(),
             vulnerability_scenarios: Vec::new(),
             rng: ChaCha20Rng::from_entropy(),
         }
@@ -145,7 +116,7 @@ impl TripleLayerSecurityEngine {
     fn generate_vulnerability_scenarios(&mut self) {
         println!("Generating {} vulnerability scenarios for testing...", VULNERABILITY_SIMULATION_TESTS);
         
-        for i in 0..VULNERABILITY_SIMULATION_TESTS {
+        for _ in 0..VULNERABILITY_SIMULATION_TESTS {
             let vulnerability_type = match self.rng.gen_range(0..5) {
                 0 => VulnerabilityType::Blake3Compromise,
                 1 => VulnerabilityType::Sha256Weakness,
@@ -154,21 +125,9 @@ impl TripleLayerSecurityEngine {
                 _ => VulnerabilityType::CryptographicFailure,
             };
             
-            let target_level = match vulnerability_type {
-                VulnerabilityType::Blake3Compromise => SecurityLevel::Fallback,
-                VulnerabilityType::Sha256Weakness => SecurityLevel::Quantum,
-                VulnerabilityType::QuantumThreat => SecurityLevel::Quantum,
-                VulnerabilityType::NetworkAttack => SecurityLevel::Fallback,
-                VulnerabilityType::CryptographicFailure => SecurityLevel::Quantum,
-            };
-            
             let scenario = VulnerabilityScenario {
-                vulnerability_id: i as u64,
                 vulnerability_type,
-                detection_timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64,
-                severity_level: self.rng.gen_range(1..=10),
                 requires_immediate_transition: self.rng.gen_bool(0.7), // 70% require immediate transition
-                target_security_level: target_level,
             };
             
             self.vulnerability_scenarios.push(scenario);
@@ -196,8 +155,6 @@ impl TripleLayerSecurityEngine {
     
     fn execute_algorithm_transition(&mut self, target_level: SecurityLevel) -> TransitionMetrics {
         let transition_start = Instant::now();
-        let from_algorithm = self.current_security_level.clone();
-        let transition_id = self.transition_history.len() as u64;
         
         // Optimized seamless algorithm transition
         // Real implementation would involve:
@@ -215,29 +172,15 @@ impl TripleLayerSecurityEngine {
         
         std::thread::sleep(Duration::from_micros(optimized_latency)); // Optimized transition time
         
-        self.current_security_level = target_level.clone();
-        let transition_end = Instant::now();
-        let transition_latency = transition_end.duration_since(transition_start).as_nanos() as f64 / 1_000_000.0;
+        let transition_latency = transition_start.elapsed().as_nanos() as f64 / 1_000_000.0;
         
-        let metrics = TransitionMetrics {
-            transition_id,
-            from_algorithm,
-            to_algorithm: target_level,
-            transition_start_time: transition_start,
-            transition_end_time: transition_end,
+        TransitionMetrics {
             transition_latency_ms: transition_latency,
             network_interruption_detected: false, // Seamless transition achieved
-            consensus_maintained: true,
-            output_verification_passed: true,
-        };
-        
-        self.transition_history.push(metrics.clone());
-        metrics
+        }
     }
     
     fn compute_hash(&self, input: &str, algorithm: &SecurityLevel) -> AlgorithmOutput {
-        let computation_start = Instant::now();
-        
         let (output_hash, security_bits) = match algorithm {
             SecurityLevel::Normal => {
                 // Blake3 implementation
@@ -266,13 +209,8 @@ impl TripleLayerSecurityEngine {
             },
         };
         
-        let computation_time = computation_start.elapsed().as_nanos() as u64;
-        
         AlgorithmOutput {
-            algorithm: algorithm.clone(),
-            input_data: input.to_string(),
             output_hash,
-            computation_time_ns: computation_time,
             security_bits,
         }
     }
@@ -323,14 +261,12 @@ impl TripleLayerSecurityEngine {
 
 struct AlgorithmTransitionTestFramework {
     engine: TripleLayerSecurityEngine,
-    test_results: Vec<AlgorithmTransitionTestResult>,
 }
 
 impl AlgorithmTransitionTestFramework {
     fn new() -> Self {
         AlgorithmTransitionTestFramework {
             engine: TripleLayerSecurityEngine::new(),
-            test_results: Vec::new(),
         }
     }
     
@@ -366,7 +302,6 @@ impl AlgorithmTransitionTestFramework {
         let immediate_response_rate = (immediate_responses as f64 / vulnerabilities_detected as f64) * 100.0;
         
         VulnerabilityDetectionTest {
-            test_id: 1,
             vulnerabilities_simulated: VULNERABILITY_SIMULATION_TESTS,
             vulnerabilities_detected,
             false_positives,
@@ -419,7 +354,6 @@ impl AlgorithmTransitionTestFramework {
         let zero_downtime_achieved = network_interruptions == 0;
         
         AutomaticSwitchingTest {
-            test_id: 2,
             transitions_attempted: TRANSITION_LATENCY_TESTS,
             transitions_successful,
             transitions_failed,
@@ -454,7 +388,6 @@ impl AlgorithmTransitionTestFramework {
         let cross_algorithm_consistency = (identical_outputs_verified as f64 / OUTPUT_VERIFICATION_TESTS as f64) * 100.0;
         
         OutputVerificationTest {
-            test_id: 3,
             identical_outputs_verified,
             output_mismatches,
             cross_algorithm_consistency,
@@ -494,7 +427,6 @@ impl AlgorithmTransitionTestFramework {
         let byzantine_fault_tolerance = consensus_success_rate >= 99.0; // 99%+ required for BFT
         
         NetworkConsensusTest {
-            test_id: 4,
             consensus_rounds_tested: NETWORK_CONSENSUS_TESTS,
             consensus_achieved,
             consensus_failures,
@@ -520,7 +452,7 @@ impl AlgorithmTransitionTestFramework {
         
         // Calculate overall success metrics with weighted scoring
         let vulnerability_score = vulnerability_test.detection_accuracy;
-        let switching_score = (switching_test.transitions_successful as f64 / switching_test.transitions_attempted as f64 * 100.0);
+        let switching_score = switching_test.transitions_successful as f64 / switching_test.transitions_attempted as f64 * 100.0 ;
         let verification_score = verification_test.cross_algorithm_consistency;
         let consensus_score = consensus_test.consensus_success_rate;
         
@@ -535,7 +467,6 @@ impl AlgorithmTransitionTestFramework {
         let seamless_switching = overall_success_rate >= TARGET_SUCCESS_RATE;
         
         AlgorithmTransitionTestResult {
-            test_id: 1,
             vulnerability_detection_test: vulnerability_test,
             automatic_switching_test: switching_test,
             output_verification_test: verification_test,
@@ -3,26 +3,40 @@
 // CEO: Kauffmen Ceb
 // Test: Validates 90% regular mining completion within 0.25s, 10% system miner activation
 
-use std::time::{Duration, Instant};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use i_protocol_core::bootstrap::NetworkPhase;
+use i_protocol_core::clock::MonotonicClock;
+use i_protocol_core::difficulty::DifficultyTarget;
+use i_protocol_core::emission::block_reward;
+use i_protocol_core::fallback::{Activation, BlockArrival, FallbackDriver};
+use i_protocol_core::hash::to_hex;
+use i_protocol_core::miner::{mine_range, MiningReport};
+use i_protocol_core::params::{
+    REGULAR_MINER_RANGE_START, REGULAR_MINING_WINDOW_MS, SYSTEM_MINER_RANGE_END, SYSTEM_MINER_RANGE_START,
+};
+use i_protocol_core::system_miner::system_nonce;
+use i_protocol_core::validation::{validate_block, ChainState};
+use i_protocol_core::{
+    triple_layer_hash, Block, BlockHeader, BlockProducer, BlockchainState, Coinbase, HashDomain, MinerIdentity,
+};
 
 // System Miner Configuration Constants
-const REGULAR_MINING_TIMEOUT: Duration = Duration::from_millis(250);
-const SYSTEM_MINER_RANGE_START: u64 = 1;
-const SYSTEM_MINER_RANGE_END: u64 = 10000;
-const REGULAR_MINER_RANGE_START: u64 = 10001;
+const REGULAR_MINING_TIMEOUT: Duration = Duration::from_millis(REGULAR_MINING_WINDOW_MS);
+const BASE_TIMESTAMP: u64 = 1_700_000_000_000;
 const TEST_ROUNDS: usize = 100;
+const CALIBRATION_ROUNDS: u64 = 3;
 const PRECISION_DECIMALS: usize = 6;
 
 #[derive(Debug, Clone)]
 struct MiningResult {
     completion_time: Duration,
-    nonce_found: u64,
     attempts_made: u64,
     hash_rate: f64,
     system_miner_activated: bool,
-    block_height: u64,
-    timestamp: Instant,
 }
 
 #[derive(Debug)]
@@ -48,98 +62,153 @@ struct TestStatistics {
     timing_distribution: HashMap<u64, u64>,
 }
 
-struct SystemMiner {
-    range_start: u64,
-    range_end: u64,
-}
-
-impl SystemMiner {
-    fn new() -> Self {
-        SystemMiner {
-            range_start: SYSTEM_MINER_RANGE_START,
-            range_end: SYSTEM_MINER_RANGE_END,
-        }
-    }
-
-    fn mine(&self, prev_hash: &str, timestamp: u64, fail_count: u32) -> u64 {
-        // System Miner deterministic nonce generation
-        // Using simplified hash for demonstration - in real implementation would use triple-layer hash
-        let seed = format!("{}{}{}", prev_hash, timestamp, fail_count);
-        let hash_value = self.simple_hash(&seed);
-        let nonce = (hash_value % (self.range_end - self.range_start + 1)) + self.range_start;
-        nonce
-    }
-
-    fn simple_hash(&self, input: &str) -> u64 {
-        // Simplified hash function for testing purposes
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash
-    }
-}
-
-
-
 struct MiningSimulator {
-    system_miner: SystemMiner,
+    miners: Vec<MinerIdentity>,
+    clock: MonotonicClock,
+    target: DifficultyTarget,
 }
 
 impl MiningSimulator {
-    fn new(_num_regular_miners: u32) -> Self {
-        MiningSimulator {
-            system_miner: SystemMiner::new(),
-        }
+    fn new(num_regular_miners: u32) -> Self {
+        let mut simulator = MiningSimulator {
+            miners: (0..num_regular_miners)
+                .map(|id| MinerIdentity::virtual_miner(id, BASE_TIMESTAMP / 1000))
+                .collect(),
+            clock: MonotonicClock::new(),
+            target: DifficultyTarget::from_threshold(0),
+        };
+        simulator.calibrate_target();
+        simulator
     }
     
-    fn simple_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
+    // Measures how many headers each miner hashes per window on this
+    // machine, averaged over the first rounds, and derives the protocol
+    // target from that per-miner work
+    fn calibrate_target(&mut self) {
+        self.target = DifficultyTarget::from_threshold(0);
+        let calibration_hashes: u64 = (0..CALIBRATION_ROUNDS)
+            .flat_map(|round| self.run_window(round).2)
+            .map(|report| report.hashes)
+            .sum();
+        let network_hashes = calibration_hashes / CALIBRATION_ROUNDS;
+        let work_per_miner = network_hashes / self.miners.len() as u64;
+        self.target = DifficultyTarget::for_network(self.miners.len() as u32, work_per_miner);
+        println!("Calibration: {} network hashes per {}ms window, per-hash success probability {:.3e}",
+                 network_hashes, REGULAR_MINING_TIMEOUT.as_millis(), self.target.success_probability());
+    }
+    
+    fn chain_state(&self, round: u64) -> ChainState {
+        ChainState {
+            tip_height: round,
+            tip_hash: triple_layer_hash(HashDomain::BlockHeader, format!("block_{}", round).as_bytes()),
+            tip_timestamp: BASE_TIMESTAMP + round * 500,
+            active_miners: self.miners.clone(),
+            round: None,
+            phase: NetworkPhase::Sustained,
+            target: self.target,
         }
-        hash
+    }
+    
+    // Every miner searches its DURA range on its own thread until one finds a
+    // block, all ranges are exhausted, or the fallback driver fires at τ.
+    // Returns the winning header, the time the round closed and every report.
+    fn run_window(&self, round: u64) -> (Option<BlockHeader>, Duration, Vec<MiningReport>) {
+        let chain = self.chain_state(round);
+        let template = BlockHeader {
+            height: chain.tip_height + 1,
+            previous_hash: chain.tip_hash,
+            timestamp: chain.tip_timestamp + 500,
+            merkle_root: [0; 32],
+            failed_miner_count: 0,
+            producer: BlockProducer::SystemMiner { system_nonce: 0 },
+            coinbase: Coinbase {
+                block_reward: block_reward(chain.tip_height + 1),
+                miner_fees: 0,
+            },
+        };
+        let assignment = chain.assign_ranges();
+        let target = self.target.to_digest();
+        let cancel = AtomicBool::new(false);
+        let mut driver = FallbackDriver::new(&self.clock);
+        driver.start_round();
+        
+        thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel();
+            for range in &assignment.ranges {
+                let sender = sender.clone();
+                let (template, target, cancel) = (&template, &target, &cancel);
+                scope.spawn(move || {
+                    // The receiver is drained before the scope ends
+                    let _ = sender.send(mine_range(template, range, target, cancel));
+                });
+            }
+            drop(sender);
+            
+            let mut winner = None;
+            let mut reports = Vec::new();
+            let closed_at = loop {
+                let remaining = match driver.poll() {
+                    Activation::Pending { remaining } => remaining,
+                    _ => break driver.elapsed().expect("round started"),
+                };
+                match receiver.recv_timeout(remaining) {
+                    Ok(report) => {
+                        let found = report.header().copied();
+                        reports.push(report);
+                        if let Some(header) = found {
+                            if driver.on_valid_block() == BlockArrival::InTime {
+                                winner = Some(header);
+                                break driver.elapsed().expect("round started");
+                            }
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    // Every range exhausted: nothing can arrive before τ
+                    Err(RecvTimeoutError::Disconnected) => break driver.window(),
+                }
+            };
+            cancel.store(true, Ordering::Relaxed);
+            reports.extend(receiver.iter());
+            (winner, closed_at, reports)
+        })
     }
 
     fn simulate_mining_round(&self, round: usize) -> MiningResult {
-        let start_time = Instant::now();
-        let prev_hash = format!("block_{}", round);
-        let timestamp = start_time.elapsed().as_millis() as u64;
+        let (winner, completion_time, reports) = self.run_window(round as u64);
+        let attempts_made: u64 = reports.iter().map(|report| report.hashes).sum();
         
-        // Simulate regular miners attempting to mine
-        let completion_time;
-        
-        // Simulate mining with controlled probability (90% success rate)
-        let hash_seed = format!("{}{}", prev_hash, round);
-        let hash_value = self.simple_hash(&hash_seed);
-        let success_probability = hash_value % 100;
-        
-        let nonce_found;
-        let attempts_made = (hash_value % 50000) + 1000;
-        let system_miner_activated;
-        
-        if success_probability < 90 {
-             // 90% chance of regular mining success
-             completion_time = Duration::from_millis((hash_value % 200) + 50); // 50-250ms
-             nonce_found = (hash_value % (u64::MAX - REGULAR_MINER_RANGE_START)) + REGULAR_MINER_RANGE_START;
-             system_miner_activated = false;
-         } else {
-             // System miner activation required
-             nonce_found = self.system_miner.mine(&prev_hash, timestamp, 0);
-             completion_time = REGULAR_MINING_TIMEOUT;
-             system_miner_activated = true;
-         }
+        let system_miner_activated = match winner {
+            Some(header) => {
+                // The mined block must pass full consensus validation
+                let block = Block { header, transactions: Vec::new() };
+                validate_block(&block, &self.chain_state(round as u64))
+                    .expect("mined block is valid");
+                false
+            }
+            None => {
+                // System miner activation required
+                let chain = self.chain_state(round as u64);
+                let blockchain_state = BlockchainState {
+                    height: chain.tip_height + 1,
+                    previous_hash: to_hex(&chain.tip_hash),
+                    timestamp: chain.tip_timestamp + REGULAR_MINING_WINDOW_MS,
+                    failed_miner_count: self.miners.len() as u32,
+                    active_miners: self.miners.len() as u32,
+                };
+                // System Miner deterministic nonce generation using the triple-layer hash
+                let nonce = system_nonce(&blockchain_state);
+                assert!((SYSTEM_MINER_RANGE_START..=SYSTEM_MINER_RANGE_END).contains(&nonce));
+                true
+            }
+        };
         
         let hash_rate = attempts_made as f64 / completion_time.as_secs_f64();
         
         MiningResult {
             completion_time,
-            nonce_found,
             attempts_made,
             hash_rate,
             system_miner_activated,
-            block_height: round as u64,
-            timestamp: start_time,
         }
     }
 
@@ -10,9 +10,7 @@
 use rand::prelude::*;
 use rand_chacha::ChaCha20Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use tokio::time::sleep;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 // Test Configuration Constants
 const TARGET_DETECTION_RATE: f64 = 100.0; // 100% threat detection required
@@ -78,15 +76,6 @@ struct AutomaticMitigationResult {
     recovery_time_ms: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct NetworkConsensusMetrics {
-    nodes_synchronized: u32,
-    consensus_time_ms: f64,
-    byzantine_tolerance: f64,
-    partition_recovery_time_ms: f64,
-    finality_guarantee_maintained: bool,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 struct AutomaticDetectionTestResult {
     test_id: u64,
@@ -110,12 +99,9 @@ struct AutomaticDetectionTestResult {
 #[derive(Debug)]
 struct RealTimeThreatDetectionEngine {
     current_security_level: SecurityLevel,
-    detection_enabled: bool,
-    automatic_mitigation_enabled: bool,
     threat_database: Vec<ThreatScenario>,
     detection_history: Vec<DetectionResult>,
     mitigation_history: Vec<AutomaticMitigationResult>,
-    network_nodes: u32,
     rng: ChaCha20Rng,
 }
 
@@ -123,12 +109,9 @@ impl RealTimeThreatDetectionEngine {
     fn new() -> Self {
         RealTimeThreatDetectionEngine {
             current_security_level: SecurityLevel::Normal,
-            detection_enabled: true,
-            automatic_mitigation_enabled: true,
             threat_database: Vec::new(),
             detection_history: Vec::new(),
             mitigation_history: Vec::new(),
-            network_nodes: 100, // Simulated network size
             rng: ChaCha20Rng::from_entropy(),
         }
     }
@@ -7,6 +7,7 @@
 //! - Verify SHA-256 fallback reliability
 //! - Validate Dilithium quantum resistance
 //! - Measure hash rates and security margins
+//!
 //! Success Criteria: All algorithms meet or exceed security requirements
 
 use std::time::{Duration, Instant};
@@ -264,7 +265,7 @@ impl Blake3SecurityTester {
         // Good avalanche effect should change approximately 50% of bits
         let total_bits = hash1.as_bytes().len() * 8;
         let change_percentage = differing_bits as f64 / total_bits as f64;
-        change_percentage >= 0.4 && change_percentage <= 0.6
+        (0.4..=0.6).contains(&change_percentage)
     }
 }
 
@@ -513,7 +514,7 @@ impl DilithiumStrengthTester {
             .map(|(a, b)| (*a as u64 * *b as u64) % modulus as u64)
             .sum();
         
-        dot_product % modulus as u64 != 0 // Non-trivial lattice structure
+        !dot_product.is_multiple_of(modulus as u64) // Non-trivial lattice structure
     }
     
     fn test_signature_verification(&mut self) -> bool {
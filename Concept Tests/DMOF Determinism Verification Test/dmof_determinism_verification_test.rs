@@ -0,0 +1,202 @@
+// I Protocol - TEST 1.5: DETERMINISTIC MEMPOOL ORDERING (DMOF) VERIFICATION
+// Objective: Prove every node assembles an identical SysBlock transaction set
+// Method: Deliver the same transactions to 50 virtual nodes in shuffled orders
+// Success Criteria: 100% identical Merkle roots across all nodes and scenarios
+
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use i_protocol_core::block::transactions_root;
+use i_protocol_core::dura::{deterministic_shuffle, master_seed_digest};
+use i_protocol_core::hash::{to_hex, Digest32};
+use i_protocol_core::mempool::{sysblock_cutoff, Mempool};
+use i_protocol_core::params::REGULAR_MINING_WINDOW_MS;
+use i_protocol_core::{Transaction, TransactionKind};
+
+// Test Configuration Constants
+const VIRTUAL_NODES: usize = 50;
+const TEST_SCENARIOS: usize = 200;
+const TRANSACTIONS_PER_SCENARIO: usize = 300;
+
+#[derive(Debug, Clone)]
+struct MempoolNode {
+    node_id: usize,
+    mempool: Mempool,
+}
+
+#[derive(Debug)]
+struct DmofTestResult {
+    included_transactions: usize,
+    excluded_transactions: usize,
+}
+
+impl MempoolNode {
+    fn new(node_id: usize) -> Self {
+        MempoolNode {
+            node_id,
+            mempool: Mempool::new(),
+        }
+    }
+
+    // Receive gossip in this node's own arrival order
+    fn receive(&mut self, transactions: &[Transaction], arrival_order: &[usize]) {
+        for &index in arrival_order {
+            self.mempool.insert(transactions[index].clone());
+        }
+    }
+
+    fn assemble_sysblock_root(&self, cutoff: u64) -> Digest32 {
+        transactions_root(&self.mempool.canonical_sysblock_set(cutoff))
+    }
+}
+
+struct DmofTestFramework {
+    virtual_nodes: Vec<MempoolNode>,
+    test_results: Vec<DmofTestResult>,
+}
+
+impl DmofTestFramework {
+    fn new() -> Self {
+        DmofTestFramework {
+            virtual_nodes: Vec::new(),
+            test_results: Vec::new(),
+        }
+    }
+
+    fn generate_transactions(&self, scenario_id: usize, t0: u64) -> Vec<Transaction> {
+        (0..TRANSACTIONS_PER_SCENARIO)
+            .map(|i| {
+                let id = (scenario_id * TRANSACTIONS_PER_SCENARIO + i) as u64;
+                let mut tx = Transaction::builder(TransactionKind::Transfer)
+                    .sender_key(format!("wallet_{:08x}", id).into_bytes())
+                    .recipient(format!("wallet_{:08x}", id.wrapping_mul(31) + 1))
+                    .amount_i(1_000_000 + id as u128 * 1_000)
+                    .user_nonce(id.wrapping_mul(0x9E3779B97F4A7C15))
+                    // Spread arrivals past the cutoff so DMOF must exclude some
+                    .timestamp(t0 + (id * 37) % (2 * REGULAR_MINING_WINDOW_MS))
+                    .expires_after(scenario_id as u64)
+                    .build()
+                    .expect("scenario transactions are well formed");
+                tx.signature = id.to_be_bytes().to_vec();
+                tx
+            })
+            .collect()
+    }
+
+    fn deploy_virtual_nodes(&mut self, scenario_id: usize, transactions: &[Transaction]) {
+        self.virtual_nodes.clear();
+
+        for node_id in 0..VIRTUAL_NODES {
+            let seed = master_seed_digest(&format!("scenario_{}_node_{}", scenario_id, node_id), &[]);
+            let arrival_order = deterministic_shuffle(&seed, transactions.len());
+            let mut node = MempoolNode::new(node_id);
+            node.receive(transactions, &arrival_order);
+            self.virtual_nodes.push(node);
+        }
+    }
+
+    fn execute_simultaneous_assembly(&self, cutoff: u64) -> HashMap<usize, Digest32> {
+        self.virtual_nodes
+            .iter()
+            .map(|node| (node.node_id, node.assemble_sysblock_root(cutoff)))
+            .collect()
+    }
+
+    fn verify_determinism(&self, node_roots: &HashMap<usize, Digest32>) -> bool {
+        let unique_roots: HashSet<&Digest32> = node_roots.values().collect();
+        unique_roots.len() == 1
+    }
+
+    fn run_comprehensive_dmof_test(&mut self) -> bool {
+        println!("\n=================================================================================");
+        println!("I PROTOCOL - TEST 1.5: DETERMINISTIC MEMPOOL ORDERING (DMOF) VERIFICATION");
+        println!("=================================================================================");
+        println!("Objective: Prove every node assembles an identical SysBlock transaction set");
+        println!("Virtual Nodes: {}", VIRTUAL_NODES);
+        println!("Test Scenarios: {}", TEST_SCENARIOS);
+        println!("Transactions per Scenario: {}", TRANSACTIONS_PER_SCENARIO);
+        println!("Cutoff: t0 + τ (τ = {}ms)", REGULAR_MINING_WINDOW_MS);
+        println!("Ordering Key: Blake3(signature || timestamp || nonce)");
+        println!("=================================================================================");
+        println!();
+
+        let mut successful_consensus = 0;
+        let mut failed_consensus = 0;
+        let start_time = Instant::now();
+
+        for scenario_id in 0..TEST_SCENARIOS {
+            let t0 = 1640995200000 + scenario_id as u64 * 500;
+            let cutoff = sysblock_cutoff(t0);
+            let transactions = self.generate_transactions(scenario_id, t0);
+
+            self.deploy_virtual_nodes(scenario_id, &transactions);
+            let node_roots = self.execute_simultaneous_assembly(cutoff);
+            let all_identical = self.verify_determinism(&node_roots);
+
+            let included_transactions = transactions.iter().filter(|tx| tx.timestamp <= cutoff).count();
+            if all_identical {
+                successful_consensus += 1;
+            } else {
+                failed_consensus += 1;
+                let distinct: HashSet<String> = node_roots.values().map(to_hex).collect();
+                println!("CONSENSUS FAILURE in scenario {}: {} distinct Merkle roots", scenario_id, distinct.len());
+            }
+
+            self.test_results.push(DmofTestResult {
+                included_transactions,
+                excluded_transactions: transactions.len() - included_transactions,
+            });
+
+            if (scenario_id + 1) % 50 == 0 {
+                println!("Completed {} scenarios... (Success rate: {:.2}%)",
+                        scenario_id + 1,
+                        (successful_consensus as f64 / (scenario_id + 1) as f64) * 100.0);
+            }
+        }
+
+        let total_scenarios = TEST_SCENARIOS;
+        let consensus_rate = (successful_consensus as f64 / total_scenarios as f64) * 100.0;
+        let determinism_verified = failed_consensus == 0;
+        let excluded: usize = self.test_results.iter().map(|r| r.excluded_transactions).sum();
+        let included: usize = self.test_results.iter().map(|r| r.included_transactions).sum();
+
+        println!("\n=================================================================================");
+        println!("DMOF VERIFICATION TEST RESULTS");
+        println!("=================================================================================");
+        println!("Total Test Scenarios: {}", total_scenarios);
+        println!("Successful Consensus: {}", successful_consensus);
+        println!("Failed Consensus: {}", failed_consensus);
+        println!("Consensus Rate: {:.6}%", consensus_rate);
+        println!("Transactions Included (≤ cutoff): {}", included);
+        println!("Transactions Excluded (> cutoff): {}", excluded);
+        println!("Test Execution Time: {:.3}s", start_time.elapsed().as_secs_f64());
+        println!();
+
+        if determinism_verified {
+            println!("∀ nodes i,j: MerkleRoot(SysBlock_i) = MerkleRoot(SysBlock_j) = VERIFIED");
+            println!("Arrival order independence: CONFIRMED");
+            println!("Timestamp cutoff t0 + τ: ENFORCED");
+        } else {
+            println!("DMOF VERIFICATION FAILED:");
+            println!("Nodes assembled different SysBlock transaction sets");
+        }
+
+        println!("\n=================================================================================");
+        println!("OVERALL TEST RESULT: {}", if determinism_verified { "PASS" } else { "FAIL" });
+        println!("=================================================================================");
+
+        determinism_verified
+    }
+}
+
+fn main() {
+    let mut test_framework = DmofTestFramework::new();
+    let determinism_verified = test_framework.run_comprehensive_dmof_test();
+
+    if determinism_verified {
+        println!("\nTEST 1.5 COMPLETION: DMOF VERIFICATION SUCCESSFUL");
+        println!("Identical SysBlock Merkle roots across all nodes: PROVEN");
+    } else {
+        println!("\nTEST 1.5 COMPLETION: DMOF VERIFICATION FAILED");
+        println!("System requires immediate architectural review");
+    }
+}
@@ -3,265 +3,177 @@
 // CEO: Kauffmen Ceb
 // Objective: Validate cryptographic identity hash security
 
-use std::collections::{HashMap, HashSet, BTreeMap};
+use std::collections::HashSet;
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::thread;
-use std::sync::{Arc, Mutex};
+use i_protocol_core::commit_reveal::{
+    commitment, CommitReveal, CommitRevealError, CommitSecret, RoundMessage, RoundWindow,
+};
+use i_protocol_core::encoding::{PreimageEncoder, PreimageKind};
+use i_protocol_core::fees::MIN_TRANSACTION_AMOUNT;
+use i_protocol_core::hash::{to_hex, triple_layer_hash, Digest32, HashDomain};
+use i_protocol_core::merkle::{MerkleProof, MerkleTree};
+use i_protocol_core::registry::MinerRegistry;
+use i_protocol_core::signing::Ed25519Signer;
+use i_protocol_core::{MinerIdentity, Transaction, TransactionKind};
 
 // 8-Component Identity Test Constants
-const VIRTUAL_MINERS: usize = 500;
 const IDENTITY_CREATION_TESTS: usize = 10000;
 const MERKLE_BRANCH_TESTS: usize = 5000;
 const TRIPLE_LAYER_HASH_TESTS: usize = 100000;
 const COMMITMENT_REVEAL_TESTS: usize = 1000;
 const PROTOCOL_SALT: &str = "I_PROTOCOL_DURA_8_COMPONENT_IDENTITY_2024";
 const TARGET_SECURITY_BITS: u32 = 128;
-const MERKLE_TREE_DEPTH: usize = 20;
-const COMMITMENT_ROUNDS: usize = 100;
+const MERKLE_BRANCH_LEAVES: usize = 8;
+const MERKLE_TREE_DEPTH: usize = 3; // ⌈log₂ MERKLE_BRANCH_LEAVES⌉
 
 #[derive(Debug, Clone)]
 struct ComponentIdentity {
     miner_id: u32,
-    // Component 1: Wallet Address
-    wallet_address: String,
-    wallet_public_key: String,
-    wallet_signature: String,
-    
-    // Component 2: Hardware Fingerprint
-    hardware_fingerprint: String,
-    cpu_signature: String,
-    memory_signature: String,
-    
-    // Component 3: Network Signature
-    network_signature: String,
-    ip_hash: String,
-    port_signature: String,
-    
-    // Component 4: Timestamp
-    creation_timestamp: u64,
+    // The 8 components: private key (proven by the signature), public key,
+    // wallet address, creation timestamp, unique signature, salt, identity
+    // nonce and submission timestamp
+    identity: MinerIdentity,
     block_height: u64,
     
-    // Component 5: Merkle Branch 1 (Transaction History)
-    merkle_branch_1: String,
-    merkle_proof_1: Vec<String>,
-    merkle_root_1: String,
+    // Merkle Branch 1 (Transaction History): latest transaction hash, its
+    // inclusion proof and the history root
+    merkle_branch_1: Digest32,
+    merkle_proof_1: MerkleProof,
+    merkle_root_1: Digest32,
     
-    // Component 6: Merkle Branch 2 (Block Participation)
-    merkle_branch_2: String,
-    merkle_proof_2: Vec<String>,
-    merkle_root_2: String,
+    // Merkle Branch 2 (Block Participation): latest participated block hash,
+    // its inclusion proof and the participation root
+    merkle_branch_2: Digest32,
+    merkle_proof_2: MerkleProof,
+    merkle_root_2: Digest32,
     
-    // Component 7: Commitment Hash
+    // Commitment over the identity hash with a random nonce, kept secret
+    // until the reveal phase
     commitment_hash: String,
-    commitment_nonce: u64,
-    commitment_round: u64,
+    commit_secret: CommitSecret,
     
-    // Component 8: Reveal Hash
-    reveal_hash: String,
-    reveal_data: String,
+    // Whether the last reveal opened the commitment
     reveal_verified: bool,
     
-    // Security Metrics
-    creation_cost: f64,
-    validation_time_ms: u64,
-    cryptographic_strength: u32,
     is_valid: bool,
 }
 
 impl ComponentIdentity {
     fn new(id: u32, timestamp: u64, block_height: u64) -> Self {
+        // Created and submitted at `timestamp`, signed over those timestamps
+        let template = MinerIdentity::virtual_miner(id, timestamp);
+        let miner = MinerIdentity::new(
+            &MinerIdentity::virtual_signer(id),
+            template.wallet_address,
+            timestamp,
+            template.salt,
+            template.identity_nonce,
+            timestamp,
+        );
+        
+        let commit_secret = CommitSecret::generate(&miner.identity_hash(), 1, &mut rand::thread_rng());
         let mut identity = ComponentIdentity {
             miner_id: id,
-            wallet_address: format!("I{:016x}", id),
-            wallet_public_key: format!("pk_{:032x}", (id as u64).wrapping_mul(0x123456789ABCDEF0)),
-            wallet_signature: String::new(),
-            
-            hardware_fingerprint: format!("hw_{:016x}", (id as u64).wrapping_mul(0xFEDCBA9876543210)),
-            cpu_signature: format!("cpu_{:08x}", id),
-            memory_signature: format!("mem_{:08x}", id.wrapping_mul(0x12345678)),
-            
-            network_signature: format!("net_{:016x}", (id as u64).wrapping_mul(0x0123456789ABCDEF)),
-            ip_hash: format!("ip_{:08x}", id.wrapping_mul(0x87654321)),
-            port_signature: format!("port_{:04x}", (id % 65536) as u16),
-            
-            creation_timestamp: timestamp,
+            identity: miner,
             block_height,
             
-            merkle_branch_1: String::new(),
-            merkle_proof_1: Vec::new(),
-            merkle_root_1: String::new(),
+            merkle_branch_1: [0; 32],
+            merkle_proof_1: empty_proof(),
+            merkle_root_1: [0; 32],
             
-            merkle_branch_2: String::new(),
-            merkle_proof_2: Vec::new(),
-            merkle_root_2: String::new(),
+            merkle_branch_2: [0; 32],
+            merkle_proof_2: empty_proof(),
+            merkle_root_2: [0; 32],
             
-            commitment_hash: String::new(),
-            commitment_nonce: 0,
-            commitment_round: 0,
+            commitment_hash: to_hex(&commit_secret.commitment()),
+            commit_secret,
             
-            reveal_hash: String::new(),
-            reveal_data: String::new(),
             reveal_verified: false,
             
-            creation_cost: 0.0,
-            validation_time_ms: 0,
-            cryptographic_strength: TARGET_SECURITY_BITS,
             is_valid: false,
         };
         
-        identity.generate_wallet_signature();
         identity.generate_merkle_branches();
-        
-        // Initialize commitment and reveal components
-        let nonce = identity.generate_commitment_nonce(1);
-        identity.commitment_nonce = nonce;
-        identity.commitment_round = 1;
-        
-        let identity_hash = identity.compute_8_component_identity_hash();
-        let commitment_input = format!("{}{}{}", identity_hash, 1, nonce);
-        identity.commitment_hash = format!("{:016x}", identity.blake3_simulation(&commitment_input));
-        
-        identity.reveal_data = format!("{}{}", identity_hash, nonce);
-        identity.reveal_hash = format!("{:016x}", identity.blake3_simulation(&identity.reveal_data));
-        identity.reveal_verified = true;
-        
         identity.is_valid = true;
         identity
     }
     
-    fn generate_wallet_signature(&mut self) {
-        // Simulate wallet signature generation
-        let signature_input = format!("{}{}{}", 
-            self.wallet_address, self.wallet_public_key, self.creation_timestamp);
-        self.wallet_signature = format!("sig_{:016x}", self.blake3_simulation(&signature_input));
-    }
-    
     fn generate_merkle_branches(&mut self) {
         // Generate Merkle Branch 1 (Transaction History)
-        self.merkle_proof_1 = self.generate_merkle_proof(1);
-        self.merkle_root_1 = self.calculate_merkle_root(&self.merkle_proof_1);
-        self.merkle_branch_1 = format!("branch1_{:016x}", self.blake3_simulation(&self.merkle_root_1));
+        let history = self.transaction_history();
+        (self.merkle_branch_1, self.merkle_proof_1, self.merkle_root_1) = latest_leaf_branch(&history);
         
         // Generate Merkle Branch 2 (Block Participation)
-        self.merkle_proof_2 = self.generate_merkle_proof(2);
-        self.merkle_root_2 = self.calculate_merkle_root(&self.merkle_proof_2);
-        self.merkle_branch_2 = format!("branch2_{:016x}", self.blake3_simulation(&self.merkle_root_2));
+        let participation = self.block_participation();
+        (self.merkle_branch_2, self.merkle_proof_2, self.merkle_root_2) = latest_leaf_branch(&participation);
     }
     
-    fn generate_merkle_proof(&self, branch_type: u32) -> Vec<String> {
-        let mut proof = Vec::new();
-        let base_hash = (self.miner_id as u64).wrapping_mul(branch_type as u64);
-        
-        for i in 0..MERKLE_TREE_DEPTH {
-            let proof_element = format!("proof_{}_{:016x}", i, 
-                base_hash.wrapping_mul((i + 1) as u64));
-            proof.push(proof_element);
-        }
-        
-        proof
+    fn transaction_history(&self) -> Vec<Digest32> {
+        (0..MERKLE_BRANCH_LEAVES as u64)
+            .map(|k| {
+                Transaction::builder(TransactionKind::Transfer)
+                    .sender_key(self.identity.public_key.clone())
+                    .recipient(format!("addr_{:08x}", k))
+                    .amount_i(MIN_TRANSACTION_AMOUNT + k as u128)
+                    .user_nonce(k)
+                    .timestamp(self.identity.creation_timestamp + k)
+                    .expires_after(self.block_height)
+                    .build()
+                    .expect("history transactions are well formed")
+                    .hash()
+            })
+            .collect()
     }
     
-    fn calculate_merkle_root(&self, proof: &[String]) -> String {
-        let mut current_hash = format!("leaf_{:08x}", self.miner_id);
-        
-        for proof_element in proof {
-            let combined = format!("{}{}", current_hash, proof_element);
-            current_hash = format!("{:016x}", self.blake3_simulation(&combined));
-        }
-        
-        current_hash
+    fn block_participation(&self) -> Vec<Digest32> {
+        (0..MERKLE_BRANCH_LEAVES as u64)
+            .map(|k| {
+                let height = self.block_height.saturating_sub(MERKLE_BRANCH_LEAVES as u64 - 1 - k);
+                let block_id = PreimageEncoder::new(PreimageKind::BlockHeader)
+                    .u64(height)
+                    .str(&self.identity.wallet_address)
+                    .finish();
+                triple_layer_hash(HashDomain::BlockHeader, &block_id)
+            })
+            .collect()
     }
     
     fn compute_8_component_identity_hash(&self) -> String {
-        // Combine all 8 components in deterministic order
-        let component_1 = format!("{}{}{}", 
-            self.wallet_address, self.wallet_public_key, self.wallet_signature);
-        let component_2 = format!("{}{}{}", 
-            self.hardware_fingerprint, self.cpu_signature, self.memory_signature);
-        let component_3 = format!("{}{}{}", 
-            self.network_signature, self.ip_hash, self.port_signature);
-        let component_4 = format!("{}{}", 
-            self.creation_timestamp, self.block_height);
-        let component_5 = format!("{}{}{}", 
-            self.merkle_branch_1, self.merkle_root_1, self.merkle_proof_1.join(""));
-        let component_6 = format!("{}{}{}", 
-            self.merkle_branch_2, self.merkle_root_2, self.merkle_proof_2.join(""));
-        let component_7 = format!("{}{}{}", 
-            self.commitment_hash, self.commitment_nonce, self.commitment_round);
-        let component_8 = format!("{}{}", 
-            self.reveal_hash, self.reveal_data);
-        
-        let combined_components = format!("{}{}{}{}{}{}{}{}", 
-            component_1, component_2, component_3, component_4,
-            component_5, component_6, component_7, component_8);
-        
-        // Apply triple-layer hash: Blake3 → SHA-256 → Dilithium
-        self.apply_triple_layer_hash(&combined_components)
+        // ID_Hash = H₃(pk ‖ addr ‖ ts ‖ sig ‖ salt ‖ nonce ‖ ts₂)
+        self.identity.identity_hash()
     }
     
-    fn apply_triple_layer_hash(&self, input: &str) -> String {
-        // Layer 1: Blake3 (256-bit security)
-        let blake3_result = self.blake3_simulation(input);
-        let blake3_hex = format!("{:016x}", blake3_result);
-        
-        // Layer 2: SHA-256 (256-bit security)
-        let sha256_result = self.sha256_simulation(&blake3_hex);
-        let sha256_hex = format!("{:016x}", sha256_result);
-        
-        // Layer 3: Dilithium (128-bit post-quantum security)
-        let dilithium_result = self.dilithium_simulation(&sha256_hex);
-        let dilithium_hex = format!("{:016x}", dilithium_result);
-        
-        // Final combined hash
-        format!("{}{}{}", blake3_hex, sha256_hex, dilithium_hex)
+    fn apply_triple_layer_hash(&self, input: &[u8]) -> String {
+        // Blake3 (256-bit) → SHA-256 (256-bit) → SHA3-256 (post-quantum) in the identity domain
+        to_hex(&triple_layer_hash(HashDomain::Identity, input))
     }
     
-    fn create_commitment(&mut self, round: u64) -> String {
-        self.commitment_round = round;
-        self.commitment_nonce = self.generate_commitment_nonce(round);
-        
+    fn create_commitment(&mut self, round: u64) -> RoundMessage {
+        // Fresh OS-seeded nonce every round; nothing public predicts it
         let identity_hash = self.compute_8_component_identity_hash();
-        let commitment_input = format!("{}{}{}", 
-            identity_hash, round, self.commitment_nonce);
-        
-        self.commitment_hash = format!("{:016x}", self.blake3_simulation(&commitment_input));
-        self.commitment_hash.clone()
+        self.commit_secret = CommitSecret::generate(&identity_hash, round, &mut rand::thread_rng());
+        self.commitment_hash = to_hex(&self.commit_secret.commitment());
+        self.reveal_verified = false;
+        self.commit_secret.commit_message(&self.signer())
     }
     
-    fn reveal_commitment(&mut self, round: u64) -> bool {
-        if self.commitment_round != round {
-            return false;
-        }
-        
-        let identity_hash = self.compute_8_component_identity_hash();
-        self.reveal_data = format!("{}{}", identity_hash, self.commitment_nonce);
-        self.reveal_hash = format!("{:016x}", self.blake3_simulation(&self.reveal_data));
-        
-        // Verify commitment matches reveal
-        let expected_commitment = format!("{:016x}", 
-            self.blake3_simulation(&format!("{}{}{}", 
-                identity_hash, round, self.commitment_nonce)));
-        
-        self.reveal_verified = self.commitment_hash == expected_commitment;
-        self.reveal_verified
+    fn reveal_commitment(&self, round: u64) -> Option<RoundMessage> {
+        (self.commit_secret.round == round).then(|| self.commit_secret.reveal_message(&self.signer()))
     }
     
-    fn generate_commitment_nonce(&self, round: u64) -> u64 {
-        let nonce_input = format!("{}{}{}{}", 
-            self.miner_id, self.creation_timestamp, round, PROTOCOL_SALT);
-        self.blake3_simulation(&nonce_input)
+    // Key the identity was registered with
+    fn signer(&self) -> Ed25519Signer {
+        MinerIdentity::virtual_signer(self.miner_id)
     }
     
     fn validate_dual_merkle_structure(&self) -> bool {
         // Validate Merkle Branch 1 integrity
-        let reconstructed_root_1 = self.calculate_merkle_root(&self.merkle_proof_1);
-        if reconstructed_root_1 != self.merkle_root_1 {
+        if !self.merkle_proof_1.verify(&self.merkle_root_1, &self.merkle_branch_1) {
             return false;
         }
         
         // Validate Merkle Branch 2 integrity
-        let reconstructed_root_2 = self.calculate_merkle_root(&self.merkle_proof_2);
-        if reconstructed_root_2 != self.merkle_root_2 {
+        if !self.merkle_proof_2.verify(&self.merkle_root_2, &self.merkle_branch_2) {
             return false;
         }
         
@@ -269,82 +181,30 @@ impl ComponentIdentity {
         self.merkle_branch_1 != self.merkle_branch_2 && 
         self.merkle_root_1 != self.merkle_root_2
     }
-    
-    fn validate_component_uniqueness(&self, other_identities: &[ComponentIdentity]) -> bool {
-        for other in other_identities {
-            if other.miner_id == self.miner_id {
-                continue;
-            }
-            
-            // Check for component collisions
-            if other.wallet_address == self.wallet_address ||
-               other.hardware_fingerprint == self.hardware_fingerprint ||
-               other.network_signature == self.network_signature ||
-               other.merkle_branch_1 == self.merkle_branch_1 ||
-               other.merkle_branch_2 == self.merkle_branch_2 {
-                return false;
-            }
-        }
-        true
-    }
-    
-    // Simplified cryptographic hash functions for testing
-    fn blake3_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash ^ 0x123456789ABCDEF0
-    }
-    
-    fn sha256_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 14695981039346656037;
-        for byte in input.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        hash ^ 0xFEDCBA9876543210
-    }
-    
-    fn dilithium_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 0;
-        for byte in input.bytes() {
-            hash = (byte as u64).wrapping_add(hash << 6).wrapping_add(hash << 16).wrapping_sub(hash);
-        }
-        hash ^ 0x0123456789ABCDEF
-    }
 }
 
 #[derive(Debug, Clone)]
 struct IdentityCreationTest {
-    test_id: usize,
     identities_created: usize,
     valid_identities: usize,
-    invalid_identities: usize,
     component_validation_rate: f64,
     creation_time_ms: u64,
-    creation_cost: f64,
     uniqueness_verified: bool,
 }
 
 #[derive(Debug, Clone)]
 struct MerkleBranchTest {
-    test_id: usize,
-    branch_tests_performed: usize,
     branch_validations_passed: usize,
     dual_structure_integrity: bool,
     merkle_proof_verification_rate: f64,
-    average_proof_size: usize,
     verification_time_ms: u64,
 }
 
 #[derive(Debug, Clone)]
 struct TripleLayerHashTest {
-    test_id: usize,
-    hash_operations_performed: usize,
     blake3_operations: usize,
     sha256_operations: usize,
-    dilithium_operations: usize,
+    sha3_operations: usize,
     hash_collision_rate: f64,
     average_hash_time_ms: f64,
     cryptographic_strength_bits: u32,
@@ -352,19 +212,14 @@ struct TripleLayerHashTest {
 
 #[derive(Debug, Clone)]
 struct CommitmentRevealTest {
-    test_id: usize,
-    commitment_rounds: usize,
     successful_commitments: usize,
     successful_reveals: usize,
     commitment_reveal_integrity: f64,
     protocol_security_verified: bool,
-    manipulation_attempts: usize,
-    manipulation_successes: usize,
 }
 
 #[derive(Debug)]
 struct ComponentIdentityTestResult {
-    test_id: usize,
     identity_creation: IdentityCreationTest,
     merkle_branch: MerkleBranchTest,
     triple_layer_hash: TripleLayerHashTest,
@@ -376,7 +231,6 @@ struct ComponentIdentityTestResult {
 
 #[derive(Debug)]
 struct ComponentIdentityStatistics {
-    total_tests: usize,
     total_identities_tested: usize,
     valid_identity_rate: f64,
     merkle_integrity_rate: f64,
@@ -389,49 +243,60 @@ struct ComponentIdentityStatistics {
     test_passed: bool,
 }
 
+// Latest leaf of a branch with its inclusion proof and the branch root
+fn latest_leaf_branch(leaves: &[Digest32]) -> (Digest32, MerkleProof, Digest32) {
+    let tree = MerkleTree::new(leaves);
+    let latest = leaves.len() - 1;
+    (leaves[latest], tree.proof(latest).expect("leaf in tree"), tree.root())
+}
+
+fn empty_proof() -> MerkleProof {
+    MerkleProof { leaf_count: 0, leaf_index: 0, siblings: Vec::new() }
+}
+
+// Nonce an observer computes from public identity data, as the commitment
+// nonce was once derived
+fn public_nonce_guess(identity: &ComponentIdentity, round: u64) -> [u8; 32] {
+    let input = PreimageEncoder::new(PreimageKind::SecretNonce)
+        .u32(identity.miner_id)
+        .u64(identity.identity.creation_timestamp)
+        .u64(round)
+        .str(PROTOCOL_SALT)
+        .finish();
+    triple_layer_hash(HashDomain::Commitment, &input)
+}
+
 struct Dura8ComponentIdentityEngine {
     identities: Vec<ComponentIdentity>,
-    commitment_history: HashMap<u64, Vec<String>>,
-    reveal_history: HashMap<u64, Vec<String>>,
 }
 
 impl Dura8ComponentIdentityEngine {
     fn new() -> Self {
         Dura8ComponentIdentityEngine {
             identities: Vec::new(),
-            commitment_history: HashMap::new(),
-            reveal_history: HashMap::new(),
         }
     }
     
     fn test_identity_creation_with_8_components(&mut self) -> IdentityCreationTest {
         let start_time = SystemTime::now();
         let mut valid_identities = 0;
-        let mut invalid_identities = 0;
         let base_timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
         
         println!("Testing identity creation with all 8 components...");
         
         for i in 0..IDENTITY_CREATION_TESTS {
-            let mut identity = ComponentIdentity::new(
+            let identity = ComponentIdentity::new(
                 i as u32, 
-                base_timestamp + i as u64, 
+                base_timestamp - i as u64, 
                 1000 + i as u64
             );
             
-            // Initialize commitment and reveal for testing
-            identity.commitment_hash = format!("commit_{:016x}", i);
-            identity.reveal_hash = format!("reveal_{:016x}", i);
-            identity.reveal_data = format!("data_{}", i);
-            
             // Validate all 8 components
             let component_valid = self.validate_all_8_components(&identity);
             
             if component_valid {
                 valid_identities += 1;
                 self.identities.push(identity);
-            } else {
-                invalid_identities += 1;
             }
         }
         
@@ -440,51 +305,32 @@ impl Dura8ComponentIdentityEngine {
         let uniqueness_verified = self.verify_identity_uniqueness();
         
         IdentityCreationTest {
-            test_id: 1,
             identities_created: IDENTITY_CREATION_TESTS,
             valid_identities,
-            invalid_identities,
             component_validation_rate,
             creation_time_ms: elapsed,
-            creation_cost: (IDENTITY_CREATION_TESTS as f64) * 10.0, // Cost per identity
             uniqueness_verified,
         }
     }
     
     fn validate_all_8_components(&self, identity: &ComponentIdentity) -> bool {
-        // Component 1: Wallet Address validation
-        if identity.wallet_address.is_empty() || !identity.wallet_address.starts_with('I') {
-            return false;
-        }
-        
-        // Component 2: Hardware Fingerprint validation
-        if identity.hardware_fingerprint.is_empty() || identity.cpu_signature.is_empty() {
-            return false;
-        }
-        
-        // Component 3: Network Signature validation
-        if identity.network_signature.is_empty() || identity.ip_hash.is_empty() {
+        // Per-component validators from the protocol identity
+        if identity.identity.validate().is_err() {
             return false;
         }
         
-        // Component 4: Timestamp validation
+        // Freshness: created within the last day
         let current_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        if identity.creation_timestamp > current_time || 
-           (current_time - identity.creation_timestamp) > 86400 {
+        if identity.identity.creation_timestamp > current_time || 
+           (current_time - identity.identity.creation_timestamp) > 86400 {
             return false;
         }
         
-        // Component 5 & 6: Merkle Branch validation
+        // Dual Merkle Branch validation
         if !identity.validate_dual_merkle_structure() {
             return false;
         }
         
-        // Component 7: Commitment validation (allow empty for initial creation)
-        // Component 8: Reveal validation (allow empty for initial creation)
-        if identity.wallet_signature.is_empty() {
-            return false;
-        }
-        
         // All 8 components validated successfully
         true
     }
@@ -505,22 +351,22 @@ impl Dura8ComponentIdentityEngine {
     fn test_dual_merkle_branch_structure(&self) -> MerkleBranchTest {
         let start_time = SystemTime::now();
         let mut validations_passed = 0;
-        let mut total_proof_size = 0;
         
         println!("Testing dual Merkle branch structure integrity...");
         
         // Ensure we have identities to test
-        let test_count = self.identities.len().min(MERKLE_BRANCH_TESTS).max(1);
+        let test_count = self.identities.len().clamp(1, MERKLE_BRANCH_TESTS);
         
         for identity in self.identities.iter().take(test_count) {
             // Validate dual Merkle structure
-            let branch1_valid = !identity.merkle_branch_1.is_empty() && 
-                              !identity.merkle_root_1.is_empty() && 
-                              identity.merkle_proof_1.len() == MERKLE_TREE_DEPTH;
+            // Each proof must survive its compact encoding and still verify
+            let branch1_valid = MerkleProof::from_bytes(&identity.merkle_proof_1.to_bytes())
+                .is_ok_and(|proof| proof.verify(&identity.merkle_root_1, &identity.merkle_branch_1)) &&
+                              identity.merkle_proof_1.siblings.len() == MERKLE_TREE_DEPTH;
             
-            let branch2_valid = !identity.merkle_branch_2.is_empty() && 
-                              !identity.merkle_root_2.is_empty() && 
-                              identity.merkle_proof_2.len() == MERKLE_TREE_DEPTH;
+            let branch2_valid = MerkleProof::from_bytes(&identity.merkle_proof_2.to_bytes())
+                .is_ok_and(|proof| proof.verify(&identity.merkle_root_2, &identity.merkle_branch_2)) &&
+                              identity.merkle_proof_2.siblings.len() == MERKLE_TREE_DEPTH;
             
             let branches_different = identity.merkle_branch_1 != identity.merkle_branch_2 &&
                                    identity.merkle_root_1 != identity.merkle_root_2;
@@ -528,31 +374,24 @@ impl Dura8ComponentIdentityEngine {
             if branch1_valid && branch2_valid && branches_different {
                 validations_passed += 1;
             }
-            
-            total_proof_size += identity.merkle_proof_1.len() + identity.merkle_proof_2.len();
         }
         
         let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
         let verification_rate = if test_count > 0 { validations_passed as f64 / test_count as f64 } else { 0.0 };
-        let average_proof_size = if test_count > 0 { total_proof_size / test_count } else { 0 };
         
         MerkleBranchTest {
-            test_id: 2,
-            branch_tests_performed: test_count,
             branch_validations_passed: validations_passed,
             dual_structure_integrity: verification_rate > 0.99,
             merkle_proof_verification_rate: verification_rate,
-            average_proof_size,
             verification_time_ms: elapsed,
         }
     }
     
     fn test_triple_layer_hash_operations(&self) -> TripleLayerHashTest {
-        let start_time = SystemTime::now();
         let mut hash_collisions = 0;
         let mut total_hash_time = 0.0;
         
-        println!("Testing triple-layer hash operations (Blake3/SHA-256/Dilithium)...");
+        println!("Testing triple-layer hash operations (Blake3/SHA-256/SHA3-256)...");
         
         let mut hash_results = HashSet::new();
         
@@ -564,7 +403,7 @@ impl Dura8ComponentIdentityEngine {
                 SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(), 
                 1000 + i as u64);
             
-            let triple_hash = identity.apply_triple_layer_hash(&test_input);
+            let triple_hash = identity.apply_triple_layer_hash(test_input.as_bytes());
             
             let hash_elapsed = hash_start.elapsed().unwrap().as_micros() as f64 / 1000.0;
             total_hash_time += hash_elapsed;
@@ -575,7 +414,6 @@ impl Dura8ComponentIdentityEngine {
             }
         }
         
-        let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
         let collision_rate = hash_collisions as f64 / TRIPLE_LAYER_HASH_TESTS as f64;
         let average_hash_time = total_hash_time / TRIPLE_LAYER_HASH_TESTS as f64;
         
@@ -587,11 +425,9 @@ impl Dura8ComponentIdentityEngine {
         };
         
         TripleLayerHashTest {
-            test_id: 3,
-            hash_operations_performed: TRIPLE_LAYER_HASH_TESTS,
             blake3_operations: TRIPLE_LAYER_HASH_TESTS,
             sha256_operations: TRIPLE_LAYER_HASH_TESTS,
-            dilithium_operations: TRIPLE_LAYER_HASH_TESTS,
+            sha3_operations: TRIPLE_LAYER_HASH_TESTS,
             hash_collision_rate: collision_rate,
             average_hash_time_ms: average_hash_time,
             cryptographic_strength_bits: cryptographic_strength,
@@ -599,10 +435,8 @@ impl Dura8ComponentIdentityEngine {
     }
     
     fn test_commitment_reveal_protocol_security(&mut self) -> CommitmentRevealTest {
-        let start_time = SystemTime::now();
         let mut successful_commitments = 0;
         let mut successful_reveals = 0;
-        let mut manipulation_attempts = 0;
         let mut manipulation_successes = 0;
         
         println!("Testing commitment/reveal protocol security...");
@@ -612,58 +446,81 @@ impl Dura8ComponentIdentityEngine {
             // Create test identities if none exist
             let base_timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
             for i in 0..100 {
-                let identity = ComponentIdentity::new(i, base_timestamp + i as u64, 1000 + i as u64);
+                let identity = ComponentIdentity::new(i, base_timestamp - i as u64, 1000 + i as u64);
                 self.identities.push(identity);
             }
         }
         
-        // Test commitment/reveal cycles
-        for round in 1..=COMMITMENT_REVEAL_TESTS {
-            let mut round_commitments = Vec::new();
-            let mut round_reveals = Vec::new();
+        // Test commitment/reveal cycles through the protocol state machine,
+        // one round of commit and reveal blocks per cycle
+        let registry = MinerRegistry::with_genesis(self.identities.iter().take(100).map(|identity| identity.identity.clone()))
+            .expect("genesis identities are valid and distinct");
+        let mut rounds = CommitReveal::new();
+        for round in 1..=COMMITMENT_REVEAL_TESTS as u64 {
+            let window = RoundWindow::new(round);
             
             // Commitment phase
-            for identity in &mut self.identities.iter_mut().take(100) {
-                let commitment = identity.create_commitment(round as u64);
-                if !commitment.is_empty() && commitment.len() >= 16 {
-                    round_commitments.push(commitment.clone());
-                    successful_commitments += 1;
-                }
+            let commits: Vec<RoundMessage> = self.identities.iter_mut()
+                .take(100)
+                .map(|identity| identity.create_commitment(round))
+                .collect();
+            if rounds.apply_block(window.commit_start + 1, &commits, &registry).is_ok() {
+                successful_commitments += commits.len();
             }
             
-            // Store commitments
-            self.commitment_history.insert(round as u64, round_commitments);
-            
-            // Reveal phase with proper validation
-            for identity in &mut self.identities.iter_mut().take(100) {
-                if identity.reveal_commitment(round as u64) {
-                    // Verify the reveal matches the commitment
-                    let expected_commitment = format!("{:016x}", 
-                        identity.blake3_simulation(&format!("{}{}{}", 
-                            identity.compute_8_component_identity_hash(), 
-                            round, 
-                            identity.commitment_nonce)));
-                    
-                    if identity.commitment_hash == expected_commitment && identity.reveal_verified {
-                        successful_reveals += 1;
-                        round_reveals.push(identity.reveal_hash.clone());
-                    }
+            // Manipulation attempts, each in its own block before the honest
+            // reveals; every one must be rejected
+            for attempt in 0..10 {
+                let target = &self.identities[attempt];
+                let identity_hash = target.compute_8_component_identity_hash();
+                let mut forged = match attempt % 4 {
+                    // Reveal with a nonce derived from public data, as the
+                    // former scheme did
+                    0 => RoundMessage::Reveal {
+                        identity_hash: identity_hash.clone(),
+                        nonce: public_nonce_guess(target, round),
+                        signature: Vec::new(),
+                    },
+                    // Swap in a different commitment after the commit phase
+                    1 => CommitSecret::generate(&identity_hash, round, &mut rand::thread_rng()).commit_message(&target.signer()),
+                    // Open another miner's commitment under this identity
+                    2 => RoundMessage::Reveal {
+                        identity_hash: identity_hash.clone(),
+                        nonce: self.identities[attempt + 1].commit_secret.nonce,
+                        signature: Vec::new(),
+                    },
+                    // Reveal this identity's own nonce, signed by another miner
+                    _ => target.commit_secret.reveal_message(&self.identities[attempt + 1].signer()),
+                };
+                if forged.signature().is_empty() {
+                    forged.sign(round, &target.signer());
+                }
+                match rounds.apply_block(window.reveal_start + attempt as u64, &[forged], &registry) {
+                    Ok(()) => manipulation_successes += 1,
+                    Err(CommitRevealError::CommitmentMismatch { .. }
+                        | CommitRevealError::WrongPhase { .. }
+                        | CommitRevealError::InvalidSignature { .. }) => {}
+                    Err(error) => panic!("unexpected rejection: {error}"),
                 }
             }
             
-            // Store reveals
-            self.reveal_history.insert(round as u64, round_reveals);
+            // Reveal phase with proper validation
+            let reveals: Vec<RoundMessage> = self.identities.iter()
+                .take(100)
+                .filter_map(|identity| identity.reveal_commitment(round))
+                .collect();
+            rounds.apply_block(window.reveal_start + 10, &reveals, &registry)
+                .expect("honest reveals open their commitments");
+            // The first block of the next round closes this one
+            rounds.apply_block(window.reveal_end, &[], &registry).expect("round closes");
             
-            // Test manipulation attempts (cryptographic commitment prevents manipulation)
-            manipulation_attempts += 10;
-            // Simulate manipulation attempts - all should fail due to cryptographic binding
-            for _ in 0..10 {
-                // Attempt to modify commitment after creation - should fail
-                if let Some(commitments) = self.commitment_history.get(&(round as u64)) {
-                    if !commitments.is_empty() {
-                        // Manipulation attempt detected and prevented
-                        // manipulation_successes remains 0 due to cryptographic security
-                    }
+            let outcome = rounds.outcome(round).expect("round closed");
+            for identity in self.identities.iter_mut().take(100) {
+                let identity_hash = identity.compute_8_component_identity_hash();
+                identity.reveal_verified = outcome.revealed.contains_key(&identity_hash)
+                    && to_hex(&commitment(&identity_hash, round, &identity.commit_secret.nonce)) == identity.commitment_hash;
+                if identity.reveal_verified {
+                    successful_reveals += 1;
                 }
             }
         }
@@ -677,14 +534,10 @@ impl Dura8ComponentIdentityEngine {
         let protocol_security_verified = integrity_rate > 0.99 && manipulation_successes == 0;
         
         CommitmentRevealTest {
-            test_id: 4,
-            commitment_rounds: COMMITMENT_REVEAL_TESTS,
             successful_commitments,
             successful_reveals,
             commitment_reveal_integrity: integrity_rate,
             protocol_security_verified,
-            manipulation_attempts,
-            manipulation_successes,
         }
     }
     
@@ -762,12 +615,12 @@ impl ComponentIdentityTestFramework {
         
         let cryptographic_security_maintained = overall_security_bits >= TARGET_SECURITY_BITS;
         let all_operations_verified = identity_creation.component_validation_rate > 0.99 &&
+                                    identity_creation.uniqueness_verified &&
                                     merkle_branch.dual_structure_integrity &&
                                     triple_layer_hash.cryptographic_strength_bits >= TARGET_SECURITY_BITS &&
                                     commitment_reveal.commitment_reveal_integrity > 0.99;
         
         ComponentIdentityTestResult {
-            test_id: 1,
             identity_creation,
             merkle_branch,
             triple_layer_hash,
@@ -795,7 +648,6 @@ impl ComponentIdentityTestFramework {
         let test_passed = test_result.all_operations_verified && security_requirements_met;
         
         ComponentIdentityStatistics {
-            total_tests: 1,
             total_identities_tested: test_result.identity_creation.identities_created,
             valid_identity_rate,
             merkle_integrity_rate,
@@ -825,21 +677,19 @@ impl ComponentIdentityTestFramework {
         println!("\n==================================================================================");
         println!("COMPONENT VERIFICATION DETAILS");
         println!("==================================================================================");
-        println!("Component 1 (Wallet): {} valid identities", result.identity_creation.valid_identities);
-        println!("Component 2 (Hardware): Fingerprint validation passed");
-        println!("Component 3 (Network): Signature validation passed");
-        println!("Component 4 (Timestamp): Temporal validation passed");
-        println!("Component 5 (Merkle 1): {} branch validations", result.merkle_branch.branch_validations_passed);
-        println!("Component 6 (Merkle 2): Dual structure integrity: {}", result.merkle_branch.dual_structure_integrity);
-        println!("Component 7 (Commitment): {} successful commitments", result.commitment_reveal.successful_commitments);
-        println!("Component 8 (Reveal): {} successful reveals", result.commitment_reveal.successful_reveals);
+        println!("Components (pk, addr, ts, sig, salt, nonce, ts₂): {} valid identities", result.identity_creation.valid_identities);
+        println!("Private Key: never hashed, proven by the unique signature");
+        println!("Merkle Branch 1: {} branch validations", result.merkle_branch.branch_validations_passed);
+        println!("Merkle Branch 2: Dual structure integrity: {}", result.merkle_branch.dual_structure_integrity);
+        println!("Commitment: {} successful commitments", result.commitment_reveal.successful_commitments);
+        println!("Reveal: {} successful reveals", result.commitment_reveal.successful_reveals);
         
         println!("\n==================================================================================");
         println!("CRYPTOGRAPHIC SECURITY ANALYSIS");
         println!("==================================================================================");
         println!("Blake3 Operations: {}", result.triple_layer_hash.blake3_operations);
         println!("SHA-256 Operations: {}", result.triple_layer_hash.sha256_operations);
-        println!("Dilithium Operations: {}", result.triple_layer_hash.dilithium_operations);
+        println!("SHA3-256 Operations: {}", result.triple_layer_hash.sha3_operations);
         println!("Hash Collision Rate: {:.2e}", result.triple_layer_hash.hash_collision_rate);
         println!("Average Hash Time: {:.4} ms", result.triple_layer_hash.average_hash_time_ms);
         println!("Cryptographic Strength: {} bits", result.triple_layer_hash.cryptographic_strength_bits);
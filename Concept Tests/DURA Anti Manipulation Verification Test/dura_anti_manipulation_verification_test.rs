@@ -3,137 +3,69 @@
 // CEO: Kauffmen Ceb
 // Objective: Prove attack cost ≥ O(2^128), benefit → 0
 
-use std::collections::{HashMap, HashSet, BTreeMap};
+use std::collections::HashSet;
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::thread;
-use std::sync::{Arc, Mutex};
+use i_protocol_core::commit_reveal::{CommitReveal, CommitRevealError, CommitSecret, RoundMessage, RoundWindow};
+use i_protocol_core::hash::{to_hex, triple_layer_hash, HashDomain};
+use i_protocol_core::registry::MinerRegistry;
+use i_protocol_core::signing::Ed25519Signer;
+use i_protocol_core::MinerIdentity;
 
 // Anti-Manipulation Test Constants
 const VIRTUAL_MINERS: usize = 200;
-const HASH_GRINDING_ATTEMPTS: usize = 1000000; // 1M attempts per attack
+const HASH_GRINDING_ATTEMPTS: usize = 10000; // Signed reveals per attack
 const SYBIL_IDENTITY_ATTEMPTS: usize = 10000;
 const SEED_MANIPULATION_TESTS: usize = 1000;
-const COMPUTATIONAL_COST_SAMPLES: usize = 100000;
 const PROTOCOL_SALT: &str = "I_PROTOCOL_DURA_ANTI_MANIPULATION_2024";
 const TARGET_SECURITY_BITS: u32 = 128; // O(2^128) security requirement
-const NONCES_PER_MINER: u64 = 250000;
-const COMMITMENT_REVEAL_ROUNDS: usize = 100;
 
 #[derive(Debug, Clone)]
-struct MinerIdentity {
+struct VirtualMiner {
     miner_id: u32,
-    wallet_address: String,
-    hardware_fingerprint: String,
-    network_signature: String,
-    timestamp: u64,
-    merkle_branch_1: String,
-    merkle_branch_2: String,
-    commitment_hash: String,
-    reveal_hash: String,
-    creation_cost: f64, // Computational cost to create identity
-    is_active: bool,
+    identity: MinerIdentity,
 }
 
-impl MinerIdentity {
+impl VirtualMiner {
     fn new(id: u32, timestamp: u64) -> Self {
-        MinerIdentity {
-            miner_id: id,
-            wallet_address: format!("wallet_{:08x}", id),
-            hardware_fingerprint: format!("hw_{:016x}", (id as u64).wrapping_mul(0x123456789ABCDEF)),
-            network_signature: format!("net_sig_{:08x}", id),
+        // Created and submitted at `timestamp`, signed over those timestamps
+        let template = MinerIdentity::virtual_miner(id, timestamp);
+        let identity = MinerIdentity::new(
+            &MinerIdentity::virtual_signer(id),
+            template.wallet_address,
+            timestamp,
+            template.salt,
+            template.identity_nonce,
             timestamp,
-            merkle_branch_1: format!("merkle_1_{:08x}", id),
-            merkle_branch_2: format!("merkle_2_{:08x}", id),
-            commitment_hash: format!("commit_{:016x}", (id as u64).wrapping_mul(0xFEDCBA9876543210)),
-            reveal_hash: String::new(),
-            creation_cost: 1.0, // Base cost
-            is_active: true,
-        }
-    }
-
-    fn compute_8_component_identity_hash(&self) -> String {
-        // 8-Component Identity: wallet ‖ hw_fingerprint ‖ net_sig ‖ timestamp ‖ merkle_1 ‖ merkle_2 ‖ commit ‖ reveal
-        let combined = format!(
-            "{}{}{}{}{}{}{}{}",
-            self.wallet_address,
-            self.hardware_fingerprint,
-            self.network_signature,
-            self.timestamp,
-            self.merkle_branch_1,
-            self.merkle_branch_2,
-            self.commitment_hash,
-            self.reveal_hash
         );
         
-        // Triple-layer hash: Blake3 → SHA-256 → Dilithium (simplified)
-        let layer1 = self.blake3_simulation(&combined);
-        let layer2 = self.sha256_simulation(&format!("{}", layer1));
-        let layer3 = self.dilithium_simulation(&format!("{}", layer2));
-        
-        format!("{:016x}", layer3)
-    }
-
-    fn create_commitment(&mut self, round: u64) -> String {
-        // Commitment = H₃(identity_hash ‖ round ‖ secret_nonce)
-        let secret_nonce = self.generate_secret_nonce(round);
-        let identity_hash = self.compute_8_component_identity_hash();
-        let commitment_input = format!("{}{}{}", identity_hash, round, secret_nonce);
-        
-        self.commitment_hash = format!("{:016x}", self.blake3_simulation(&commitment_input));
-        self.commitment_hash.clone()
-    }
-
-    fn reveal_commitment(&mut self, round: u64) -> String {
-        // Reveal = identity_hash ‖ secret_nonce (proves commitment)
-        let secret_nonce = self.generate_secret_nonce(round);
-        let identity_hash = self.compute_8_component_identity_hash();
-        self.reveal_hash = format!("{}{}", identity_hash, secret_nonce);
-        self.reveal_hash.clone()
+        VirtualMiner {
+            miner_id: id,
+            identity,
+        }
     }
 
-    fn generate_secret_nonce(&self, round: u64) -> u64 {
-        // Secret nonce derived from miner identity and round
-        let input = format!("{}{}{}", self.miner_id, self.timestamp, round);
-        self.blake3_simulation(&input)
+    fn compute_8_component_identity_hash(&self) -> String {
+        // ID_Hash = H₃(pk ‖ addr ‖ ts ‖ sig ‖ salt ‖ nonce ‖ ts₂)
+        self.identity.identity_hash()
     }
 
-    // Simplified cryptographic hash functions for testing
-    fn blake3_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash ^ 0x123456789ABCDEF0
+    fn signer(&self) -> Ed25519Signer {
+        MinerIdentity::virtual_signer(self.miner_id)
     }
 
-    fn sha256_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 14695981039346656037;
-        for byte in input.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        hash ^ 0xFEDCBA9876543210
-    }
-
-    fn dilithium_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 0;
-        for byte in input.bytes() {
-            hash = (byte as u64).wrapping_add(hash << 6).wrapping_add(hash << 16).wrapping_sub(hash);
-        }
-        hash ^ 0x0123456789ABCDEF
+    // Commitment = H₃(identity_hash ‖ round ‖ nonce) over a nonce drawn from
+    // the OS-seeded thread RNG; nothing public predicts it
+    fn create_commitment(&self, round: u64) -> (CommitSecret, RoundMessage) {
+        let secret = CommitSecret::generate(&self.compute_8_component_identity_hash(), round, &mut rand::thread_rng());
+        let message = secret.commit_message(&self.signer());
+        (secret, message)
     }
 }
 
 #[derive(Debug, Clone)]
 struct AttackAttempt {
-    attack_type: String,
-    attacker_id: u32,
-    target_position: usize,
     computational_cost: f64,
-    success_probability: f64,
     attack_benefit: f64,
-    hash_attempts: usize,
-    time_elapsed_ms: u64,
     attack_successful: bool,
 }
 
@@ -141,51 +73,38 @@ struct AttackAttempt {
 struct SybilResistanceTest {
     identity_creation_attempts: usize,
     unique_identities_created: usize,
-    duplicate_detection_rate: f64,
-    creation_cost_per_identity: f64,
     total_computational_cost: f64,
     sybil_attack_success_rate: f64,
-    resistance_verified: bool,
 }
 
 #[derive(Debug, Clone)]
 struct SeedManipulationTest {
     manipulation_attempts: usize,
-    network_control_percentage: f64,
     successful_manipulations: usize,
-    required_network_control: f64,
     manipulation_cost: f64,
     manipulation_benefit: f64,
-    manipulation_prevented: bool,
 }
 
 #[derive(Debug, Clone)]
 struct ComputationalCostAnalysis {
-    hash_grinding_cost: f64,
-    sybil_creation_cost: f64,
-    seed_manipulation_cost: f64,
     total_attack_cost: f64,
     expected_benefit: f64,
-    cost_benefit_ratio: f64,
     attack_economically_viable: bool,
 }
 
 #[derive(Debug)]
 struct AntiManipulationTestResult {
-    test_id: usize,
     hash_grinding_attacks: Vec<AttackAttempt>,
     sybil_resistance: SybilResistanceTest,
     seed_manipulation: SeedManipulationTest,
     computational_analysis: ComputationalCostAnalysis,
     security_bits_achieved: u32,
-    attack_cost_order: String, // O(2^n) notation
     benefit_approaches_zero: bool,
     anti_manipulation_verified: bool,
 }
 
 #[derive(Debug)]
 struct AntiManipulationStatistics {
-    total_tests: usize,
     hash_grinding_attempts: usize,
     successful_hash_grinds: usize,
     sybil_identities_attempted: usize,
@@ -198,125 +117,110 @@ struct AntiManipulationStatistics {
     attack_success_rate: f64,
     economic_viability_rate: f64,
     anti_manipulation_success_rate: f64,
-    mathematical_proof_validated: bool,
     test_passed: bool,
 }
 
 struct DuraAntiManipulationEngine {
-    miner_identities: Vec<MinerIdentity>,
-    commitment_history: HashMap<u64, Vec<String>>, // round -> commitments
-    reveal_history: HashMap<u64, Vec<String>>, // round -> reveals
+    miner_identities: Vec<VirtualMiner>,
 }
 
 impl DuraAntiManipulationEngine {
-    fn new(miners: Vec<MinerIdentity>) -> Self {
+    fn new(miners: Vec<VirtualMiner>) -> Self {
         DuraAntiManipulationEngine {
             miner_identities: miners,
-            commitment_history: HashMap::new(),
-            reveal_history: HashMap::new(),
         }
     }
     
-    fn verify_commitment_reveal(&self, commitment: &str, reveal: &str) -> bool {
-        // Verify that reveal matches the commitment
-        // In real implementation, this would verify cryptographic commitment
-        let reveal_hash = format!("{:016x}", self.djb2_hash(reveal));
-        commitment == &reveal_hash
-    }
-    
-    fn validate_8_component_identity(&self, identity: &MinerIdentity) -> bool {
-        // Validate all 8 components of identity are unique and valid
-        // In real implementation, this would check:
-        // 1. Wallet address uniqueness and validity
-        // 2. Hardware fingerprint uniqueness
-        // 3. Network signature validity
-        // 4. Timestamp recency and validity
-        // 5. Merkle branch 1 validity
-        // 6. Merkle branch 2 validity
-        // 7. Commitment hash validity
-        // 8. Reveal hash validity
-        
-        // Simplified validation: check for basic uniqueness
-        let components = vec![
-            &identity.wallet_address,
-            &identity.hardware_fingerprint,
-            &identity.network_signature,
-            &identity.merkle_branch_1,
-            &identity.merkle_branch_2,
-            &identity.commitment_hash,
-        ];
-        
-        // Each component must be unique and non-empty
-        for component in &components {
-            if component.is_empty() || component.len() < 8 {
-                return false;
-            }
+    fn validate_8_component_identity(&self, miner: &VirtualMiner) -> bool {
+        // Per-component validators from the protocol identity: public key,
+        // wallet address, timestamps, unique signature (the private key's
+        // proof) and salt
+        if miner.identity.validate().is_err() {
+            return false;
         }
         
         // Additional validation: timestamp must be recent
         let current_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        if identity.timestamp > current_time || (current_time - identity.timestamp) > 3600 {
+        let created = miner.identity.creation_timestamp;
+        if created > current_time || (current_time - created) > 3600 {
             return false; // Timestamp too old or in future
         }
         
         // Simplified: only 1% of identities pass all validations (simulates real-world difficulty)
-        (identity.miner_id % 100) == 0
+        miner.miner_id.is_multiple_of(100)
     }
 
     fn simulate_hash_grinding_attack(&mut self, target_position: usize) -> AttackAttempt {
-        let start_time = SystemTime::now();
         let attacker_id = 999999; // Dedicated attacker ID
         let mut hash_attempts = 0;
-        let mut attack_successful = false;
         
-        // Commitment/Reveal Protocol prevents hash grinding
-        // Attacker must commit identity hash in Round N-1, reveal in Round N
-        // This eliminates the ability to grind hashes after seeing target
-        
-        // Simulate realistic hash grinding with commitment scheme protection
-        for attempt in 0..HASH_GRINDING_ATTEMPTS {
+        // Commitment/Reveal Protocol prevents hash grinding: every miner
+        // commits in the commit phase, so once the honest reveals are public
+        // the attacker can only open its own commitment or withhold it
+        let attacker = VirtualMiner::new(attacker_id, 
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs());
+        let registry = MinerRegistry::with_genesis(
+            self.miner_identities.iter().chain([&attacker]).map(|miner| miner.identity.clone())
+        ).expect("genesis identities are valid and distinct");
+        let round = 1;
+        let window = RoundWindow::new(round);
+        let mut rounds = CommitReveal::new();
+        
+        let (secrets, mut commits): (Vec<CommitSecret>, Vec<RoundMessage>) = self.miner_identities.iter()
+            .map(|miner| miner.create_commitment(round))
+            .unzip();
+        let (attacker_secret, attacker_commit) = attacker.create_commitment(round);
+        commits.push(attacker_commit);
+        rounds.apply_block(window.commit_start, &commits, &registry).expect("signed commitments of active miners");
+        
+        let reveals: Vec<RoundMessage> = secrets.iter().zip(&self.miner_identities)
+            .map(|(secret, miner)| secret.reveal_message(&miner.signer()))
+            .collect();
+        rounds.apply_block(window.reveal_start, &reveals, &registry).expect("honest reveals open their commitments");
+        
+        // Reveal phase: with every honest nonce in view, the attacker grinds
+        // fresh nonces and submits each, signed, in place of its commitment
+        let mut ground_nonce_accepted = false;
+        for _ in 0..HASH_GRINDING_ATTEMPTS {
             hash_attempts += 1;
-            
-            // Create identity with commitment/reveal protection
-            let mut attacker = MinerIdentity::new(attacker_id, 
-                SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs());
-            
-            // Commitment phase: attacker must commit without knowing target
-            let commitment = attacker.create_commitment(1);
-            
-            // Reveal phase: attacker reveals identity (cannot change after commitment)
-            let reveal = attacker.reveal_commitment(1);
-            
-            // Verify commitment/reveal integrity (prevents grinding)
-            if !self.verify_commitment_reveal(&commitment, &reveal) {
-                continue; // Invalid commitment/reveal
-            }
-            
-            let identity_hash = attacker.compute_8_component_identity_hash();
-            let position = self.simulate_position_from_hash(&identity_hash);
-            
-            // With commitment/reveal, attacker cannot target specific positions
-            // Success probability approaches 1/n (uniform distribution)
-            if position == target_position && attempt < 10 { // Extremely low probability
-                attack_successful = true;
-                break;
+            let candidate = CommitSecret::generate(&attacker_secret.identity_hash, round, &mut rand::thread_rng());
+            let reveal = candidate.reveal_message(&attacker.signer());
+            match rounds.apply_block(window.reveal_start + 1, &[reveal], &registry) {
+                Ok(()) => {
+                    ground_nonce_accepted = candidate.nonce != attacker_secret.nonce;
+                    break;
+                }
+                Err(CommitRevealError::CommitmentMismatch { .. }) => {}
+                Err(error) => panic!("unexpected rejection: {error}"),
             }
         }
         
-        let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
+        // Its remaining choice is to reveal or withhold; withholding must
+        // cost it the following rounds
+        let withhold = target_position % 2 == 1;
+        if !withhold && !ground_nonce_accepted {
+            rounds.apply_block(window.reveal_start + 1, &[attacker_secret.reveal_message(&attacker.signer())], &registry)
+                .expect("committed nonce opens the commitment");
+        }
+        rounds.apply_block(window.reveal_end, &[], &registry).expect("round closes");
+        let identity_hash = attacker.compute_8_component_identity_hash();
+        let withholding_free = withhold && !rounds.is_excluded(&identity_hash, round + 1);
+        
+        // DURA position among the revealed miners, under the round's master
+        // seed keyed by the hash of the block closing the round
+        let outcome = rounds.outcome(round).expect("round closed");
+        let previous_hash = to_hex(&triple_layer_hash(HashDomain::BlockHeader, format!("block_{}", window.reveal_end).as_bytes()));
+        let position = outcome.assign_ranges(&previous_hash).ranges.iter()
+            .find(|range| range.identity_hash == identity_hash)
+            .map(|range| range.position);
+        let attack_successful = withholding_free || (ground_nonce_accepted && position == Some(target_position));
+        
         let computational_cost = (hash_attempts as f64) * 1.0; // Realistic cost per hash
-        let success_probability = if attack_successful { 1.0 / VIRTUAL_MINERS as f64 } else { 0.0 };
         let attack_benefit = if attack_successful { 0.1 } else { 0.0 }; // Minimal benefit due to equal rewards
         
         AttackAttempt {
-            attack_type: "Hash Grinding (Commitment Protected)".to_string(),
-            attacker_id,
-            target_position,
             computational_cost,
-            success_probability,
             attack_benefit,
-            hash_attempts,
-            time_elapsed_ms: elapsed,
             attack_successful,
         }
     }
@@ -328,17 +232,17 @@ impl DuraAntiManipulationEngine {
         let creation_cost_per_identity = 1000.0; // High cost for 8-component identity creation
         
         // 8-Component Identity Requirements:
-        // 1. Unique wallet address (requires real wallet)
-        // 2. Hardware fingerprint (requires unique hardware)
-        // 3. Network signature (requires network participation)
-        // 4. Timestamp (must be recent and valid)
-        // 5. Merkle branch 1 (requires blockchain participation)
-        // 6. Merkle branch 2 (requires dual verification)
-        // 7. Commitment hash (requires cryptographic commitment)
-        // 8. Reveal hash (requires reveal protocol participation)
+        // 1. Private key (proven through the unique signature)
+        // 2. Public key (matching a supported signature scheme)
+        // 3. Wallet address (requires real wallet)
+        // 4. Creation timestamp (must be recent and valid)
+        // 5. Unique signature (made with the private key)
+        // 6. Salt (fresh randomness per identity)
+        // 7. Identity nonce
+        // 8. Submission timestamp (not before creation)
         
         for i in 0..SYBIL_IDENTITY_ATTEMPTS {
-            let sybil_miner = MinerIdentity::new(
+            let sybil_miner = VirtualMiner::new(
                 1000000 + i as u32,
                 SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
             );
@@ -357,20 +261,13 @@ impl DuraAntiManipulationEngine {
         }
         
         // Sybil resistance: Creating multiple valid identities is extremely expensive
-        let duplicate_detection_rate = 1.0 - (valid_identities as f64 / SYBIL_IDENTITY_ATTEMPTS as f64);
         let sybil_success_rate = valid_identities as f64 / SYBIL_IDENTITY_ATTEMPTS as f64;
         
-        // Strong Sybil resistance due to 8-component requirements
-        let resistance_verified = duplicate_detection_rate > 0.99 && sybil_success_rate < 0.01;
-        
         SybilResistanceTest {
             identity_creation_attempts: SYBIL_IDENTITY_ATTEMPTS,
             unique_identities_created: valid_identities,
-            duplicate_detection_rate,
-            creation_cost_per_identity,
             total_computational_cost: total_cost,
             sybil_attack_success_rate: sybil_success_rate,
-            resistance_verified,
         }
     }
 
@@ -389,9 +286,6 @@ impl DuraAntiManipulationEngine {
         for test in 0..SEED_MANIPULATION_TESTS {
             let network_control = (test as f64 / SEED_MANIPULATION_TESTS as f64) * 100.0;
             
-            // Simulate master seed generation with commitment/reveal protection
-            let original_seed = self.generate_master_seed_simulation();
-            
             // Attempt manipulation - requires breaking cryptographic assumptions
             if network_control >= required_network_control {
                 // Even with 51% control, manipulation requires breaking cryptography:
@@ -426,16 +320,12 @@ impl DuraAntiManipulationEngine {
         
         let total_cost = SEED_MANIPULATION_TESTS as f64 * manipulation_cost_per_attempt;
         let manipulation_benefit = 0.0; // No benefit due to equal reward distribution
-        let manipulation_prevented = true; // Cryptographically guaranteed
         
         SeedManipulationTest {
             manipulation_attempts: SEED_MANIPULATION_TESTS,
-            network_control_percentage: required_network_control,
             successful_manipulations,
-            required_network_control,
             manipulation_cost: total_cost,
             manipulation_benefit,
-            manipulation_prevented,
         }
     }
 
@@ -458,12 +348,8 @@ impl DuraAntiManipulationEngine {
         let attack_economically_viable = cost_benefit_ratio < 1.0;
         
         ComputationalCostAnalysis {
-            hash_grinding_cost,
-            sybil_creation_cost,
-            seed_manipulation_cost,
             total_attack_cost,
             expected_benefit,
-            cost_benefit_ratio,
             attack_economically_viable,
         }
     }
@@ -484,19 +370,13 @@ impl DuraAntiManipulationEngine {
         // SHA-256: 256-bit security against all known attacks
         // Dilithium: 128-bit post-quantum security (NIST Level 3)
         
-        let blake3_security = 256;
-        let sha256_security = 256;
         let dilithium_security = 128; // Post-quantum secure
         
         // Security is determined by weakest link, but layered defense provides additional protection
         let base_cryptographic_security = dilithium_security; // 128-bit minimum
         
-        // Additional security layers:
-        let commitment_reveal_security = 64; // Prevents hash grinding
-        let eight_component_security = 32; // Prevents Sybil attacks
-        let chacha20_security = 256; // Deterministic shuffling security
-        
-        // Combined security (conservative estimate)
+        // Combined security (conservative estimate): commitment/reveal,
+        // 8-component identities and ChaCha20 shuffling add no bits on top
         let combined_security = base_cryptographic_security;
         
         // If no successful attacks, full security is achieved
@@ -515,26 +395,6 @@ impl DuraAntiManipulationEngine {
             calculated_bits.min(TARGET_SECURITY_BITS).max(combined_security)
         }
     }
-
-    fn simulate_position_from_hash(&self, identity_hash: &str) -> usize {
-        // Simulate position assignment from identity hash
-        let hash_value = self.djb2_hash(identity_hash);
-        (hash_value as usize) % VIRTUAL_MINERS
-    }
-
-    fn generate_master_seed_simulation(&self) -> String {
-        // Simplified master seed generation for testing
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        format!("master_seed_{:016x}", self.djb2_hash(&format!("{}{}", timestamp, PROTOCOL_SALT)))
-    }
-
-    fn djb2_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash
-    }
 }
 
 struct AntiManipulationTestFramework {
@@ -549,11 +409,11 @@ impl AntiManipulationTestFramework {
         AntiManipulationTestFramework { engine }
     }
 
-    fn generate_virtual_miners() -> Vec<MinerIdentity> {
+    fn generate_virtual_miners() -> Vec<VirtualMiner> {
         let base_timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
         
         (0..VIRTUAL_MINERS)
-            .map(|i| MinerIdentity::new(i as u32, base_timestamp + i as u64))
+            .map(|i| VirtualMiner::new(i as u32, base_timestamp + i as u64))
             .collect()
     }
 
@@ -587,19 +447,16 @@ impl AntiManipulationTestFramework {
         let success_rate = successful_attacks as f64 / total_attacks as f64;
         let security_bits = self.engine.calculate_security_bits(total_attacks, success_rate);
         
-        let attack_cost_order = format!("O(2^{})", security_bits);
         let benefit_approaches_zero = computational_analysis.expected_benefit < 1.0;
         let anti_manipulation_verified = security_bits >= TARGET_SECURITY_BITS && 
                                        !computational_analysis.attack_economically_viable;
         
         AntiManipulationTestResult {
-            test_id,
             hash_grinding_attacks,
             sybil_resistance,
             seed_manipulation,
             computational_analysis,
             security_bits_achieved: security_bits,
-            attack_cost_order,
             benefit_approaches_zero,
             anti_manipulation_verified,
         }
@@ -645,7 +502,6 @@ impl AntiManipulationTestFramework {
         let test_passed = mathematical_proof_validated && anti_manipulation_success_rate >= 1.0;
         
         AntiManipulationStatistics {
-            total_tests: 1,
             hash_grinding_attempts: total_hash_grinding,
             successful_hash_grinds,
             sybil_identities_attempted: sybil_attempts,
@@ -658,7 +514,6 @@ impl AntiManipulationTestFramework {
             attack_success_rate,
             economic_viability_rate,
             anti_manipulation_success_rate,
-            mathematical_proof_validated,
             test_passed,
         }
     }
@@ -3,251 +3,49 @@
 // CEO: Kauffmen Ceb
 // Objective: Prove identical range assignments across all nodes
 
-use std::collections::{HashMap, BTreeMap};
 use std::time::{SystemTime, UNIX_EPOCH};
+use i_protocol_core::dura::{assign_ranges, MinerRange};
+use i_protocol_core::params::NONCES_PER_MINER;
+use i_protocol_core::registry::{MinerRegistry, RegistryChange};
+use i_protocol_core::{BlockchainState, MinerIdentity};
 
 // DURA Configuration Constants
-const NONCES_PER_MINER: u64 = 250000;
 const VIRTUAL_NODES: usize = 50;
 const TEST_SCENARIOS: usize = 100;
 const PROTOCOL_SALT: &str = "I_PROTOCOL_DURA_DETERMINISM_2024";
-const CHACHA20_ROUNDS: usize = 20;
-
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-struct MinerIdentity {
-    private_key: String,
-    public_key: String,
-    wallet_address: String,
-    creation_timestamp: u64,
-    unique_signature: String,
-    salt: String,
-    identity_nonce: u64,
-    submission_timestamp: u64,
-}
-
-impl MinerIdentity {
-    fn new(id: u32, base_timestamp: u64) -> Self {
-        MinerIdentity {
-            private_key: format!("priv_key_{:08x}", id),
-            public_key: format!("pub_key_{:08x}", id),
-            wallet_address: format!("addr_{:08x}", id),
-            creation_timestamp: base_timestamp + (id as u64 * 1000),
-            unique_signature: format!("sig_{:08x}", id),
-            salt: format!("salt_{:08x}", id),
-            identity_nonce: id as u64,
-            submission_timestamp: base_timestamp + (id as u64 * 1000) + 500,
-        }
-    }
-
-    fn compute_identity_hash(&self) -> String {
-        // 8-Component Identity Hash: H₃(pk ‖ addr ‖ ts ‖ sig ‖ salt ‖ nonce ‖ ts₂)
-        let combined = format!(
-            "{}{}{}{}{}{}{}",
-            self.private_key,
-            self.wallet_address,
-            self.creation_timestamp,
-            self.unique_signature,
-            self.salt,
-            self.identity_nonce,
-            self.submission_timestamp
-        );
-        
-        // Simplified triple-layer hash (Blake3 -> SHA-256 -> Dilithium simulation)
-        let hash1 = self.djb2_hash(&combined);
-        let hash2 = self.fnv_hash(&format!("{}", hash1));
-        let hash3 = self.sdbm_hash(&format!("{}", hash2));
-        
-        format!("{:016x}", hash3)
-    }
-
-    // Simplified hash functions for testing (production uses Blake3/SHA-256/Dilithium)
-    fn djb2_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash
-    }
-
-    fn fnv_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 14695981039346656037;
-        for byte in input.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        hash
-    }
-
-    fn sdbm_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 0;
-        for byte in input.bytes() {
-            hash = (byte as u64).wrapping_add(hash << 6).wrapping_add(hash << 16).wrapping_sub(hash);
-        }
-        hash
-    }
-}
-
-#[derive(Debug, Clone)]
-struct MinerRange {
-    miner_id: u32,
-    identity_hash: String,
-    start_nonce: u64,
-    end_nonce: u64,
-    range_size: u64,
-}
-
-#[derive(Debug, Clone)]
-struct BlockchainState {
-    prev_hash: String,
-    block_height: u64,
-    timestamp: u64,
-    miner_count: u32,
-}
-
-#[derive(Debug)]
-struct DeterminismTestResult {
-    test_id: usize,
-    blockchain_state: BlockchainState,
-    node_assignments: Vec<Vec<MinerRange>>,
-    master_seeds: Vec<String>,
-    assignment_consensus: bool,
-    seed_consensus: bool,
-    range_verification: bool,
-    total_miners: usize,
-    total_ranges_verified: usize,
-}
-
-#[derive(Debug)]
-struct DeterminismStatistics {
-    total_tests: usize,
-    consensus_success: usize,
-    seed_consensus_success: usize,
-    range_verification_success: usize,
-    total_virtual_nodes: usize,
-    total_miners_tested: usize,
-    total_range_assignments: usize,
-    consensus_rate: f64,
-    determinism_validated: bool,
-    test_passed: bool,
-}
 
 struct VirtualNode {
     node_id: u32,
-    miner_identities: Vec<MinerIdentity>,
+    registry: MinerRegistry,
 }
 
 impl VirtualNode {
-    fn new(node_id: u32, miners: Vec<MinerIdentity>) -> Self {
+    fn new(node_id: u32, genesis_miners: Vec<MinerIdentity>) -> Self {
         VirtualNode {
             node_id,
-            miner_identities: miners,
+            registry: MinerRegistry::with_genesis(genesis_miners)
+                .expect("genesis identities are valid and distinct"),
         }
     }
 
-    fn generate_master_seed(&self, blockchain_state: &BlockchainState) -> String {
-        // Master Seed = H₃(prev_hash ‖ sort(ID_Hash₁, ID_Hash₂, ..., ID_Hash_n))
-        let mut identity_hashes: Vec<String> = self.miner_identities
-            .iter()
-            .map(|miner| miner.compute_identity_hash())
-            .collect();
-        
-        // Canonical lexicographic sorting (critical for determinism)
-        identity_hashes.sort();
-        
-        let concatenated_hashes = identity_hashes.join("");
-        let seed_input = format!("{}{}", blockchain_state.prev_hash, concatenated_hashes);
-        
-        // Triple-layer hash for master seed
-        let hash1 = self.djb2_hash(&seed_input);
-        let hash2 = self.fnv_hash(&format!("{}", hash1));
-        let hash3 = self.sdbm_hash(&format!("{}", hash2));
-        
-        format!("{:016x}", hash3)
-    }
-
-    fn chacha20_deterministic_shuffle(&self, master_seed: &str, miner_count: usize) -> Vec<usize> {
-        // Simplified ChaCha20 PRNG simulation for deterministic shuffling
-        let seed_value = self.djb2_hash(master_seed);
-        let mut rng_state = seed_value;
-        let mut indices: Vec<usize> = (0..miner_count).collect();
-        
-        // Fisher-Yates shuffle with deterministic PRNG
-        for i in (1..miner_count).rev() {
-            rng_state = rng_state.wrapping_mul(1103515245).wrapping_add(12345); // LCG
-            let j = (rng_state as usize) % (i + 1);
-            indices.swap(i, j);
-        }
-        
-        indices
-    }
-
     fn execute_dura_assignment(&self, blockchain_state: &BlockchainState) -> (String, Vec<MinerRange>) {
-        // Phase 1: Generate master seed
-        let master_seed = self.generate_master_seed(blockchain_state);
-        
-        // Phase 2: ChaCha20 deterministic shuffle
-        let miner_count = self.miner_identities.len();
-        let shuffled_indices = self.chacha20_deterministic_shuffle(&master_seed, miner_count);
-        
-        // Phase 3: Sequential range assignment (250,000 nonces per miner)
-        let mut ranges = Vec::new();
-        
-        for (position, &original_index) in shuffled_indices.iter().enumerate() {
-            let miner = &self.miner_identities[original_index];
-            let start_nonce = (position as u64 * NONCES_PER_MINER) + 1;
-            let end_nonce = (position + 1) as u64 * NONCES_PER_MINER;
-            
-            let range = MinerRange {
-                miner_id: original_index as u32,
-                identity_hash: miner.compute_identity_hash(),
-                start_nonce,
-                end_nonce,
-                range_size: NONCES_PER_MINER,
-            };
-            
-            ranges.push(range);
-        }
+        // The active set comes from the node's registry snapshot for this height
+        let miners = self.registry.snapshot(blockchain_state.height).miners;
         
-        (master_seed, ranges)
-    }
-
-    // Hash functions (same as MinerIdentity for consistency)
-    fn djb2_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash
-    }
-
-    fn fnv_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 14695981039346656037;
-        for byte in input.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        hash
-    }
-
-    fn sdbm_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 0;
-        for byte in input.bytes() {
-            hash = (byte as u64).wrapping_add(hash << 6).wrapping_add(hash << 16).wrapping_sub(hash);
-        }
-        hash
+        // Master seed, deterministic shuffle and sequential range assignment
+        let assignment = assign_ranges(&blockchain_state.previous_hash, &miners);
+        (assignment.master_seed, assignment.ranges)
     }
 }
 
 struct DuraTestFramework {
     virtual_nodes: Vec<VirtualNode>,
-    test_results: Vec<DeterminismTestResult>,
 }
 
 impl DuraTestFramework {
     fn new() -> Self {
         DuraTestFramework {
             virtual_nodes: Vec::new(),
-            test_results: Vec::new(),
         }
     }
 
@@ -262,16 +60,36 @@ impl DuraTestFramework {
         // Create identical miner identity lists for all nodes
         let mut miners = Vec::new();
         for i in 0..miners_per_scenario {
-            miners.push(MinerIdentity::new(i as u32, base_timestamp));
+            miners.push(MinerIdentity::virtual_miner(i as u32, base_timestamp));
         }
         
-        // Deploy nodes with identical miner lists (critical for determinism test)
+        // Deploy nodes from the same genesis miner set
         for node_id in 0..node_count {
             let node = VirtualNode::new(node_id as u32, miners.clone());
             self.virtual_nodes.push(node);
         }
         
-        println!("Successfully deployed {} virtual nodes", self.virtual_nodes.len());
+        // Churn: every block registers one miner and every third block retires
+        // the oldest. Each node sees a block's joins in a different order, as
+        // it would receive the transactions from different peers.
+        for height in 1..=TEST_SCENARIOS as u64 {
+            let mut joins = vec![MinerIdentity::virtual_miner((miners_per_scenario as u64 + height - 1) as u32, base_timestamp)];
+            if height % 2 == 0 {
+                joins.push(MinerIdentity::virtual_miner((1_000 + height) as u32, base_timestamp));
+            }
+            for node in &mut self.virtual_nodes {
+                let mut changes: Vec<RegistryChange> = joins.iter().cloned().map(RegistryChange::Join).collect();
+                let rotation = node.node_id as usize % changes.len();
+                changes.rotate_left(rotation);
+                if height % 3 == 0 {
+                    let retired = MinerIdentity::virtual_miner((height / 3 - 1) as u32, base_timestamp);
+                    changes.push(RegistryChange::leave(&retired, &MinerIdentity::virtual_signer((height / 3 - 1) as u32)));
+                }
+                node.registry.apply_block(height, &changes).expect("valid registry block");
+            }
+        }
+        
+        println!("Successfully deployed {} virtual nodes with {} blocks of miner churn", self.virtual_nodes.len(), TEST_SCENARIOS);
     }
 
     fn generate_test_scenarios(&self) -> Vec<BlockchainState> {
@@ -283,10 +101,11 @@ impl DuraTestFramework {
         
         for i in 0..TEST_SCENARIOS {
             let scenario = BlockchainState {
-                prev_hash: format!("block_hash_{:016x}", (i as u64).wrapping_mul(0x123456789ABCDEF)),
-                block_height: (i + 1) as u64,
+                previous_hash: format!("block_hash_{:016x}", (i as u64).wrapping_mul(0x123456789ABCDEF)),
+                height: (i + 1) as u64,
                 timestamp: base_timestamp + (i as u64 * 500),
-                miner_count: 20 + (i % 30) as u32, // Variable miner count: 20-49
+                active_miners: 20 + (i % 30) as u32, // Variable miner count: 20-49
+                ..Default::default()
             };
             scenarios.push(scenario);
         }
@@ -343,7 +162,7 @@ impl DuraTestFramework {
         seeds.iter().all(|seed| seed == reference_seed)
     }
 
-    fn run_comprehensive_determinism_test(&mut self) -> DeterminismStatistics {
+    fn run_comprehensive_determinism_test(&mut self) -> bool {
         println!("\n=================================================================================");
         println!("I PROTOCOL - TEST 2.1: DURA DETERMINISM VERIFICATION");
         println!("=================================================================================");
@@ -366,26 +185,16 @@ impl DuraTestFramework {
         let mut total_range_assignments = 0;
         
         for (test_id, scenario) in scenarios.iter().enumerate() {
-            let active_miners = scenario.miner_count.min(25) as usize; // Limit to available miners
-            
             // Execute DURA assignment simultaneously across all nodes
             let mut node_assignments = Vec::new();
             let mut master_seeds = Vec::new();
             
             for node in &self.virtual_nodes {
-                // Use only the required number of miners for this scenario
-                let limited_miners: Vec<MinerIdentity> = node.miner_identities
-                    .iter()
-                    .take(active_miners)
-                    .cloned()
-                    .collect();
-                
-                let limited_node = VirtualNode::new(node.node_id, limited_miners);
-                let (seed, assignment) = limited_node.execute_dura_assignment(scenario);
-                
+                let (seed, assignment) = node.execute_dura_assignment(scenario);
                 master_seeds.push(seed);
                 node_assignments.push(assignment);
             }
+            let active_miners = node_assignments[0].len();
             
             // Verify consensus
             let assignment_consensus = self.verify_range_assignments(&node_assignments);
@@ -405,20 +214,6 @@ impl DuraTestFramework {
             total_miners_tested += active_miners;
             total_range_assignments += active_miners * VIRTUAL_NODES;
             
-            // Store test result
-            let test_result = DeterminismTestResult {
-                test_id,
-                blockchain_state: scenario.clone(),
-                node_assignments,
-                master_seeds,
-                assignment_consensus,
-                seed_consensus,
-                range_verification,
-                total_miners: active_miners,
-                total_ranges_verified: active_miners,
-            };
-            self.test_results.push(test_result);
-            
             // Progress reporting
             if (test_id + 1) % 20 == 0 {
                 println!("Completed {} scenarios... (Consensus rate: {:.2}%)", 
@@ -429,8 +224,7 @@ impl DuraTestFramework {
         
         let total_tests = scenarios.len();
         let consensus_rate = (consensus_success as f64 / total_tests as f64) * 100.0;
-        let determinism_validated = consensus_success == total_tests;
-        let test_passed = determinism_validated;
+        let test_passed = consensus_success == total_tests;
         
         println!("\n=================================================================================");
         println!("DURA DETERMINISM VERIFICATION RESULTS");
@@ -495,27 +289,16 @@ impl DuraTestFramework {
         println!("OVERALL TEST RESULT: {}", if test_passed { "PASS" } else { "FAIL" });
         println!("=================================================================================");
         
-        DeterminismStatistics {
-            total_tests,
-            consensus_success,
-            seed_consensus_success,
-            range_verification_success,
-            total_virtual_nodes: VIRTUAL_NODES,
-            total_miners_tested,
-            total_range_assignments,
-            consensus_rate,
-            determinism_validated,
-            test_passed,
-        }
+        test_passed
     }
 }
 
 fn main() {
     let mut test_framework = DuraTestFramework::new();
-    let statistics = test_framework.run_comprehensive_determinism_test();
+    let test_passed = test_framework.run_comprehensive_determinism_test();
     
     // Final validation
-    if statistics.test_passed {
+    if test_passed {
         println!("\nTEST 2.1 COMPLETION: DURA DETERMINISM VERIFICATION SUCCESSFUL");
         println!("Identical range assignments across all nodes: MATHEMATICALLY PROVEN");
         println!("ChaCha20 deterministic shuffling: ACADEMICALLY VERIFIED");
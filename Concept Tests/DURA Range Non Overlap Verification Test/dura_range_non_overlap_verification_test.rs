@@ -3,276 +3,65 @@
 // CEO: Kauffmen Ceb
 // Objective: Mathematically prove ∀i≠j: Range_i ∩ Range_j = ∅
 
-use std::collections::{HashMap, BTreeMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
+use i_protocol_core::bootstrap::{BootstrapController, NetworkPhase};
+use i_protocol_core::dura::{assign_ranges, MinerRange};
+use i_protocol_core::layout::{NonceRange, RangeLayout};
+use i_protocol_core::params::{NONCES_PER_MINER, REGISTRY_ACTIVATION_DELAY, SYSTEM_MINER_RANGE_END, SYSTEM_MINER_RANGE_START};
+use i_protocol_core::registry::RegistryChange;
+use i_protocol_core::{BlockchainState, MinerIdentity};
 
 // DURA Configuration Constants
-const NONCES_PER_MINER: u64 = 250000;
 const VIRTUAL_MINERS: usize = 1000;
 const TEST_SCENARIOS: usize = 500;
 const EDGE_CASE_SCENARIOS: usize = 100;
 const PROTOCOL_SALT: &str = "I_PROTOCOL_DURA_NON_OVERLAP_2024";
 const MAX_DYNAMIC_MINERS: usize = 1500;
 const MIN_DYNAMIC_MINERS: usize = 100;
+const BLOCKS_PER_REGISTRATION: u64 = 10;
+const MINERS_PER_REGISTRATION: usize = 10;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-struct MinerIdentity {
-    private_key: String,
-    public_key: String,
-    wallet_address: String,
-    creation_timestamp: u64,
-    unique_signature: String,
-    salt: String,
-    identity_nonce: u64,
-    submission_timestamp: u64,
+#[derive(Debug, Clone)]
+struct VirtualMiner {
+    identity: MinerIdentity,
     is_active: bool,
 }
 
-impl MinerIdentity {
+impl VirtualMiner {
     fn new(id: u32, base_timestamp: u64) -> Self {
-        MinerIdentity {
-            private_key: format!("priv_key_{:08x}", id),
-            public_key: format!("pub_key_{:08x}", id),
-            wallet_address: format!("addr_{:08x}", id),
-            creation_timestamp: base_timestamp + (id as u64 * 1000),
-            unique_signature: format!("sig_{:08x}", id),
-            salt: format!("salt_{:08x}", id),
-            identity_nonce: id as u64,
-            submission_timestamp: base_timestamp + (id as u64 * 1000) + 500,
+        VirtualMiner {
+            identity: MinerIdentity::virtual_miner(id, base_timestamp),
             is_active: true,
         }
     }
-
-    fn compute_identity_hash(&self) -> String {
-        // 8-Component Identity Hash: H₃(pk ‖ addr ‖ ts ‖ sig ‖ salt ‖ nonce ‖ ts₂)
-        let combined = format!(
-            "{}{}{}{}{}{}{}",
-            self.private_key,
-            self.wallet_address,
-            self.creation_timestamp,
-            self.unique_signature,
-            self.salt,
-            self.identity_nonce,
-            self.submission_timestamp
-        );
-        
-        // Simplified triple-layer hash (Blake3 -> SHA-256 -> Dilithium simulation)
-        let hash1 = self.djb2_hash(&combined);
-        let hash2 = self.fnv_hash(&format!("{}", hash1));
-        let hash3 = self.sdbm_hash(&format!("{}", hash2));
-        
-        format!("{:016x}", hash3)
-    }
-
-    // Simplified hash functions for testing (production uses Blake3/SHA-256/Dilithium)
-    fn djb2_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash
-    }
-
-    fn fnv_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 14695981039346656037;
-        for byte in input.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        hash
-    }
-
-    fn sdbm_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 0;
-        for byte in input.bytes() {
-            hash = (byte as u64).wrapping_add(hash << 6).wrapping_add(hash << 16).wrapping_sub(hash);
-        }
-        hash
-    }
-}
-
-#[derive(Debug, Clone)]
-struct MinerRange {
-    miner_id: u32,
-    identity_hash: String,
-    start_nonce: u64,
-    end_nonce: u64,
-    range_size: u64,
-    position: usize,
-}
-
-#[derive(Debug, Clone)]
-struct BlockchainState {
-    prev_hash: String,
-    block_height: u64,
-    timestamp: u64,
-    active_miner_count: u32,
-}
-
-#[derive(Debug)]
-struct NonOverlapTestResult {
-    test_id: usize,
-    scenario_type: String,
-    blockchain_state: BlockchainState,
-    range_assignments: Vec<MinerRange>,
-    total_miners: usize,
-    total_nonces_assigned: u64,
-    range_verification_passed: bool,
-    non_overlap_verified: bool,
-    sequential_order_verified: bool,
-    exact_nonce_count_verified: bool,
-    gap_analysis_passed: bool,
-    overlap_count: usize,
-    gap_count: usize,
-}
-
-#[derive(Debug)]
-struct NonOverlapStatistics {
-    total_tests: usize,
-    standard_scenarios: usize,
-    edge_case_scenarios: usize,
-    dynamic_scenarios: usize,
-    non_overlap_success: usize,
-    sequential_order_success: usize,
-    exact_nonce_success: usize,
-    gap_analysis_success: usize,
-    total_miners_tested: usize,
-    total_ranges_verified: usize,
-    total_nonces_verified: u64,
-    overlap_violations: usize,
-    gap_violations: usize,
-    success_rate: f64,
-    mathematical_proof_validated: bool,
-    test_passed: bool,
 }
 
 struct DuraRangeAssignmentEngine {
-    miner_identities: Vec<MinerIdentity>,
+    miners: Vec<VirtualMiner>,
 }
 
 impl DuraRangeAssignmentEngine {
-    fn new(miners: Vec<MinerIdentity>) -> Self {
-        DuraRangeAssignmentEngine {
-            miner_identities: miners,
-        }
-    }
-
-    fn generate_master_seed(&self, blockchain_state: &BlockchainState) -> String {
-        // Master Seed = H₃(prev_hash ‖ sort(ID_Hash₁, ID_Hash₂, ..., ID_Hash_n))
-        let mut identity_hashes: Vec<String> = self.miner_identities
-            .iter()
-            .filter(|miner| miner.is_active)
-            .map(|miner| miner.compute_identity_hash())
-            .collect();
-        
-        // Canonical lexicographic sorting (critical for determinism)
-        identity_hashes.sort();
-        
-        let concatenated_hashes = identity_hashes.join("");
-        let seed_input = format!("{}{}", blockchain_state.prev_hash, concatenated_hashes);
-        
-        // Triple-layer hash for master seed
-        let hash1 = self.djb2_hash(&seed_input);
-        let hash2 = self.fnv_hash(&format!("{}", hash1));
-        let hash3 = self.sdbm_hash(&format!("{}", hash2));
-        
-        format!("{:016x}", hash3)
-    }
-
-    fn chacha20_deterministic_shuffle(&self, master_seed: &str, miner_count: usize) -> Vec<usize> {
-        // Simplified ChaCha20 PRNG simulation for deterministic shuffling
-        let seed_value = self.djb2_hash(master_seed);
-        let mut rng_state = seed_value;
-        let mut indices: Vec<usize> = (0..miner_count).collect();
-        
-        // Fisher-Yates shuffle with deterministic PRNG
-        for i in (1..miner_count).rev() {
-            rng_state = rng_state.wrapping_mul(1103515245).wrapping_add(12345); // LCG
-            let j = (rng_state as usize) % (i + 1);
-            indices.swap(i, j);
-        }
-        
-        indices
+    fn new(miners: Vec<VirtualMiner>) -> Self {
+        DuraRangeAssignmentEngine { miners }
     }
 
     fn execute_sequential_range_assignment(&self, blockchain_state: &BlockchainState) -> Vec<MinerRange> {
-        // Phase 1: Generate master seed
-        let master_seed = self.generate_master_seed(blockchain_state);
-        
-        // Phase 2: Get active miners
-        let active_miners: Vec<&MinerIdentity> = self.miner_identities
+        // Only active miners take part in the assignment
+        let active_miners: Vec<MinerIdentity> = self.miners
             .iter()
             .filter(|miner| miner.is_active)
+            .map(|miner| miner.identity.clone())
             .collect();
         
-        let miner_count = active_miners.len();
-        
-        // Phase 3: ChaCha20 deterministic shuffle
-        let shuffled_indices = self.chacha20_deterministic_shuffle(&master_seed, miner_count);
-        
-        // Phase 4: Sequential range assignment (250,000 nonces per miner)
-        // Formula: Range_i = [i × 250,000 + 1, (i + 1) × 250,000]
-        let mut ranges = Vec::new();
-        
-        for (position, &original_index) in shuffled_indices.iter().enumerate() {
-            let miner = active_miners[original_index];
-            let start_nonce = (position as u64 * NONCES_PER_MINER) + 1;
-            let end_nonce = (position + 1) as u64 * NONCES_PER_MINER;
-            
-            let range = MinerRange {
-                miner_id: original_index as u32,
-                identity_hash: miner.compute_identity_hash(),
-                start_nonce,
-                end_nonce,
-                range_size: NONCES_PER_MINER,
-                position,
-            };
-            
-            ranges.push(range);
-        }
-        
-        ranges
-    }
-
-    // Hash functions (same as MinerIdentity for consistency)
-    fn djb2_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash
-    }
-
-    fn fnv_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 14695981039346656037;
-        for byte in input.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        hash
-    }
-
-    fn sdbm_hash(&self, input: &str) -> u64 {
-        let mut hash: u64 = 0;
-        for byte in input.bytes() {
-            hash = (byte as u64).wrapping_add(hash << 6).wrapping_add(hash << 16).wrapping_sub(hash);
-        }
-        hash
+        // Formula: Range_i = [10,001 + i × 250,000, 10,000 + (i + 1) × 250,000]
+        assign_ranges(&blockchain_state.previous_hash, &active_miners).ranges
     }
 }
 
-struct NonOverlapTestFramework {
-    test_results: Vec<NonOverlapTestResult>,
-}
+struct NonOverlapTestFramework;
 
 impl NonOverlapTestFramework {
-    fn new() -> Self {
-        NonOverlapTestFramework {
-            test_results: Vec::new(),
-        }
-    }
-
-    fn generate_virtual_miners(&self, count: usize) -> Vec<MinerIdentity> {
+    fn generate_virtual_miners(&self, count: usize) -> Vec<VirtualMiner> {
         let base_timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
@@ -280,7 +69,7 @@ impl NonOverlapTestFramework {
         
         let mut miners = Vec::new();
         for i in 0..count {
-            miners.push(MinerIdentity::new(i as u32, base_timestamp));
+            miners.push(VirtualMiner::new(i as u32, base_timestamp));
         }
         
         miners
@@ -296,10 +85,11 @@ impl NonOverlapTestFramework {
         // Standard scenarios with fixed miner counts
         for i in 0..TEST_SCENARIOS {
             let scenario = BlockchainState {
-                prev_hash: format!("block_hash_{:016x}", (i as u64).wrapping_mul(0x123456789ABCDEF)),
-                block_height: (i + 1) as u64,
+                previous_hash: format!("block_hash_{:016x}", (i as u64).wrapping_mul(0x123456789ABCDEF)),
+                height: (i + 1) as u64,
                 timestamp: base_timestamp + (i as u64 * 500),
-                active_miner_count: VIRTUAL_MINERS as u32,
+                active_miners: VIRTUAL_MINERS as u32,
+                ..Default::default()
             };
             scenarios.push((scenario, "Standard".to_string()));
         }
@@ -308,10 +98,11 @@ impl NonOverlapTestFramework {
         for i in 0..EDGE_CASE_SCENARIOS {
             let miner_count = MIN_DYNAMIC_MINERS + (i * (MAX_DYNAMIC_MINERS - MIN_DYNAMIC_MINERS) / EDGE_CASE_SCENARIOS);
             let scenario = BlockchainState {
-                prev_hash: format!("edge_hash_{:016x}", (i as u64).wrapping_mul(0xFEDCBA9876543210)),
-                block_height: (TEST_SCENARIOS + i + 1) as u64,
+                previous_hash: format!("edge_hash_{:016x}", (i as u64).wrapping_mul(0xFEDCBA9876543210)),
+                height: (TEST_SCENARIOS + i + 1) as u64,
                 timestamp: base_timestamp + ((TEST_SCENARIOS + i) as u64 * 500),
-                active_miner_count: miner_count as u32,
+                active_miners: miner_count as u32,
+                ..Default::default()
             };
             scenarios.push((scenario, "Edge Case".to_string()));
         }
@@ -321,6 +112,18 @@ impl NonOverlapTestFramework {
 
     fn verify_non_overlap(&self, ranges: &[MinerRange]) -> (bool, usize) {
         let mut overlap_count = 0;
+        let system_range = RangeLayout::system_range();
+        
+        // Reserved segment: ∀i: Range_i ∩ [1, 10,000] = ∅
+        for (i, range) in ranges.iter().enumerate() {
+            let nonces = NonceRange { start: range.start_nonce, end: range.end_nonce };
+            if nonces.overlaps(&system_range) {
+                overlap_count += 1;
+                println!("SYSTEM SEGMENT OVERLAP: Range {} [{}, {}] overlaps System Miner range [{}, {}]",
+                        i, range.start_nonce, range.end_nonce,
+                        SYSTEM_MINER_RANGE_START, SYSTEM_MINER_RANGE_END);
+            }
+        }
         
         // Mathematical verification: ∀i≠j: Range_i ∩ Range_j = ∅
         for i in 0..ranges.len() {
@@ -392,7 +195,7 @@ impl NonOverlapTestFramework {
         (gap_count == 0, gap_count)
     }
 
-    fn simulate_dynamic_miner_changes(&self, miners: &mut Vec<MinerIdentity>, scenario_id: usize) {
+    fn simulate_dynamic_miner_changes(&self, miners: &mut Vec<VirtualMiner>, scenario_id: usize) {
         // Simulate miners joining and leaving dynamically
         let change_type = scenario_id % 4;
         
@@ -416,7 +219,7 @@ impl NonOverlapTestFramework {
                 let join_count = miners.len() / 20;
                 for i in 0..join_count {
                     let new_id = miners.len() + i;
-                    miners.push(MinerIdentity::new(new_id as u32, base_timestamp));
+                    miners.push(VirtualMiner::new(new_id as u32, base_timestamp));
                 }
             },
             2 => {
@@ -435,7 +238,7 @@ impl NonOverlapTestFramework {
                 
                 for i in 0..change_count {
                     let new_id = miners.len() + i;
-                    miners.push(MinerIdentity::new(new_id as u32, base_timestamp));
+                    miners.push(VirtualMiner::new(new_id as u32, base_timestamp));
                 }
             },
             _ => {
@@ -444,7 +247,64 @@ impl NonOverlapTestFramework {
         }
     }
 
-    fn run_comprehensive_non_overlap_test(&mut self) -> NonOverlapStatistics {
+    // Bootstrap ramp: a batch of MINERS_PER_REGISTRATION miners joins every
+    // BLOCKS_PER_REGISTRATION blocks from a single range to MAX_DYNAMIC_MINERS;
+    // at every height the DURA ranges must tile exactly the active mining
+    // range the controller reports
+    fn run_bootstrap_ramp_test(&self) -> bool {
+        println!("\n=================================================================================");
+        println!("BOOTSTRAP RAMP: 1 → {} MINERS", MAX_DYNAMIC_MINERS);
+        println!("=================================================================================");
+        
+        let mut controller = BootstrapController::new();
+        let miners = self.generate_virtual_miners(MAX_DYNAMIC_MINERS);
+        for (batch, joining) in miners.chunks(MINERS_PER_REGISTRATION).enumerate() {
+            let changes: Vec<RegistryChange> = joining.iter().map(|miner| RegistryChange::Join(miner.identity.clone())).collect();
+            controller.apply_block((batch as u64 + 1) * BLOCKS_PER_REGISTRATION, &changes).expect("valid registry block");
+        }
+        
+        let mut heights_checked = 0;
+        let mut failures = 0;
+        let batches = MAX_DYNAMIC_MINERS.div_ceil(MINERS_PER_REGISTRATION) as u64;
+        let last_height = batches * BLOCKS_PER_REGISTRATION + REGISTRY_ACTIVATION_DELAY;
+        for height in (1..=last_height).step_by(BLOCKS_PER_REGISTRATION as usize / 2) {
+            let miners = controller.active_miners(height);
+            heights_checked += 1;
+            if miners.is_empty() {
+                // Before the first registration the single bootstrap range is unowned
+                if controller.get_active_mining_range(height) != NONCES_PER_MINER {
+                    failures += 1;
+                    println!("BOOTSTRAP RAMP FAILURE at height {}: no bootstrap range", height);
+                }
+                continue;
+            }
+            
+            let ranges = assign_ranges(&format!("bootstrap_block_{}", height), &miners).ranges;
+            let (non_overlap, _) = self.verify_non_overlap(&ranges);
+            let exact = self.verify_exact_nonce_count(&ranges);
+            let (no_gaps, _) = self.verify_gap_analysis(&ranges);
+            let range_matches = ranges.len() as u64 * NONCES_PER_MINER == controller.get_active_mining_range(height);
+            
+            if !(non_overlap && exact && no_gaps && range_matches) {
+                failures += 1;
+                println!("BOOTSTRAP RAMP FAILURE at height {}: {} miners", height, miners.len());
+            }
+        }
+        
+        let end = controller.bootstrap_end();
+        println!("Heights Checked: {}", heights_checked);
+        println!("Final Active Mining Range: {} nonces ({} miners)",
+                 controller.get_active_mining_range(last_height), controller.active_miner_count(last_height));
+        match end {
+            Some(end) => println!("Bootstrap Phase Ends at Height: {} ({:?} from then on)", end, controller.phase(end)),
+            None => println!("Bootstrap Phase Ends at Height: not reached"),
+        }
+        let passed = failures == 0 && end.is_some_and(|end| controller.phase(end) == NetworkPhase::Sustained);
+        println!("Bootstrap Ramp Result: {}", if passed { "PASS" } else { "FAIL" });
+        passed
+    }
+
+    fn run_comprehensive_non_overlap_test(&self) -> bool {
         println!("\n=================================================================================");
         println!("I PROTOCOL - TEST 2.2: DURA RANGE NON-OVERLAP VERIFICATION");
         println!("=================================================================================");
@@ -473,7 +333,7 @@ impl NonOverlapTestFramework {
         
         for (test_id, (scenario, scenario_type)) in scenarios.iter().enumerate() {
             // Generate miners for this scenario
-            let mut miners = self.generate_virtual_miners(scenario.active_miner_count as usize);
+            let mut miners = self.generate_virtual_miners(scenario.active_miners as usize);
             
             // Apply dynamic changes for edge cases
             if scenario_type == "Edge Case" {
@@ -499,11 +359,6 @@ impl NonOverlapTestFramework {
             let exact_nonce_count_verified = self.verify_exact_nonce_count(&range_assignments);
             let (gap_analysis_passed, gap_count) = self.verify_gap_analysis(&range_assignments);
             
-            let range_verification_passed = non_overlap_verified && 
-                                           sequential_order_verified && 
-                                           exact_nonce_count_verified && 
-                                           gap_analysis_passed;
-            
             // Update statistics
             if non_overlap_verified {
                 non_overlap_success += 1;
@@ -530,24 +385,6 @@ impl NonOverlapTestFramework {
             total_ranges_verified += range_assignments.len();
             total_nonces_verified += range_assignments.len() as u64 * NONCES_PER_MINER;
             
-            // Store test result
-            let test_result = NonOverlapTestResult {
-                test_id,
-                scenario_type: scenario_type.clone(),
-                blockchain_state: scenario.clone(),
-                range_assignments,
-                total_miners: active_miners,
-                total_nonces_assigned: active_miners as u64 * NONCES_PER_MINER,
-                range_verification_passed,
-                non_overlap_verified,
-                sequential_order_verified,
-                exact_nonce_count_verified,
-                gap_analysis_passed,
-                overlap_count,
-                gap_count,
-            };
-            self.test_results.push(test_result);
-            
             // Progress reporting
             if (test_id + 1) % 100 == 0 {
                 println!("Completed {} scenarios... (Non-overlap success rate: {:.2}%)", 
@@ -586,14 +423,15 @@ impl NonOverlapTestFramework {
         if test_passed {
             println!("MATHEMATICAL NON-OVERLAP VALIDATION:");
             println!("∀i≠j: Range_i ∩ Range_j = ∅: MATHEMATICALLY PROVEN");
-            println!("Sequential Range Assignment: Range_i = [i × 250,000 + 1, (i + 1) × 250,000]");
+            println!("System Segment Isolation: Range_i ∩ [1, 10,000] = ∅: VERIFIED");
+            println!("Sequential Range Assignment: Range_i = [10,001 + i × 250,000, 10,000 + (i + 1) × 250,000]");
             println!("Non-Overlapping Property: max(Range_i) < min(Range_j) when i < j: VERIFIED");
             println!("Exact Nonce Allocation: Each miner receives exactly 250,000 nonces: GUARANTEED");
             println!("Zero Range Overlaps: MATHEMATICALLY CERTAIN");
             println!();
             println!("RANGE ASSIGNMENT ANALYSIS:");
-            println!("- Sequential Formula: start_nonce = position × 250,000 + 1");
-            println!("- Sequential Formula: end_nonce = (position + 1) × 250,000");
+            println!("- Sequential Formula: start_nonce = 10,001 + position × 250,000");
+            println!("- Sequential Formula: end_nonce = 10,000 + (position + 1) × 250,000");
             println!("- Range Size: Exactly 250,000 nonces per miner");
             println!("- Gap Prevention: end_nonce(i) + 1 = start_nonce(i+1)");
             println!("- Overlap Prevention: Mathematically impossible by design");
@@ -616,8 +454,9 @@ impl NonOverlapTestFramework {
         println!("MATHEMATICAL PROOF ANALYSIS");
         println!("=================================================================================");
         println!("Range Assignment Formula Verification:");
-        println!("- Formula: Range_i = [i × 250,000 + 1, (i + 1) × 250,000]");
-        println!("- Non-Overlap Proof: end_nonce(i) = i × 250,000 < (i+1) × 250,000 + 1 = start_nonce(i+1)");
+        println!("- Formula: Range_i = [10,001 + i × 250,000, 10,000 + (i + 1) × 250,000]");
+        println!("- Non-Overlap Proof: end_nonce(i) = 10,000 + (i+1) × 250,000 < 10,001 + (i+1) × 250,000 = start_nonce(i+1)");
+        println!("- System Segment Proof: start_nonce(0) = 10,001 > 10,000 = max(System Miner range)");
         println!("- Mathematical Certainty: ∀i≠j: max(Range_i) < min(Range_j) when i < j");
         println!("- Set Theory: Range_i ∩ Range_j = ∅ for all i ≠ j");
         println!();
@@ -631,38 +470,23 @@ impl NonOverlapTestFramework {
         println!("OVERALL TEST RESULT: {}", if test_passed { "PASS" } else { "FAIL" });
         println!("=================================================================================");
         
-        NonOverlapStatistics {
-            total_tests,
-            standard_scenarios,
-            edge_case_scenarios,
-            dynamic_scenarios,
-            non_overlap_success,
-            sequential_order_success,
-            exact_nonce_success,
-            gap_analysis_success,
-            total_miners_tested,
-            total_ranges_verified,
-            total_nonces_verified,
-            overlap_violations,
-            gap_violations,
-            success_rate,
-            mathematical_proof_validated,
-            test_passed,
-        }
+        test_passed
     }
 }
 
 fn main() {
-    let mut test_framework = NonOverlapTestFramework::new();
-    let statistics = test_framework.run_comprehensive_non_overlap_test();
+    let test_framework = NonOverlapTestFramework;
+    let non_overlap_passed = test_framework.run_comprehensive_non_overlap_test();
+    let bootstrap_ramp_passed = test_framework.run_bootstrap_ramp_test();
     
     // Final validation
-    if statistics.test_passed {
+    if non_overlap_passed && bootstrap_ramp_passed {
         println!("\nTEST 2.2 COMPLETION: DURA RANGE NON-OVERLAP VERIFICATION SUCCESSFUL");
         println!("Mathematical proof ∀i≠j: Range_i ∩ Range_j = ∅: ESTABLISHED");
         println!("Sequential range assignment formula: VERIFIED");
         println!("Zero range overlaps in all test scenarios: ACHIEVED");
         println!("Edge cases with dynamic miner joining/leaving: HANDLED");
+        println!("Bootstrap ramp from a single range to {} miners: VERIFIED", MAX_DYNAMIC_MINERS);
         println!("Success Criteria: Zero range overlaps in all scenarios: ACCOMPLISHED");
     } else {
         println!("\nTEST 2.2 COMPLETION: DURA RANGE NON-OVERLAP VERIFICATION FAILED");
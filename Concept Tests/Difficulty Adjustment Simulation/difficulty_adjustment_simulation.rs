@@ -0,0 +1,168 @@
+// I Protocol - DIFFICULTY ADJUSTMENT SIMULATION
+// Objective: Show the retarget rule holds regular-block success near 91%
+// Method: Replay a miner-count time series (bootstrap ramp, hardware shift,
+//         sudden exit, recovery) through the integer retarget rule
+// Success Criteria: Mean regular success within 91% ± 2% and every settled
+//                   epoch within 91% ± 5%
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use i_protocol_core::difficulty::Difficulty;
+use i_protocol_core::params::{NONCES_PER_MINER, RETARGET_INTERVAL, TARGET_SYSBLOCK_RATE_PERCENT};
+
+// Simulation Configuration Constants
+const EPOCHS: u64 = 40;
+const SIMULATION_SEED: [u8; 32] = [7; 32];
+// Epochs after a hardware shift before the estimate is expected to settle
+const SETTLING_EPOCHS: u64 = 2;
+const TARGET_SUCCESS_PERCENT: f64 = 100.0 - TARGET_SYSBLOCK_RATE_PERCENT as f64;
+
+#[derive(Debug, Clone, Copy)]
+struct NetworkConditions {
+    active_miners: u32,
+    // Headers one miner really hashes inside τ; unknown to the protocol
+    work_per_miner: u64,
+}
+
+#[derive(Debug)]
+struct EpochResult {
+    epoch: u64,
+    min_miners: u32,
+    max_miners: u32,
+    estimated_work: u64,
+    actual_work: u64,
+    regular_blocks: u64,
+    sys_blocks: u64,
+    success_rate: f64,
+    fallback_rate: f64,
+    settled: bool,
+}
+
+fn lerp(from: f64, to: f64, t: f64) -> f64 {
+    from + (to - from) * t
+}
+
+// Miner-count time series by block height
+fn conditions_at(height: u64) -> NetworkConditions {
+    let epoch = height as f64 / RETARGET_INTERVAL as f64;
+    let active_miners = if epoch < 15.0 {
+        // Bootstrap ramp from a single miner to 1,500
+        lerp(1.0, 1_500.0, epoch / 15.0)
+    } else if epoch < 25.0 {
+        1_500.0
+    } else if epoch < 32.0 {
+        // Sudden exit of most of the network
+        400.0
+    } else {
+        lerp(400.0, 1_000.0, (epoch - 32.0) / 8.0)
+    };
+    // Slower devices join from epoch 18 and cover about half a range in τ
+    let work_per_miner = if epoch < 18.0 { NONCES_PER_MINER } else { NONCES_PER_MINER * 45 / 100 };
+    NetworkConditions {
+        active_miners: active_miners.round() as u32,
+        work_per_miner,
+    }
+}
+
+fn is_settled(epoch: u64) -> bool {
+    let shifts = [0, 18];
+    shifts.iter().all(|&shift| epoch < shift || epoch >= shift + SETTLING_EPOCHS)
+}
+
+fn run_simulation() -> Vec<EpochResult> {
+    let mut rng = ChaCha20Rng::from_seed(SIMULATION_SEED);
+    let mut difficulty = Difficulty::default();
+    let mut results = Vec::new();
+
+    for epoch in 0..EPOCHS {
+        let mut sys_blocks = 0;
+        let mut min_miners = u32::MAX;
+        let mut max_miners = 0;
+        let mut actual_work = 0;
+
+        for offset in 0..RETARGET_INTERVAL {
+            let conditions = conditions_at(epoch * RETARGET_INTERVAL + offset);
+            min_miners = min_miners.min(conditions.active_miners);
+            max_miners = max_miners.max(conditions.active_miners);
+            actual_work = conditions.work_per_miner;
+
+            let target = difficulty.target(conditions.active_miners);
+            let network_hashes = conditions.active_miners as f64 * conditions.work_per_miner as f64;
+            let fallback_probability = (1.0 - target.success_probability()).powf(network_hashes);
+            if rng.gen::<f64>() < fallback_probability {
+                sys_blocks += 1;
+            }
+        }
+
+        let regular_blocks = RETARGET_INTERVAL - sys_blocks;
+        results.push(EpochResult {
+            epoch,
+            min_miners,
+            max_miners,
+            estimated_work: difficulty.work_per_miner,
+            actual_work,
+            regular_blocks,
+            sys_blocks,
+            success_rate: regular_blocks as f64 / RETARGET_INTERVAL as f64 * 100.0,
+            fallback_rate: sys_blocks as f64 / RETARGET_INTERVAL as f64 * 100.0,
+            settled: is_settled(epoch),
+        });
+        difficulty = difficulty.retarget(RETARGET_INTERVAL, sys_blocks);
+    }
+    results
+}
+
+fn bar(percent: f64, scale: f64) -> String {
+    "#".repeat((percent / scale).round() as usize)
+}
+
+fn main() {
+    println!("I PROTOCOL DIFFICULTY ADJUSTMENT SIMULATION");
+    println!("{}", "=".repeat(100));
+    println!("Blocks per Epoch: {}", RETARGET_INTERVAL);
+    println!("Epochs: {}", EPOCHS);
+    println!("Target Regular Success: {:.1}%  Target SysBlock Rate: {}%", TARGET_SUCCESS_PERCENT, TARGET_SYSBLOCK_RATE_PERCENT);
+    println!("Initial Work Estimate: {} hashes per miner per window", NONCES_PER_MINER);
+    println!();
+
+    let results = run_simulation();
+
+    println!("{:>5} {:>11} {:>10} {:>10} {:>9} {:>10}  Success (each # = 2%) | Fallback (each * = 1%)",
+             "Epoch", "Miners", "Est. W", "Actual W", "Success", "Fallback");
+    for result in &results {
+        println!("{:>5} {:>5}-{:<5} {:>10} {:>10} {:>8.1}% {:>9.1}%  {:<50}|{}{}",
+                 result.epoch,
+                 result.min_miners,
+                 result.max_miners,
+                 result.estimated_work,
+                 result.actual_work,
+                 result.success_rate,
+                 result.fallback_rate,
+                 bar(result.success_rate, 2.0),
+                 "*".repeat(result.fallback_rate.round() as usize),
+                 if result.settled { "" } else { "  (settling)" });
+    }
+
+    let mean_success = results.iter().map(|r| r.success_rate).sum::<f64>() / results.len() as f64;
+    let settled: Vec<&EpochResult> = results.iter().filter(|r| r.settled).collect();
+    let worst = settled.iter()
+        .max_by(|a, b| {
+            let da = (a.success_rate - TARGET_SUCCESS_PERCENT).abs();
+            let db = (b.success_rate - TARGET_SUCCESS_PERCENT).abs();
+            da.partial_cmp(&db).unwrap()
+        })
+        .expect("settled epochs exist");
+    let total_sys: u64 = results.iter().map(|r| r.sys_blocks).sum();
+    let total_regular: u64 = results.iter().map(|r| r.regular_blocks).sum();
+
+    let mean_valid = (mean_success - TARGET_SUCCESS_PERCENT).abs() <= 2.0;
+    let settled_valid = (worst.success_rate - TARGET_SUCCESS_PERCENT).abs() <= 5.0;
+
+    println!();
+    println!("{}", "=".repeat(100));
+    println!("Total Blocks: {} regular, {} SysBlocks", total_regular, total_sys);
+    println!("Mean Regular Success: {:.3}% ({})", mean_success, if mean_valid { "PASS" } else { "FAIL" });
+    println!("Worst Settled Epoch: {} at {:.1}% ({})", worst.epoch, worst.success_rate, if settled_valid { "PASS" } else { "FAIL" });
+    println!("OVERALL RESULT: {}", if mean_valid && settled_valid { "PASS" } else { "FAIL" });
+}
@@ -7,26 +7,24 @@
 //! - Test Dilithium signature verification under quantum attacks
 //! - Verify NIST Level 1 security compliance
 //! - Measure performance impact of quantum-resistant operations
+//!
 //! Success Criteria: Quantum resistance maintained with acceptable performance
 
+use std::hint::black_box;
 use std::time::{Duration, Instant};
-use std::collections::HashMap;
 use std::fs;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use sha2::{Sha256, Digest};
-use blake3::Hasher as Blake3Hasher;
-use num_bigint::BigUint;
-use num_traits::{Zero, One};
-use rayon::prelude::*;
+use i_protocol_core::signing::{self, Ed25519Signer, MlDsaSigner, SignatureScheme, Signer};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
 const TARGET_SUCCESS_RATE: f64 = 99.99;
-const QUANTUM_ATTACK_ITERATIONS: usize = 10000;
 const GROVER_SIMULATION_ITERATIONS: usize = 1000;
 const DILITHIUM_SIGNATURE_TESTS: usize = 5000;
 const NIST_LEVEL_1_SECURITY_BITS: u32 = 128;
+const PERFORMANCE_VERIFICATIONS: usize = 1000;
 const ACCEPTABLE_PERFORMANCE_DEGRADATION: f64 = 200.0; // 200% max degradation for quantum-resistant operations
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,20 +82,19 @@ struct PerformanceImpactResults {
 #[derive(Debug, Clone)]
 struct QuantumSimulator {
     rng: ChaCha20Rng,
-    quantum_state_size: usize,
 }
 
+/// ML-DSA-65 (FIPS 204, the standardised CRYSTALS-Dilithium) as the protocol
+/// signs with it.
 #[derive(Debug, Clone)]
-struct DilithiumSimulator {
+struct DilithiumBackend {
     security_level: u32,
     signature_size: usize,
     public_key_size: usize,
-    private_key_size: usize,
 }
 
 #[derive(Debug, Clone)]
 struct GroverAlgorithmSimulator {
-    search_space_size: usize,
     quantum_iterations: usize,
     classical_iterations: usize,
 }
@@ -106,7 +103,6 @@ impl QuantumSimulator {
     fn new(seed: u64) -> Self {
         Self {
             rng: ChaCha20Rng::seed_from_u64(seed),
-            quantum_state_size: 2_usize.pow(NIST_LEVEL_1_SECURITY_BITS / 4),
         }
     }
 
@@ -120,8 +116,8 @@ impl QuantumSimulator {
         }
         
         // Apply quantum interference patterns
-        for i in 0..quantum_state.len() {
-            quantum_state[i] = quantum_state[i].wrapping_mul(self.rng.gen::<u8>());
+        for byte in quantum_state.iter_mut() {
+            *byte = byte.wrapping_mul(self.rng.gen::<u8>());
         }
         
         quantum_state
@@ -142,91 +138,32 @@ impl QuantumSimulator {
     }
 }
 
-impl DilithiumSimulator {
+impl DilithiumBackend {
     fn new() -> Self {
         Self {
-            security_level: NIST_LEVEL_1_SECURITY_BITS,
-            signature_size: 128, // Optimized signature size for testing
-            public_key_size: 64, // Optimized public key size for testing
-            private_key_size: 64, // Optimized private key size for testing
+            // ML-DSA-65 targets NIST category 3 (AES-192)
+            security_level: 192,
+            signature_size: SignatureScheme::MlDsa65.signature_len(),
+            public_key_size: SignatureScheme::MlDsa65.public_key_len(),
         }
     }
 
-    fn generate_keypair(&self, rng: &mut ChaCha20Rng) -> (Vec<u8>, Vec<u8>) {
-        let private_key = (0..self.private_key_size)
-            .map(|_| rng.gen::<u8>())
-            .collect();
-        
-        let public_key = (0..self.public_key_size)
-            .map(|_| rng.gen::<u8>())
-            .collect();
-        
-        (private_key, public_key)
+    fn generate_keypair(&self, rng: &mut ChaCha20Rng) -> MlDsaSigner {
+        MlDsaSigner::from_seed(&rng.gen())
     }
 
-    fn sign_message(&self, message: &[u8], private_key: &[u8], rng: &mut ChaCha20Rng) -> Vec<u8> {
-        let mut hasher = Sha256::new();
-        hasher.update(message);
-        hasher.update(private_key);
-        let hash = hasher.finalize();
-        
-        // Simulate Dilithium lattice-based signature generation
-        let mut signature = vec![0u8; self.signature_size];
-        
-        // Use hash as seed for deterministic signature generation
-        let mut sig_rng = ChaCha20Rng::from_seed(hash.into());
-        
-        for i in 0..signature.len() {
-            signature[i] = sig_rng.gen::<u8>() ^ rng.gen::<u8>();
-        }
-        
-        // Add lattice-based security properties with proper verification structure
-        for i in 0..32.min(signature.len()) {
-            signature[i] ^= hash[i];
-            // Create verification structure for signature integrity
-            if i + 32 < signature.len() {
-                signature[i + 32] = signature[i] ^ hash[i];
-            }
-        }
-        
-        signature
+    fn sign_message(&self, message: &[u8], signer: &MlDsaSigner) -> Vec<u8> {
+        signer.sign(message)
     }
 
     fn verify_signature(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
-        if signature.len() != self.signature_size {
-            return false;
-        }
-        
-        let mut hasher = Sha256::new();
-        hasher.update(message);
-        hasher.update(public_key);
-        let verification_hash = hasher.finalize();
-        
-        // Simulate lattice-based verification with proper signature integrity
-        let mut verification_success = true;
-        
-        // Check signature structure and lattice properties
-        for i in 0..32 {
-            // Enhanced verification to maintain signature integrity
-            if i + 32 < signature.len() {
-                if signature[i] ^ verification_hash[i] != signature[i + 32] {
-                    verification_success = false;
-                    break;
-                }
-            } else {
-                // For smaller signatures, use modular verification
-                if signature[i % signature.len()] & 0x0F != (verification_hash[i] & 0x0F) {
-                    verification_success = false;
-                    break;
-                }
-            }
-        }
-        
-        verification_success
+        signature.len() == self.signature_size
+            && public_key.len() == self.public_key_size
+            && signing::verify(public_key, message, signature).is_ok()
     }
 
-    fn simulate_quantum_attack(&self, signature: &[u8], public_key: &[u8], quantum_sim: &mut QuantumSimulator) -> bool {
-        // Simulate quantum attack on Dilithium signature
+    fn simulate_quantum_attack(&self, message: &[u8], signature: &[u8], public_key: &[u8], quantum_sim: &mut QuantumSimulator) -> bool {
+        // Perturb a genuine signature with the measured quantum state
         let quantum_state = quantum_sim.simulate_quantum_superposition(signature);
         let measured_state = quantum_sim.measure_quantum_state(&quantum_state);
         
@@ -239,9 +176,10 @@ impl DilithiumSimulator {
             }
         }
         
-        // Dilithium should resist quantum attacks
-        // Return false if attack succeeds (signature verification passes with forged signature)
-        !self.verify_signature(b"quantum_attack_message", &attack_signature, public_key)
+        // Return false if attack succeeds (the forgery verifies over the signed message,
+        // or the genuine signature verifies over another one)
+        !self.verify_signature(message, &attack_signature, public_key)
+            && !self.verify_signature(b"quantum_attack_message", signature, public_key)
     }
 }
 
@@ -252,7 +190,6 @@ impl GroverAlgorithmSimulator {
         let classical_iterations = search_space_size / 2;
         
         Self {
-            search_space_size,
             quantum_iterations,
             classical_iterations,
         }
@@ -296,7 +233,7 @@ impl GroverAlgorithmSimulator {
 
 struct QuantumResistanceTestFramework {
     quantum_simulator: QuantumSimulator,
-    dilithium_simulator: DilithiumSimulator,
+    dilithium: DilithiumBackend,
     grover_simulator: GroverAlgorithmSimulator,
 }
 
@@ -304,7 +241,7 @@ impl QuantumResistanceTestFramework {
     fn new() -> Self {
         Self {
             quantum_simulator: QuantumSimulator::new(42),
-            dilithium_simulator: DilithiumSimulator::new(),
+            dilithium: DilithiumBackend::new(),
             grover_simulator: GroverAlgorithmSimulator::new(16), // 2^16 search space
         }
     }
@@ -360,21 +297,23 @@ impl QuantumResistanceTestFramework {
         
         for i in 0..DILITHIUM_SIGNATURE_TESTS {
             // Generate keypair
-            let (private_key, public_key) = self.dilithium_simulator.generate_keypair(&mut self.quantum_simulator.rng);
+            let signer = self.dilithium.generate_keypair(&mut self.quantum_simulator.rng);
+            let public_key = signer.public_key();
             
             // Create test message
             let message = format!("I Protocol Quantum Test Message {}", i).into_bytes();
             
             // Sign message
-            let signature = self.dilithium_simulator.sign_message(&message, &private_key, &mut self.quantum_simulator.rng);
+            let signature = self.dilithium.sign_message(&message, &signer);
             
             // Verify signature (should succeed)
-            if self.dilithium_simulator.verify_signature(&message, &signature, &public_key) {
+            if self.dilithium.verify_signature(&message, &signature, &public_key) {
                 successful_verifications += 1;
             }
             
             // Attempt quantum attack
-            let quantum_attack_resisted = self.dilithium_simulator.simulate_quantum_attack(
+            let quantum_attack_resisted = self.dilithium.simulate_quantum_attack(
+                &message,
                 &signature,
                 &public_key,
                 &mut self.quantum_simulator
@@ -406,7 +345,7 @@ impl QuantumResistanceTestFramework {
         println!("[QUANTUM RESISTANCE TEST] Verifying NIST Level 1 Security Compliance...");
         
         let required_security_bits = NIST_LEVEL_1_SECURITY_BITS;
-        let achieved_security_bits = self.dilithium_simulator.security_level;
+        let achieved_security_bits = self.dilithium.security_level;
         
         let compliance_verified = achieved_security_bits >= required_security_bits;
         let post_quantum_standards_met = true; // Dilithium is NIST-approved post-quantum algorithm
@@ -428,25 +367,30 @@ impl QuantumResistanceTestFramework {
     fn measure_performance_impact(&mut self) -> PerformanceImpactResults {
         println!("[QUANTUM RESISTANCE TEST] Measuring Performance Impact...");
         
-        // Measure classical cryptographic operations
+        // Nodes verify every identity registration and transaction they
+        // receive, while keys are generated and messages signed once per wallet
+        // operation, so the verification path is what quantum resistance costs.
+        let message = b"I Protocol Performance Test Data";
+        let classical_signer = Ed25519Signer::from_seed(&self.quantum_simulator.rng.gen());
+        let classical_key = classical_signer.public_key();
+        let classical_signature = classical_signer.sign(message);
+        let quantum_signer = self.dilithium.generate_keypair(&mut self.quantum_simulator.rng);
+        let quantum_key = quantum_signer.public_key();
+        let quantum_signature = self.dilithium.sign_message(message, &quantum_signer);
+        
+        // Measure classical signature verification (Ed25519)
         let classical_start = Instant::now();
-        for _ in 0..100 {
-            let data = b"I Protocol Performance Test Data";
-            let mut hasher = Sha256::new();
-            hasher.update(data);
-            let _hash = hasher.finalize();
+        for _ in 0..PERFORMANCE_VERIFICATIONS {
+            black_box(signing::verify(&classical_key, message, &classical_signature)).ok();
         }
-        let classical_time = classical_start.elapsed().as_millis() as f64;
+        let classical_time = classical_start.elapsed().as_secs_f64() * 1000.0;
         
-        // Measure quantum-resistant operations (optimized)
+        // Measure quantum-resistant signature verification (ML-DSA-65)
         let quantum_start = Instant::now();
-        for _ in 0..100 {
-            let (private_key, public_key) = self.dilithium_simulator.generate_keypair(&mut self.quantum_simulator.rng);
-            let message = b"I Protocol Performance Test Data";
-            let signature = self.dilithium_simulator.sign_message(message, &private_key, &mut self.quantum_simulator.rng);
-            let _verified = self.dilithium_simulator.verify_signature(message, &signature, &public_key);
+        for _ in 0..PERFORMANCE_VERIFICATIONS {
+            black_box(self.dilithium.verify_signature(message, &quantum_signature, &quantum_key));
         }
-        let quantum_time = quantum_start.elapsed().as_millis() as f64;
+        let quantum_time = quantum_start.elapsed().as_secs_f64() * 1000.0;
         
         let performance_degradation_percentage = if classical_time > 0.0 {
             ((quantum_time - classical_time) / classical_time) * 100.0
@@ -4,159 +4,166 @@
 // Objective: Prove guaranteed 0.5s block finality under network failure scenarios
 
 use std::time::{Duration, Instant};
-use std::collections::{HashMap, HashSet};
-use std::thread;
-use std::sync::{Arc, Mutex};
+use i_protocol_core::clock::ManualClock;
+use i_protocol_core::fallback::{Activation, FallbackDriver};
+use i_protocol_core::fork_choice::{ForkChoice, ForkEvent};
+use i_protocol_core::hash::{digest_prefix_u64, triple_layer_hash, Digest32, HashDomain};
+use i_protocol_core::params::{NONCES_PER_MINER, REGULAR_MINER_RANGE_START, REGULAR_MINING_WINDOW_MS, SYSTEM_MINER_SALT};
+use i_protocol_core::system_miner::system_nonce;
+use i_protocol_core::{BlockHeader, BlockProducer, BlockchainState, Coinbase};
 
 // System Configuration Constants
-const REGULAR_MINING_WINDOW: Duration = Duration::from_millis(250);
+const REGULAR_MINING_WINDOW: Duration = Duration::from_millis(REGULAR_MINING_WINDOW_MS);
 const SYSTEM_MINER_ACTIVATION_TIME: Duration = Duration::from_millis(250);
 const GUARANTEED_BLOCK_FINALITY: Duration = Duration::from_millis(500);
-const SYSTEM_MINER_RANGE_START: u64 = 1;
-const SYSTEM_MINER_RANGE_END: u64 = 10000;
-const REGULAR_MINER_RANGE_START: u64 = 10001;
-const NONCES_PER_MINER: u64 = 250000;
 const TEST_SCENARIOS: usize = 10000;
-const PROTOCOL_SALT: &str = "I_PROTOCOL_SYSTEM_MINER_FAILSAFE_2024";
+const PARTITION_NODES: usize = 20;
 
 #[derive(Debug, Clone)]
-struct BlockchainState {
-    prev_hash: String,
-    timestamp: u64,
-    fail_count: u32,
-    height: u64,
+struct FailureScenario {
+    chain: BlockchainState,
     network_partition: bool,
     byzantine_nodes: u32,
     total_miners: u32,
 }
 
-#[derive(Debug, Clone)]
-struct FailsafeTestResult {
-    test_id: usize,
-    scenario_type: String,
-    blockchain_state: BlockchainState,
-    regular_miners_failed: bool,
-    system_miner_activated: bool,
-    activation_time: Duration,
-    block_production_time: Duration,
-    finality_guarantee_met: bool,
-    generated_nonce: u64,
-    hash_operations: u64,
-}
-
 #[derive(Debug)]
 struct FailsafeStatistics {
     total_scenarios: usize,
-    regular_mining_failures: usize,
-    system_miner_activations: usize,
-    finality_guarantee_success: usize,
-    average_activation_time: f64,
-    average_block_time: f64,
-    max_block_time: f64,
-    min_block_time: f64,
-    finality_success_rate: f64,
-    mathematical_guarantee_validated: bool,
-    network_partition_tests: usize,
-    byzantine_fault_tests: usize,
-    load_stress_tests: usize,
-    edge_case_tests: usize,
     test_passed: bool,
 }
 
-struct SystemMinerNode {
-    node_id: u32,
-    range_start: u64,
-    range_end: u64,
+#[derive(Debug, Clone)]
+struct PartitionOutcome {
+    converged: bool,
+    reorg_events: usize,
 }
 
-impl SystemMinerNode {
-    fn new(node_id: u32) -> Self {
-        SystemMinerNode {
-            node_id,
-            range_start: SYSTEM_MINER_RANGE_START,
-            range_end: SYSTEM_MINER_RANGE_END,
-        }
+// Splits the network in two; each side falls back to its own SysBlock for
+// the same height, then the partition heals and every node must settle on
+// one tip through SCRF, and through FCR when a late regular block shows up.
+struct PartitionHarness {
+    nodes: usize,
+}
+
+impl PartitionHarness {
+    fn new(nodes: usize) -> Self {
+        PartitionHarness { nodes }
     }
 
-    // Simplified triple-layer hash implementation for testing
-    // In production: Blake3 -> SHA-256 -> CRYSTAL Dilithium
-    fn triple_layer_hash(&self, input: &str) -> u64 {
-        // Layer 1: DJB2 (simulating Blake3)
-        let mut hash1: u64 = 5381;
-        for byte in input.bytes() {
-            hash1 = ((hash1 << 5).wrapping_add(hash1)).wrapping_add(byte as u64);
+    fn sys_block(&self, scenario: &FailureScenario, parent: Digest32, side: u8) -> BlockHeader {
+        let mut header = BlockHeader {
+            height: scenario.chain.height,
+            previous_hash: parent,
+            // Each side measures its own τ from a slightly different t0
+            timestamp: scenario.chain.timestamp + side as u64 * 3,
+            // Each side assembles its SysBlock from the mempool it can see
+            merkle_root: triple_layer_hash(HashDomain::Merkle, &[side, scenario.chain.height as u8]),
+            failed_miner_count: scenario.chain.failed_miner_count + side as u32,
+            producer: BlockProducer::SystemMiner { system_nonce: 0 },
+            coinbase: Coinbase::default(),
+        };
+        header.producer = BlockProducer::SystemMiner {
+            system_nonce: system_nonce(&header.chain_state(scenario.total_miners)),
+        };
+        header
+    }
+
+    fn run(&self, scenario: &FailureScenario, scenario_id: usize) -> PartitionOutcome {
+        let parent = triple_layer_hash(HashDomain::BlockHeader, scenario.chain.previous_hash.as_bytes());
+        let side_a = self.sys_block(scenario, parent, 0);
+        let side_b = self.sys_block(scenario, parent, 1);
+
+        // Every third partition also strands a regular block behind the split
+        let late_regular_block = scenario_id.is_multiple_of(3);
+        let late = BlockHeader {
+            producer: BlockProducer::RegularMiner {
+                range_owner: triple_layer_hash(HashDomain::Identity, &scenario_id.to_be_bytes()),
+                nonce: REGULAR_MINER_RANGE_START + (scenario_id as u64 % NONCES_PER_MINER),
+            },
+            ..side_a
+        };
+
+        let mut tips = Vec::with_capacity(self.nodes);
+        let mut reorg_events = 0;
+        for node in 0..self.nodes {
+            let mut fork_choice = ForkChoice::new(parent, scenario.chain.height - 1);
+            let (own, other) = if node < self.nodes / 2 { (side_a, side_b) } else { (side_b, side_a) };
+            let mut arrivals = vec![own, other];
+            if late_regular_block {
+                arrivals.push(late);
+            }
+            for header in arrivals {
+                if let ForkEvent::Reorg(_) = fork_choice.insert(header) {
+                    reorg_events += 1;
+                }
+            }
+            tips.push(fork_choice.tip());
         }
-        
-        // Layer 2: FNV (simulating SHA-256)
-        let mut hash2: u64 = 14695981039346656037;
-        for byte in format!("{}", hash1).bytes() {
-            hash2 ^= byte as u64;
-            hash2 = hash2.wrapping_mul(1099511628211);
+
+        PartitionOutcome {
+            converged: tips.iter().all(|tip| *tip == tips[0]),
+            reorg_events,
         }
-        
-        // Layer 3: SDBM (simulating CRYSTAL Dilithium)
-        let mut hash3: u64 = 0;
-        for byte in format!("{}", hash2).bytes() {
-            hash3 = (byte as u64).wrapping_add(hash3 << 6).wrapping_add(hash3 << 16).wrapping_sub(hash3);
+    }
+}
+
+struct SystemMinerNode {
+    clock: ManualClock,
+}
+
+impl SystemMinerNode {
+    fn new() -> Self {
+        SystemMinerNode {
+            clock: ManualClock::new(),
         }
-        
-        hash3
     }
 
-    fn generate_system_miner_nonce(&self, blockchain_state: &BlockchainState) -> u64 {
-        // System Miner Formula: H3(prev_hash || timestamp || fail_count || height || salt) % 10,000
-        let input = format!(
-            "{}{}{}{}{}",
-            blockchain_state.prev_hash,
-            blockchain_state.timestamp,
-            blockchain_state.fail_count,
-            blockchain_state.height,
-            PROTOCOL_SALT
-        );
-        
-        let hash_result = self.triple_layer_hash(&input);
-        let nonce = (hash_result % (self.range_end - self.range_start + 1)) + self.range_start;
-        nonce
+    // Triple-layer hash H3: Blake3 -> SHA-256 -> SHA3-256 in the System Miner domain
+    fn triple_layer_hash(&self, input: &str) -> u64 {
+        let digest = triple_layer_hash(HashDomain::SystemNonce, input.as_bytes());
+        digest_prefix_u64(&digest)
     }
 
-    fn simulate_system_miner_activation(&self, blockchain_state: &BlockchainState) -> (Duration, u64, u64) {
-        let start_time = Instant::now();
-        
-        // System miner activates after 0.25s regular mining window
-        thread::sleep(Duration::from_millis(1)); // Minimal simulation delay
-        
-        let generated_nonce = self.generate_system_miner_nonce(blockchain_state);
-        let hash_operations = 10000; // System miner performs 10K hash operations
+    // Returns the delay between the window closing and the node noticing it
+    fn simulate_system_miner_activation(&self, poll_interval: Duration) -> Duration {
+        // The fallback driver fires once 0.25s have elapsed on the node's own
+        // monotonic clock; a busy node only notices at its next poll
+        let mut driver = FallbackDriver::new(self.clock.clone());
+        driver.start_round();
+        while driver.poll() != Activation::Fire {
+            self.clock.advance(poll_interval);
+        }
         
-        let activation_time = start_time.elapsed();
-        (activation_time, generated_nonce, hash_operations)
+        driver.elapsed().expect("round started") - driver.window()
     }
 }
 
 struct FailsafeTestFramework {
     system_miner: SystemMinerNode,
-    test_results: Vec<FailsafeTestResult>,
 }
 
 impl FailsafeTestFramework {
     fn new() -> Self {
         FailsafeTestFramework {
-            system_miner: SystemMinerNode::new(1),
-            test_results: Vec::new(),
+            system_miner: SystemMinerNode::new(),
         }
     }
 
-    fn generate_failure_scenarios(&self) -> Vec<(String, BlockchainState)> {
+    fn generate_failure_scenarios(&self) -> Vec<(String, FailureScenario)> {
         let mut scenarios = Vec::new();
         
         // Standard network failure scenarios
         for i in 0..7000 {
             let scenario_type = "Standard Network Failure".to_string();
-            let state = BlockchainState {
-                prev_hash: format!("failure_block_{:016x}", (i as u64).wrapping_mul(0x123456789ABCDEF)),
-                timestamp: 1640995200000 + (i as u64 * 500),
-                fail_count: (i % 1000) as u32,
-                height: (i + 1) as u64,
+            let state = FailureScenario {
+                chain: BlockchainState {
+                    previous_hash: format!("failure_block_{:016x}", (i as u64).wrapping_mul(0x123456789ABCDEF)),
+                    timestamp: 1640995200000 + (i as u64 * 500),
+                    failed_miner_count: (i % 1000) as u32,
+                    height: (i + 1) as u64,
+                    ..Default::default()
+                },
                 network_partition: false,
                 byzantine_nodes: 0,
                 total_miners: 100,
@@ -167,11 +174,14 @@ impl FailsafeTestFramework {
         // Network partition scenarios
         for i in 0..1000 {
             let scenario_type = "Network Partition".to_string();
-            let state = BlockchainState {
-                prev_hash: format!("partition_block_{:016x}", i as u64 * 0xDEADBEEF),
-                timestamp: 1640995200000 + (i as u64 * 1000),
-                fail_count: (50 + (i % 50)) as u32, // High failure rate
-                height: (7000 + i + 1) as u64,
+            let state = FailureScenario {
+                chain: BlockchainState {
+                    previous_hash: format!("partition_block_{:016x}", i as u64 * 0xDEADBEEF),
+                    timestamp: 1640995200000 + (i as u64 * 1000),
+                    failed_miner_count: (50 + (i % 50)) as u32, // High failure rate
+                    height: (7000 + i + 1) as u64,
+                    ..Default::default()
+                },
                 network_partition: true,
                 byzantine_nodes: (i % 33) as u32, // Up to 33% Byzantine
                 total_miners: 100,
@@ -182,11 +192,14 @@ impl FailsafeTestFramework {
         // High load stress scenarios
         for i in 0..1500 {
             let scenario_type = "High Load Stress".to_string();
-            let state = BlockchainState {
-                prev_hash: format!("stress_block_{:016x}", i as u64 * 0xCAFEBABE),
-                timestamp: 1640995200000 + (i as u64 * 100), // Rapid blocks
-                fail_count: (75 + (i % 25)) as u32, // Very high failure rate
-                height: (8000 + i + 1) as u64,
+            let state = FailureScenario {
+                chain: BlockchainState {
+                    previous_hash: format!("stress_block_{:016x}", i as u64 * 0xCAFEBABE),
+                    timestamp: 1640995200000 + (i as u64 * 100), // Rapid blocks
+                    failed_miner_count: (75 + (i % 25)) as u32, // Very high failure rate
+                    height: (8000 + i + 1) as u64,
+                    ..Default::default()
+                },
                 network_partition: false,
                 byzantine_nodes: (i % 20) as u32,
                 total_miners: 200 + (i % 100) as u32, // Variable network size
@@ -196,29 +209,38 @@ impl FailsafeTestFramework {
         
         // Edge case scenarios
         let edge_cases = vec![
-            ("Zero Hash Edge Case".to_string(), BlockchainState {
-                prev_hash: "0000000000000000000000000000000000000000000000000000000000000000".to_string(),
-                timestamp: 0,
-                fail_count: 0,
-                height: 1,
+            ("Zero Hash Edge Case".to_string(), FailureScenario {
+                chain: BlockchainState {
+                    previous_hash: "0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+                    timestamp: 0,
+                    failed_miner_count: 0,
+                    height: 1,
+                    ..Default::default()
+                },
                 network_partition: false,
                 byzantine_nodes: 0,
                 total_miners: 1,
             }),
-            ("Maximum Values Edge Case".to_string(), BlockchainState {
-                prev_hash: "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff".to_string(),
-                timestamp: u64::MAX,
-                fail_count: u32::MAX,
-                height: u64::MAX,
+            ("Maximum Values Edge Case".to_string(), FailureScenario {
+                chain: BlockchainState {
+                    previous_hash: "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff".to_string(),
+                    timestamp: u64::MAX,
+                    failed_miner_count: u32::MAX,
+                    height: u64::MAX,
+                    ..Default::default()
+                },
                 network_partition: true,
                 byzantine_nodes: u32::MAX,
                 total_miners: u32::MAX,
             }),
-            ("Complete Network Failure".to_string(), BlockchainState {
-                prev_hash: "complete_failure_scenario".to_string(),
-                timestamp: 1640995200000,
-                fail_count: 1000,
-                height: 999999,
+            ("Complete Network Failure".to_string(), FailureScenario {
+                chain: BlockchainState {
+                    previous_hash: "complete_failure_scenario".to_string(),
+                    timestamp: 1640995200000,
+                    failed_miner_count: 1000,
+                    height: 999999,
+                    ..Default::default()
+                },
                 network_partition: true,
                 byzantine_nodes: 100,
                 total_miners: 100,
@@ -234,7 +256,7 @@ impl FailsafeTestFramework {
         scenarios
     }
 
-    fn simulate_regular_miner_failure(&self, blockchain_state: &BlockchainState) -> bool {
+    fn simulate_regular_miner_failure(&self, blockchain_state: &FailureScenario) -> bool {
         // Simulate various failure conditions
         if blockchain_state.network_partition {
             return true; // Network partition causes regular miner failure
@@ -244,13 +266,13 @@ impl FailsafeTestFramework {
             return true; // Byzantine fault tolerance exceeded
         }
         
-        if blockchain_state.fail_count > 90 {
+        if blockchain_state.chain.failed_miner_count > 90 {
             return true; // High failure rate triggers system miner
         }
         
         // Simulate random failures based on blockchain state
-        let failure_probability = (blockchain_state.fail_count as f64 / 100.0).min(1.0);
-        let hash_seed = format!("{}{}", blockchain_state.prev_hash, blockchain_state.timestamp);
+        let failure_probability = (blockchain_state.chain.failed_miner_count as f64 / 100.0).min(1.0);
+        let hash_seed = format!("{}{}", blockchain_state.chain.previous_hash, blockchain_state.chain.timestamp);
         let hash_value = self.system_miner.triple_layer_hash(&hash_seed);
         let random_value = (hash_value % 100) as f64 / 100.0;
         
@@ -266,7 +288,7 @@ impl FailsafeTestFramework {
         println!("System Miner Activation: {}ms", SYSTEM_MINER_ACTIVATION_TIME.as_millis());
         println!("Guaranteed Block Finality: {}ms", GUARANTEED_BLOCK_FINALITY.as_millis());
         println!("Test Scenarios: {}", TEST_SCENARIOS);
-        println!("Protocol Salt: {}", PROTOCOL_SALT);
+        println!("Protocol Salt: {}", SYSTEM_MINER_SALT);
         println!("=================================================================================");
         println!();
         
@@ -282,13 +304,25 @@ impl FailsafeTestFramework {
         let mut byzantine_fault_tests = 0;
         let mut load_stress_tests = 0;
         let mut edge_case_tests = 0;
+        let mut partition_convergence_success = 0;
+        let mut partition_reorgs = 0;
+        let partition_harness = PartitionHarness::new(PARTITION_NODES);
         
         let start_time = Instant::now();
         
         for (test_id, (scenario_type, blockchain_state)) in scenarios.iter().enumerate() {
             // Count scenario types
             match scenario_type.as_str() {
-                "Network Partition" => network_partition_tests += 1,
+                "Network Partition" => {
+                    network_partition_tests += 1;
+                    let outcome = partition_harness.run(blockchain_state, test_id);
+                    if outcome.converged {
+                        partition_convergence_success += 1;
+                    } else {
+                        println!("PARTITION DIVERGENCE in scenario {}: nodes disagree on the tip", test_id);
+                    }
+                    partition_reorgs += outcome.reorg_events;
+                },
                 "High Load Stress" => load_stress_tests += 1,
                 s if s.contains("Edge Case") => edge_case_tests += 1,
                 _ => {},
@@ -301,20 +335,17 @@ impl FailsafeTestFramework {
             // Simulate regular miner failure
             let regular_miners_failed = self.simulate_regular_miner_failure(blockchain_state);
             
-            let (activation_time, generated_nonce, hash_operations) = if regular_miners_failed {
+            let activation_time = if regular_miners_failed {
                 regular_mining_failures += 1;
                 system_miner_activations += 1;
                 
-                // System miner activates after regular mining window
-                let (sys_activation_time, sys_nonce, sys_hash_ops) = 
-                    self.system_miner.simulate_system_miner_activation(blockchain_state);
-                
-                (sys_activation_time, sys_nonce, sys_hash_ops)
+                // System miner activates after regular mining window; poll
+                // intervals of 0.1-5ms model nodes of differing load
+                let poll_interval = Duration::from_micros(100 * (1 + (test_id % 50) as u64));
+                self.system_miner.simulate_system_miner_activation(poll_interval)
             } else {
                 // Regular mining succeeds within 0.25s
-                let regular_time = Duration::from_millis(50 + (test_id % 200) as u64);
-                let regular_nonce = REGULAR_MINER_RANGE_START + (test_id as u64 % NONCES_PER_MINER);
-                (regular_time, regular_nonce, 250000)
+                Duration::from_millis(50 + (test_id % 200) as u64)
             };
             
             // Calculate total block production time
@@ -331,26 +362,11 @@ impl FailsafeTestFramework {
             }
             
             // Update statistics
-            total_activation_time += activation_time.as_millis() as f64;
+            total_activation_time += activation_time.as_secs_f64() * 1000.0;
             total_block_time += block_production_time.as_millis() as f64;
             max_block_time = max_block_time.max(block_production_time.as_millis() as f64);
             min_block_time = min_block_time.min(block_production_time.as_millis() as f64);
             
-            // Store test result
-            let test_result = FailsafeTestResult {
-                test_id,
-                scenario_type: scenario_type.clone(),
-                blockchain_state: blockchain_state.clone(),
-                regular_miners_failed,
-                system_miner_activated: regular_miners_failed,
-                activation_time,
-                block_production_time,
-                finality_guarantee_met,
-                generated_nonce,
-                hash_operations,
-            };
-            self.test_results.push(test_result);
-            
             // Progress reporting
             if (test_id + 1) % 1000 == 0 {
                 println!("Completed {} scenarios... (Finality success rate: {:.2}%)", 
@@ -364,7 +380,8 @@ impl FailsafeTestFramework {
         let average_block_time = total_block_time / total_scenarios as f64;
         let finality_success_rate = (finality_guarantee_success as f64 / total_scenarios as f64) * 100.0;
         let mathematical_guarantee_validated = finality_guarantee_success == total_scenarios;
-        let test_passed = mathematical_guarantee_validated;
+        let partitions_converged = partition_convergence_success == network_partition_tests;
+        let test_passed = mathematical_guarantee_validated && partitions_converged;
         
         let elapsed_time = start_time.elapsed();
         
@@ -380,6 +397,8 @@ impl FailsafeTestFramework {
         println!("Maximum Block Time: {:.3}ms", max_block_time);
         println!("Minimum Block Time: {:.3}ms", min_block_time);
         println!("Finality Success Rate: {:.6}%", finality_success_rate);
+        println!("Partition Convergence: {}/{} ({} nodes each)", partition_convergence_success, network_partition_tests, PARTITION_NODES);
+        println!("Reorg Events After Healing: {}", partition_reorgs);
         println!("Test Execution Time: {:.3}s", elapsed_time.as_secs_f64());
         println!();
         
@@ -396,6 +415,7 @@ impl FailsafeTestFramework {
             println!("- Mathematical Guarantee: Block_Time ≤ 0.5s");
             println!("- Failsafe Reliability: 100% (Deterministic)");
             println!("- Network Partition Resistance: ABSOLUTE");
+            println!("- Partition Healing: SCRF/FCR convergence on {} of {} partitions", partition_convergence_success, network_partition_tests);
             println!();
             println!("SCENARIO COVERAGE ANALYSIS:");
             println!("- Standard Network Failures: 7,000 scenarios");
@@ -412,6 +432,7 @@ impl FailsafeTestFramework {
         } else {
             println!("MATHEMATICAL FAILSAFE VALIDATION FAILED:");
             println!("Finality guarantee violations detected");
+            println!("Diverged partitions: {}", network_partition_tests - partition_convergence_success);
             println!("Failed scenarios: {}", total_scenarios - finality_guarantee_success);
             println!("System requires immediate investigation");
         }
@@ -436,19 +457,6 @@ impl FailsafeTestFramework {
         
         FailsafeStatistics {
             total_scenarios,
-            regular_mining_failures,
-            system_miner_activations,
-            finality_guarantee_success,
-            average_activation_time,
-            average_block_time,
-            max_block_time,
-            min_block_time,
-            finality_success_rate,
-            mathematical_guarantee_validated,
-            network_partition_tests,
-            byzantine_fault_tests,
-            load_stress_tests,
-            edge_case_tests,
             test_passed,
         }
     }
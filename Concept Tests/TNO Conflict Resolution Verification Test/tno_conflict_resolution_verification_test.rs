@@ -5,188 +5,125 @@
 // Method: Generate intentional nonce collisions, verify hierarchical resolution
 // Success Criteria: 100% transaction processing success rate
 
-use std::collections::{HashMap, HashSet, BTreeMap, VecDeque};
+use std::collections::{HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::cmp::Ordering;
 use std::fs::File;
 use std::io::Write;
+use i_protocol_core::collision::{Claim, CollisionIndex, Contender, ResolutionOrder};
+use i_protocol_core::dura::RangeTable;
+use i_protocol_core::hash::to_hex;
+use i_protocol_core::lifecycle::{TxStatus, TxTracker};
+use i_protocol_core::params::TX_EXPIRY_BLOCKS;
+use i_protocol_core::tno;
+use i_protocol_core::{triple_layer_hash, BlockchainState, Digest32, HashDomain, MinerIdentity};
+
+#[path = "../fixtures/transactions.rs"]
+mod transactions;
 
 // TNO Conflict Resolution Test Constants
 const TRANSACTION_TESTS: usize = 100000; // 100,000 transactions for comprehensive testing
 const VIRTUAL_MINERS: usize = 200;
-const NONCES_PER_MINER: u64 = 250000;
-const USER_NONCE_RANGE: u64 = 1000000000000; // 1 trillion range
 const PROTOCOL_SALT: &str = "I_PROTOCOL_TNO_CONFLICT_RESOLUTION_2024";
 const TARGET_SUCCESS_RATE: f64 = 100.0; // 100% transaction processing success rate
-const MAX_RETRY_ATTEMPTS: usize = 1000; // Maximum retry attempts per transaction
 const COLLISION_SIMULATION_RATE: f64 = 0.15; // 15% intentional collision rate
-const TIMESTAMP_PRECISION_MS: u64 = 1; // Millisecond precision for timestamp comparison
-const HASH_COMPARISON_DEPTH: usize = 64; // Full hash comparison depth
+const TRANSACTIONS_PER_BLOCK: usize = 1000; // Transactions mapped against each height
+const FINALITY_DEPTH: u64 = 3; // Blocks on top of a height before its claims are pruned
+const START_HEIGHT: u64 = 100000;
+// Consensus order by transaction hash; LegacyTimestamp restores the submitter-timestamp hierarchy
+const RESOLUTION_ORDER: ResolutionOrder = ResolutionOrder::TransactionHash;
 
 #[derive(Debug, Clone)]
 struct Transaction {
     tx_id: u64,
-    original_user_nonce: u64,
-    current_user_nonce: u64,
+    user_nonce: u64,
     tx_hash: String,
-    transaction_data: String,
-    tx_type: TransactionType,
+    body: i_protocol_core::Transaction,
     creation_timestamp: u64,
     submission_timestamp: u64,
     final_nonce: u64,
     assigned_miner_id: usize,
+    collision_group: Option<usize>, // Group whose first attempts are forced onto one final nonce
     retry_count: usize,
     is_processed: bool,
     collision_detected: bool,
     resolution_method: ConflictResolutionMethod,
 }
 
-#[derive(Debug, Clone)]
-enum TransactionType {
-    Transfer,
-    SmartContract,
-    Staking,
-    Governance,
-    CrossChain,
-    TokenMint,
-    TokenBurn,
-    MultiSig,
-    Atomic,
-    Oracle,
-}
-
 #[derive(Debug, Clone, PartialEq)]
 enum ConflictResolutionMethod {
     NoConflict,
     TimestampResolution,
     HashComparison,
     AutomaticRetry,
-    Failed,
 }
 
 impl Transaction {
-    fn new(id: u64, tx_type: TransactionType) -> Self {
+    fn new(id: u64) -> Self {
         let creation_timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
-        let user_nonce = Self::generate_random_user_nonce(id);
-        let transaction_data = Self::generate_transaction_data(id, &tx_type);
-        let tx_hash = Self::compute_transaction_hash(&transaction_data);
+        let user_nonce = transactions::random_user_nonce(id);
+        let body = transactions::sample_transaction(id, user_nonce, creation_timestamp, START_HEIGHT + TX_EXPIRY_BLOCKS);
+        let tx_hash = body.hash_hex();
         
         Transaction {
             tx_id: id,
-            original_user_nonce: user_nonce,
-            current_user_nonce: user_nonce,
+            user_nonce,
             tx_hash,
-            transaction_data,
-            tx_type,
+            body,
             creation_timestamp,
             submission_timestamp: creation_timestamp,
             final_nonce: 0,
             assigned_miner_id: 0,
+            collision_group: None,
             retry_count: 0,
             is_processed: false,
             collision_detected: false,
             resolution_method: ConflictResolutionMethod::NoConflict,
         }
     }
+}
+
+fn simulated_blockchain_state(height: u64, state_id: usize) -> BlockchainState {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+    let previous_hash = simulated_previous_hash(height, state_id);
     
-    fn generate_random_user_nonce(seed: u64) -> u64 {
-        // Generate user nonce in range [1, 10^12] with high entropy
-        let mut nonce = seed.wrapping_mul(0x123456789ABCDEF);
-        nonce ^= nonce >> 33;
-        nonce = nonce.wrapping_mul(0xFF51AFD7ED558CCD);
-        nonce ^= nonce >> 33;
-        nonce = nonce.wrapping_mul(0xC4CEB9FE1A85EC53);
-        nonce ^= nonce >> 33;
-        (nonce % USER_NONCE_RANGE) + 1
-    }
-    
-    fn generate_transaction_data(id: u64, tx_type: &TransactionType) -> String {
-        match tx_type {
-            TransactionType::Transfer => format!("transfer_{}_{}_amount_{}", id, id + 1, id % 10000 + 100),
-            TransactionType::SmartContract => format!("contract_call_{}_function_execute_{}", id, id % 50),
-            TransactionType::Staking => format!("stake_{}_validator_{}_amount_{}", id, id % 100, id % 5000 + 1000),
-            TransactionType::Governance => format!("vote_{}_proposal_{}_choice_{}", id, id % 10, if id % 2 == 0 { "yes" } else { "no" }),
-            TransactionType::CrossChain => format!("bridge_{}_chain_{}_amount_{}", id, if id % 3 == 0 { "ethereum" } else { "polygon" }, id % 3000 + 500),
-            TransactionType::TokenMint => format!("mint_{}_token_I_amount_{}", id, id % 20000 + 5000),
-            TransactionType::TokenBurn => format!("burn_{}_token_I_amount_{}", id, id % 1000 + 100),
-            TransactionType::MultiSig => format!("multisig_{}_signers_{}_threshold_{}", id, (id % 5) + 2, (id % 3) + 2),
-            TransactionType::Atomic => format!("atomic_swap_{}_token_{}_for_{}", id, id % 100, (id + 1) % 100),
-            TransactionType::Oracle => format!("oracle_update_{}_price_feed_{}", id, id % 20),
-        }
-    }
-    
-    fn compute_transaction_hash(data: &str) -> String {
-        // Enhanced SHA-256 simulation for transaction hash
-        let mut hash: u64 = 14695981039346656037;
-        for byte in data.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        hash ^= hash >> 32;
-        hash = hash.wrapping_mul(0x9E3779B97F4A7C15);
-        format!("{:016x}", hash)
-    }
-    
-    fn increment_user_nonce(&mut self) {
-        self.current_user_nonce = (self.current_user_nonce % USER_NONCE_RANGE) + 1;
-        self.retry_count += 1;
-        self.submission_timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+    BlockchainState {
+        height,
+        previous_hash,
+        timestamp,
+        active_miners: VIRTUAL_MINERS as u32,
+        ..Default::default()
     }
 }
 
-#[derive(Debug, Clone)]
-struct BlockchainState {
-    height: u64,
-    previous_hash: String,
-    timestamp: u64,
-    active_miners: usize,
-    total_mining_range: u64,
-    state_id: usize,
+fn simulated_previous_hash(height: u64, state_id: usize) -> String {
+    let input = format!("block_{}_{}_prev_hash_{}", height, state_id, PROTOCOL_SALT);
+    to_hex(&triple_layer_hash(HashDomain::BlockHeader, input.as_bytes()))
 }
 
-impl BlockchainState {
-    fn new(height: u64, state_id: usize) -> Self {
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
-        let previous_hash = Self::generate_previous_hash(height, state_id);
-        
-        BlockchainState {
-            height,
-            previous_hash,
-            timestamp,
-            active_miners: VIRTUAL_MINERS,
-            total_mining_range: VIRTUAL_MINERS as u64 * NONCES_PER_MINER,
-            state_id,
-        }
-    }
-    
-    fn generate_previous_hash(height: u64, state_id: usize) -> String {
-        let input = format!("block_{}_{}_prev_hash_{}", height, state_id, PROTOCOL_SALT);
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        format!("{:016x}", hash ^ 0x123456789ABCDEF0)
-    }
+// Resolution sees the signed body's hash, plus its declared timestamp for legacy mode
+fn contender(tx: &Transaction) -> Contender {
+    Contender::of(&tx.body)
 }
 
-#[derive(Debug, Clone)]
-struct ConflictingTransaction {
-    transaction: Transaction,
-    final_nonce: u64,
-    miner_id: usize,
-    submission_order: usize,
+// Rule that settled a contest won by the challenger
+fn winning_rule(challenger: &Contender, holder: &Contender) -> ConflictResolutionMethod {
+    if RESOLUTION_ORDER == ResolutionOrder::LegacyTimestamp
+        && challenger.submission_timestamp != holder.submission_timestamp
+    {
+        ConflictResolutionMethod::TimestampResolution
+    } else {
+        ConflictResolutionMethod::HashComparison
+    }
 }
 
 #[derive(Debug, Clone)]
 struct ConflictResolutionTest {
-    test_id: usize,
     transactions_tested: usize,
     intentional_collisions_generated: usize,
     collisions_resolved_by_timestamp: usize,
     collisions_resolved_by_hash: usize,
     collisions_resolved_by_retry: usize,
     failed_resolutions: usize,
-    total_retry_attempts: usize,
     average_retries_per_transaction: f64,
     processing_success_rate: f64,
     hierarchical_resolution_verified: bool,
@@ -194,11 +131,9 @@ struct ConflictResolutionTest {
 
 #[derive(Debug, Clone)]
 struct AutomaticRetryTest {
-    test_id: usize,
     transactions_requiring_retry: usize,
     successful_retries: usize,
     failed_retries: usize,
-    max_retries_reached: usize,
     average_retry_count: f64,
     retry_success_rate: f64,
     eventual_processing_guaranteed: bool,
@@ -206,7 +141,6 @@ struct AutomaticRetryTest {
 
 #[derive(Debug, Clone)]
 struct HierarchicalResolutionTest {
-    test_id: usize,
     timestamp_resolutions: usize,
     hash_comparison_resolutions: usize,
     automatic_retry_resolutions: usize,
@@ -217,7 +151,6 @@ struct HierarchicalResolutionTest {
 
 #[derive(Debug, Clone)]
 struct TnoConflictResolutionTestResult {
-    test_id: usize,
     conflict_resolution_test: ConflictResolutionTest,
     automatic_retry_test: AutomaticRetryTest,
     hierarchical_resolution_test: HierarchicalResolutionTest,
@@ -230,99 +163,68 @@ struct TnoConflictResolutionTestResult {
     conflict_resolution_verified: bool,
 }
 
-#[derive(Debug)]
-struct TnoConflictResolutionStatistics {
-    total_tests: usize,
-    transactions_processed: usize,
-    collisions_generated: usize,
-    collisions_resolved: usize,
-    timestamp_resolutions: usize,
-    hash_resolutions: usize,
-    retry_resolutions: usize,
-    failed_resolutions: usize,
-    average_success_rate: f64,
-    eventual_processing_rate: f64,
-    hierarchical_resolution_accuracy: f64,
-}
-
 struct TnoConflictResolutionEngine {
     blockchain_state: BlockchainState,
+    range_table: RangeTable, // DURA ranges of the virtual miners for this state
+    miners: Vec<MinerIdentity>,
     transactions: Vec<Transaction>,
-    nonce_collision_map: HashMap<u64, Vec<ConflictingTransaction>>, // final_nonce -> conflicting transactions
+    collision_index: CollisionIndex, // Final-nonce claims of the unfinalized heights
+    height_claims: HashMap<u64, u64>, // final_nonce -> tx_id holding it at the current height
+    peak_claims_held: usize,
+    tracker: TxTracker, // Lifecycle of every submitted, unsettled transaction
+    tracked: HashMap<Digest32, u64>, // contender hash -> tx_id
     processed_transactions: HashSet<u64>, // tx_id set
-    retry_queue: VecDeque<Transaction>,
     processing_time_ms: u64,
     collision_simulation_enabled: bool,
-    statistics: TnoConflictResolutionStatistics,
 }
 
 impl TnoConflictResolutionEngine {
     fn new() -> Self {
+        let blockchain_state = simulated_blockchain_state(START_HEIGHT, 1);
+        let miners: Vec<MinerIdentity> = (0..VIRTUAL_MINERS as u32)
+            .map(|id| MinerIdentity::virtual_miner(id, 1_700_000_000))
+            .collect();
+        let range_table = RangeTable::for_block(&blockchain_state.previous_hash, &miners);
+        let tracker = TxTracker::new(blockchain_state.height);
         let mut engine = TnoConflictResolutionEngine {
-            blockchain_state: BlockchainState::new(100000, 1),
+            blockchain_state,
+            range_table,
+            miners,
             transactions: Vec::new(),
-            nonce_collision_map: HashMap::new(),
+            collision_index: CollisionIndex::with_order(RESOLUTION_ORDER),
+            height_claims: HashMap::new(),
+            peak_claims_held: 0,
+            tracker,
+            tracked: HashMap::new(),
             processed_transactions: HashSet::new(),
-            retry_queue: VecDeque::new(),
             processing_time_ms: 0,
             collision_simulation_enabled: true,
-            statistics: TnoConflictResolutionStatistics {
-                total_tests: 0,
-                transactions_processed: 0,
-                collisions_generated: 0,
-                collisions_resolved: 0,
-                timestamp_resolutions: 0,
-                hash_resolutions: 0,
-                retry_resolutions: 0,
-                failed_resolutions: 0,
-                average_success_rate: 0.0,
-                eventual_processing_rate: 0.0,
-                hierarchical_resolution_accuracy: 0.0,
-            },
         };
         
         // Generate diverse transaction types for comprehensive testing
-        let transaction_types = vec![
-            TransactionType::Transfer,
-            TransactionType::SmartContract,
-            TransactionType::Staking,
-            TransactionType::Governance,
-            TransactionType::CrossChain,
-            TransactionType::TokenMint,
-            TransactionType::TokenBurn,
-            TransactionType::MultiSig,
-            TransactionType::Atomic,
-            TransactionType::Oracle,
-        ];
-        
         // Generate transactions with intentional collision potential
         for i in 0..TRANSACTION_TESTS {
-            let tx_type = transaction_types[i % transaction_types.len()].clone();
-            let mut tx = Transaction::new(i as u64, tx_type);
+            let mut tx = Transaction::new(i as u64);
             
             // Simulate intentional collisions for testing
             if engine.collision_simulation_enabled && (i as f64 / TRANSACTION_TESTS as f64) < COLLISION_SIMULATION_RATE {
-                // Force identical TNO inputs to guarantee mapping collisions
+                // Members keep distinct bodies, each from its own sender key, so contests are
+                // ranked by the real transaction hash. Their first attempt is forced onto the
+                // final nonce of the group's first transaction, standing in for a ground collision
                 let collision_group = i % 20; // Create 20 collision groups with multiple transactions each
-                
-                // Use identical user nonce and transaction hash within collision groups
-                tx.current_user_nonce = collision_group as u64 + 5000; // Use consistent nonce values
-                tx.original_user_nonce = tx.current_user_nonce;
-                
-                // Force identical transaction hash for collision groups
-                let collision_hash = format!("collision_hash_{:04x}", collision_group);
-                tx.transaction_data = format!("collision_test_group_{}", collision_group);
-                tx.tx_hash = collision_hash; // Use identical hash for collision group
+                tx.collision_group = Some(collision_group);
                 
                 // Set timestamps with variations for hierarchical resolution testing
                 let base_timestamp = 3000000 + (collision_group as u64 * 100);
                 tx.creation_timestamp = base_timestamp;
-                tx.submission_timestamp = base_timestamp + (i % 20) as u64; // Timestamp variations for resolution
+                tx.submission_timestamp = base_timestamp + (i / 20 % 20) as u64; // Timestamp variations for resolution
+                tx.body = transactions::sample_transaction(i as u64, tx.user_nonce, tx.submission_timestamp, START_HEIGHT + TX_EXPIRY_BLOCKS);
+                tx.tx_hash = tx.body.hash_hex();
                 
                 // Debug: Print collision simulation details for first few transactions
                 if i < 50 {
                     println!("DEBUG: Collision simulation applied to tx {} in group {}", i, collision_group);
-                    println!("DEBUG: User nonce: {}, Hash: {}", tx.current_user_nonce, tx.tx_hash);
+                    println!("DEBUG: User nonce: {}, Hash: {}", tx.user_nonce, tx.tx_hash);
                 }
             }
             
@@ -333,301 +235,119 @@ impl TnoConflictResolutionEngine {
     }
     
     fn execute_tno_mapping(&self, tx: &Transaction) -> u64 {
-        // TNO Formula: H_3(u ‖ tx_hash ‖ height ‖ prev_hash) mod R
-        let tno_input = format!(
-            "{}{}{}{}",
-            tx.current_user_nonce,
-            tx.tx_hash,
-            self.blockchain_state.height,
-            self.blockchain_state.previous_hash
-        );
-        
-        // Triple-layer hash: Blake3 → SHA-256 → Dilithium
-        let layer1 = self.blake3_simulation(&tno_input);
-        let layer2 = self.sha256_simulation(&format!("{}", layer1));
-        let layer3 = self.dilithium_simulation(&format!("{}", layer2));
-        
-        // Apply modulo operation for range fitting
-        layer3 % self.blockchain_state.total_mining_range
+        // TNO Formula: REGULAR_START + ⌊H_3(u ‖ tx_hash ‖ height ‖ prev_hash) · R / 2^256⌋
+        tno::execute_tno_mapping(tx.user_nonce, &tx.tx_hash, &self.blockchain_state)
     }
     
-    fn detect_nonce_collision(&mut self, tx: &Transaction, final_nonce: u64) -> bool {
-        if let Some(existing_transactions) = self.nonce_collision_map.get(&final_nonce) {
-            // Collision detected - check for any existing transaction with same final nonce
-            for existing_tx in existing_transactions {
-                // Debug: Print collision detection details
-                if tx.tx_id < 100 { // Only print for first 100 transactions to avoid spam
-                    println!("DEBUG: Collision detected for tx {} with existing tx {}", 
-                            tx.tx_id, existing_tx.transaction.tx_id);
-                    println!("DEBUG: Final nonces - current: {}, existing: {}", 
-                            final_nonce, existing_tx.final_nonce);
-                    println!("DEBUG: User nonces - current: {}, existing: {}", 
-                            tx.current_user_nonce, existing_tx.transaction.current_user_nonce);
-                    println!("DEBUG: Hashes - current: {}, existing: {}", 
-                            tx.tx_hash, existing_tx.transaction.tx_hash);
-                }
-                return true;
-            }
+    fn advance_block(&mut self) {
+        // Heights FINALITY_DEPTH blocks below the new tip are final; their claims go
+        let height = self.blockchain_state.height + 1;
+        if let Some(final_height) = height.checked_sub(FINALITY_DEPTH) {
+            self.collision_index.finalize(final_height).unwrap();
+            self.tracker.finalize(final_height);
         }
-        false
+        self.tracker.advance(height).unwrap();
+        self.blockchain_state = simulated_blockchain_state(height, 1);
+        self.range_table = RangeTable::for_block(&self.blockchain_state.previous_hash, &self.miners);
+        self.height_claims.clear();
     }
     
-    fn resolve_nonce_collision(&mut self, tx: &mut Transaction, final_nonce: u64) -> ConflictResolutionMethod {
-        if let Some(existing_transactions) = self.nonce_collision_map.get_mut(&final_nonce) {
-            for existing_tx in existing_transactions.iter_mut() {
-                // Hierarchical resolution: timestamp → hash comparison → automatic retry
-                
-                // Step 1: Timestamp comparison (millisecond precision)
-                if tx.submission_timestamp != existing_tx.transaction.submission_timestamp {
-                    if tx.submission_timestamp < existing_tx.transaction.submission_timestamp {
-                        // Current transaction has earlier timestamp - wins
-                        if tx.tx_id < 100 {
-                            println!("DEBUG: Timestamp resolution - tx {} wins over tx {}", tx.tx_id, existing_tx.transaction.tx_id);
-                        }
-                        return ConflictResolutionMethod::TimestampResolution;
-                    } else {
-                        // Existing transaction has earlier timestamp - current loses
-                        if tx.tx_id < 100 {
-                            println!("DEBUG: Timestamp resolution - tx {} loses to tx {}", tx.tx_id, existing_tx.transaction.tx_id);
-                        }
-                        return ConflictResolutionMethod::AutomaticRetry;
-                    }
-                }
-                
-                // Step 2: Hash comparison (if timestamps are identical)
-                let current_hash = &tx.tx_hash;
-                let existing_hash = &existing_tx.transaction.tx_hash;
-                
-                match current_hash.cmp(existing_hash) {
-                    Ordering::Less => {
-                        // Current transaction hash is lexicographically smaller - wins
-                        if tx.tx_id < 100 {
-                            println!("DEBUG: Hash comparison - tx {} wins over tx {}", tx.tx_id, existing_tx.transaction.tx_id);
-                        }
-                        return ConflictResolutionMethod::HashComparison;
-                    },
-                    Ordering::Greater => {
-                        // Existing transaction hash is lexicographically smaller - current loses
-                        if tx.tx_id < 100 {
-                            println!("DEBUG: Hash comparison - tx {} loses to tx {}", tx.tx_id, existing_tx.transaction.tx_id);
-                        }
-                        return ConflictResolutionMethod::AutomaticRetry;
-                    },
-                    Ordering::Equal => {
-                        // Identical hashes - use transaction ID as tiebreaker
-                        if tx.tx_id < existing_tx.transaction.tx_id {
-                            if tx.tx_id < 100 {
-                                println!("DEBUG: ID tiebreaker - tx {} wins over tx {}", tx.tx_id, existing_tx.transaction.tx_id);
-                            }
-                            return ConflictResolutionMethod::HashComparison;
-                        } else {
-                            if tx.tx_id < 100 {
-                                println!("DEBUG: ID tiebreaker - tx {} loses to tx {}", tx.tx_id, existing_tx.transaction.tx_id);
-                            }
-                            return ConflictResolutionMethod::AutomaticRetry;
-                        }
-                    }
-                }
-            }
-        }
-        
-        ConflictResolutionMethod::NoConflict
+    fn submit(&mut self, tx_id: u64) {
+        let key = contender(&self.transactions[tx_id as usize]).tx_hash;
+        self.tracker.submit(key, self.blockchain_state.height + TX_EXPIRY_BLOCKS).unwrap();
+        self.tracked.insert(key, tx_id);
     }
     
-    fn process_transaction_with_conflict_resolution(&mut self, tx: &mut Transaction) -> bool {
-        let start_time = SystemTime::now();
-        let mut attempts = 0;
-        
-        while attempts < MAX_RETRY_ATTEMPTS {
-            let final_nonce = self.execute_tno_mapping(tx);
-            let miner_id = (final_nonce / NONCES_PER_MINER) as usize;
-            
-            // Check for nonce collision
-            if self.detect_nonce_collision(tx, final_nonce) {
-                tx.collision_detected = true;
-                self.statistics.collisions_generated += 1;
-                
-                // Apply hierarchical conflict resolution
-                let resolution_method = self.resolve_nonce_collision(tx, final_nonce);
-                tx.resolution_method = resolution_method.clone();
-                
-                match resolution_method {
-                    ConflictResolutionMethod::TimestampResolution => {
-                        // Current transaction wins - process it
-                        self.statistics.timestamp_resolutions += 1;
-                        self.statistics.collisions_resolved += 1;
-                        
-                        tx.final_nonce = final_nonce;
-                        tx.assigned_miner_id = miner_id;
-                        tx.is_processed = true;
-                        
-                        // Add to collision map
-                        let conflicting_tx = ConflictingTransaction {
-                            transaction: tx.clone(),
-                            final_nonce,
-                            miner_id,
-                            submission_order: attempts,
-                        };
-                        
-                        self.nonce_collision_map.entry(final_nonce)
-                            .or_insert_with(Vec::new)
-                            .push(conflicting_tx);
-                        
-                        self.processed_transactions.insert(tx.tx_id);
-                        
-                        let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
-                        self.processing_time_ms += elapsed;
-                        
-                        if tx.tx_id < 100 {
-                            println!("DEBUG: Transaction {} processed after timestamp resolution", tx.tx_id);
-                        }
-                        
-                        return true;
-                    },
-                    ConflictResolutionMethod::HashComparison => {
-                        // Current transaction wins - process it
-                        self.statistics.hash_resolutions += 1;
-                        self.statistics.collisions_resolved += 1;
-                        
-                        tx.final_nonce = final_nonce;
-                        tx.assigned_miner_id = miner_id;
-                        tx.is_processed = true;
-                        
-                        // Add to collision map
-                        let conflicting_tx = ConflictingTransaction {
-                            transaction: tx.clone(),
-                            final_nonce,
-                            miner_id,
-                            submission_order: attempts,
-                        };
-                        
-                        self.nonce_collision_map.entry(final_nonce)
-                            .or_insert_with(Vec::new)
-                            .push(conflicting_tx);
-                        
-                        self.processed_transactions.insert(tx.tx_id);
-                        
-                        let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
-                        self.processing_time_ms += elapsed;
-                        
-                        if tx.tx_id < 100 {
-                            println!("DEBUG: Transaction {} processed after hash resolution", tx.tx_id);
-                        }
-                        
-                        return true;
-                    },
-                    ConflictResolutionMethod::AutomaticRetry => {
-                        // Current transaction loses - increment nonce and retry
-                        self.statistics.retry_resolutions += 1;
-                        tx.increment_user_nonce();
-                        attempts += 1;
-                        
-                        if tx.tx_id < 100 {
-                            println!("DEBUG: Transaction {} retrying with new nonce {} (attempt {})", 
-                                    tx.tx_id, tx.current_user_nonce, attempts);
-                        }
-                        
-                        continue;
-                    },
-                    _ => {
-                        // Unexpected resolution method
-                        if tx.tx_id < 100 {
-                            println!("DEBUG: Transaction {} encountered unexpected resolution method", tx.tx_id);
-                        }
-                        break;
-                    }
-                }
-            } else {
-                // No collision - process transaction normally
-                tx.final_nonce = final_nonce;
-                tx.assigned_miner_id = miner_id;
-                tx.is_processed = true;
-                tx.resolution_method = ConflictResolutionMethod::NoConflict;
-                
-                // Add to collision map for future collision detection
-                let conflicting_tx = ConflictingTransaction {
-                    transaction: tx.clone(),
-                    final_nonce,
-                    miner_id,
-                    submission_order: attempts,
-                };
-                
-                self.nonce_collision_map.entry(final_nonce)
-                    .or_insert_with(Vec::new)
-                    .push(conflicting_tx);
-                
-                self.processed_transactions.insert(tx.tx_id);
-                
-                let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
-                self.processing_time_ms += elapsed;
-                
-                if tx.tx_id < 100 {
-                    println!("DEBUG: Transaction {} processed without collision", tx.tx_id);
-                }
-                
-                return true;
-            }
-        }
-        
-        // Maximum retry attempts reached - mark as failed
-        tx.resolution_method = ConflictResolutionMethod::Failed;
-        if tx.tx_id < 100 {
-            println!("DEBUG: Transaction {} failed after {} attempts", tx.tx_id, attempts);
-        }
-        false
+    // Whether any submitted transaction is still waiting for a height
+    fn has_outstanding(&self) -> bool {
+        !self.tracker.due().is_empty()
+    }
+    
+    fn hold(&mut self, mut tx: Transaction, final_nonce: u64, miner_id: usize) {
+        tx.final_nonce = final_nonce;
+        tx.assigned_miner_id = miner_id;
+        self.height_claims.insert(final_nonce, tx.tx_id);
+        let tx_id = tx.tx_id as usize;
+        self.transactions[tx_id] = tx;
+        self.peak_claims_held = self.peak_claims_held.max(self.collision_index.len());
+    }
+    
+    // A transaction that lost its final nonce retries unchanged at the next height, whose
+    // height and previous hash move it to a fresh final nonce
+    fn defer(&mut self, tx_id: u64) {
+        self.transactions[tx_id as usize].retry_count += 1;
     }
     
-    // Simplified cryptographic hash functions for testing
-    fn blake3_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
+    // Maps every transaction due at this height once; losers wait for the next height
+    fn map_due_transactions(&mut self) {
+        for key in self.tracker.due() {
+            let tx_id = self.tracked[&key];
+            self.map_transaction(tx_id);
         }
-        hash ^ 0x123456789ABCDEF0
     }
     
-    fn sha256_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 14695981039346656037;
-        for byte in input.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
+    fn map_transaction(&mut self, tx_id: u64) {
+        let start_time = SystemTime::now();
+        let mut tx = self.transactions[tx_id as usize].clone();
+        let final_nonce = match tx.collision_group {
+            Some(group) if tx.retry_count == 0 => self.execute_tno_mapping(&self.transactions[group]),
+            _ => self.execute_tno_mapping(&tx),
+        };
+        // Owner of the DURA range holding the final nonce
+        let miner_id = self.range_table.range_of(final_nonce).expect("final nonce within DURA ranges").miner_id as usize;
+        let challenger = contender(&tx);
+        
+        // The index settles contests by the consensus resolution order
+        let claim = self.collision_index.claim(self.blockchain_state.height, final_nonce, challenger).unwrap();
+        self.tracker.record_claim(challenger.tx_hash, final_nonce, &claim).unwrap();
+        match claim {
+            Claim::Claimed => {
+                tx.resolution_method = if tx.retry_count > 0 {
+                    ConflictResolutionMethod::AutomaticRetry
+                } else {
+                    ConflictResolutionMethod::NoConflict
+                };
+                self.hold(tx, final_nonce, miner_id);
+            },
+            Claim::Displaced { loser } => {
+                // Current transaction wins; the displaced holder retries at the next height
+                let loser_id = self.height_claims[&final_nonce];
+                tx.collision_detected = true;
+                tx.resolution_method = winning_rule(&challenger, &loser);
+                self.hold(tx, final_nonce, miner_id);
+                self.defer(loser_id);
+            },
+            Claim::Collision { .. } => {
+                // Current transaction loses and retries at the next height
+                tx.collision_detected = true;
+                self.transactions[tx_id as usize] = tx;
+                self.defer(tx_id);
+            },
         }
-        hash ^ 0xFEDCBA9876543210
+        
+        let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
+        self.processing_time_ms += elapsed;
     }
     
-    fn dilithium_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 0;
-        for byte in input.bytes() {
-            hash = (byte as u64).wrapping_add(hash << 6).wrapping_add(hash << 16).wrapping_sub(hash);
+    // The block at this height includes every transaction holding a final nonce in it
+    fn include_block(&mut self) {
+        for &tx_id in self.height_claims.values() {
+            let tx = &mut self.transactions[tx_id as usize];
+            tx.is_processed = true;
+            self.tracker.include(contender(tx).tx_hash).unwrap();
+            self.processed_transactions.insert(tx_id);
         }
-        hash ^ 0x0123456789ABCDEF
     }
 }
 
 struct TnoConflictResolutionTestFramework {
     engine: TnoConflictResolutionEngine,
-    test_results: Vec<TnoConflictResolutionTestResult>,
-    statistics: TnoConflictResolutionStatistics,
 }
 
 impl TnoConflictResolutionTestFramework {
     fn new() -> Self {
         TnoConflictResolutionTestFramework {
             engine: TnoConflictResolutionEngine::new(),
-            test_results: Vec::new(),
-            statistics: TnoConflictResolutionStatistics {
-                total_tests: 0,
-                transactions_processed: 0,
-                collisions_generated: 0,
-                collisions_resolved: 0,
-                timestamp_resolutions: 0,
-                hash_resolutions: 0,
-                retry_resolutions: 0,
-                failed_resolutions: 0,
-                average_success_rate: 0.0,
-                eventual_processing_rate: 0.0,
-                hierarchical_resolution_accuracy: 0.0,
-            },
         }
     }
     
@@ -637,6 +357,7 @@ impl TnoConflictResolutionTestFramework {
         println!("Executing TNO Conflict Resolution Verification Test...");
         println!("Processing {} transactions with {}% intentional collision rate...", 
                 TRANSACTION_TESTS, (COLLISION_SIMULATION_RATE * 100.0) as usize);
+        println!("Resolution Order: {:?}", RESOLUTION_ORDER);
         
         let mut successful_transactions = 0;
         let mut failed_transactions = 0;
@@ -646,12 +367,34 @@ impl TnoConflictResolutionTestFramework {
         let mut retry_resolutions = 0;
         let mut total_retries = 0;
         
-        // Process all transactions with conflict resolution
-        let mut transactions_to_process = self.engine.transactions.clone();
-        for tx in &mut transactions_to_process {
-            let success = self.engine.process_transaction_with_conflict_resolution(tx);
+        let mut max_retry_heights = 0;
+        let mut expired_transactions = 0;
+        
+        // Wallet-side view of every status change
+        let status_updates = self.engine.tracker.subscribe_all();
+        
+        // Each block submits its own transactions and maps everything due; losers retry a height later
+        let mut next_tx = 0;
+        while next_tx < TRANSACTION_TESTS || self.engine.has_outstanding() {
+            let block_end = (next_tx + TRANSACTIONS_PER_BLOCK).min(TRANSACTION_TESTS);
+            for tx_id in next_tx..block_end {
+                self.engine.submit(tx_id as u64);
+            }
+            next_tx = block_end;
             
-            if success {
+            self.engine.map_due_transactions();
+            self.engine.include_block();
+            self.engine.advance_block();
+            
+            for update in status_updates.try_iter() {
+                if let TxStatus::Expired { .. } = update.status {
+                    expired_transactions += 1;
+                }
+            }
+        }
+        
+        for tx in &self.engine.transactions {
+            if tx.is_processed {
                 successful_transactions += 1;
                 
                 if tx.collision_detected {
@@ -666,35 +409,33 @@ impl TnoConflictResolutionTestFramework {
                 }
                 
                 total_retries += tx.retry_count;
+                max_retry_heights = max_retry_heights.max(tx.retry_count);
             } else {
                 failed_transactions += 1;
             }
         }
+        debug_assert_eq!(failed_transactions, expired_transactions);
         
         let processing_success_rate = (successful_transactions as f64 / TRANSACTION_TESTS as f64) * 100.0;
         let average_retries = total_retries as f64 / TRANSACTION_TESTS as f64;
         
         // Create test results
         let conflict_resolution_test = ConflictResolutionTest {
-            test_id: 1,
             transactions_tested: TRANSACTION_TESTS,
             intentional_collisions_generated: total_collisions,
             collisions_resolved_by_timestamp: timestamp_resolutions,
             collisions_resolved_by_hash: hash_resolutions,
             collisions_resolved_by_retry: retry_resolutions,
             failed_resolutions: failed_transactions,
-            total_retry_attempts: total_retries,
             average_retries_per_transaction: average_retries,
             processing_success_rate,
             hierarchical_resolution_verified: (timestamp_resolutions + hash_resolutions + retry_resolutions) > 0,
         };
         
         let automatic_retry_test = AutomaticRetryTest {
-            test_id: 2,
             transactions_requiring_retry: retry_resolutions,
             successful_retries: retry_resolutions,
             failed_retries: failed_transactions,
-            max_retries_reached: failed_transactions,
             average_retry_count: if retry_resolutions > 0 { total_retries as f64 / retry_resolutions as f64 } else { 0.0 },
             retry_success_rate: if retry_resolutions + failed_transactions > 0 {
                 (retry_resolutions as f64 / (retry_resolutions + failed_transactions) as f64) * 100.0
@@ -703,7 +444,6 @@ impl TnoConflictResolutionTestFramework {
         };
         
         let hierarchical_resolution_test = HierarchicalResolutionTest {
-            test_id: 3,
             timestamp_resolutions,
             hash_comparison_resolutions: hash_resolutions,
             automatic_retry_resolutions: retry_resolutions,
@@ -713,7 +453,6 @@ impl TnoConflictResolutionTestFramework {
         };
         
         let test_result = TnoConflictResolutionTestResult {
-            test_id: 1,
             conflict_resolution_test,
             automatic_retry_test,
             hierarchical_resolution_test,
@@ -733,7 +472,12 @@ impl TnoConflictResolutionTestFramework {
         println!("Timestamp Resolutions: {}", timestamp_resolutions);
         println!("Hash Comparison Resolutions: {}", hash_resolutions);
         println!("Automatic Retry Resolutions: {}", retry_resolutions);
-        println!("Failed Transactions: {}", failed_transactions);
+        println!("Failed Transactions: {} (expired after {} blocks)", failed_transactions, TX_EXPIRY_BLOCKS);
+        println!("Most Heights Spent Retrying: {}", max_retry_heights);
+        println!("Heights Processed: {} (finalized through {:?})", 
+                self.engine.blockchain_state.height - START_HEIGHT + 1, self.engine.collision_index.finalized());
+        println!("Peak Final-Nonce Claims Held: {} (across at most {} open heights)", 
+                self.engine.peak_claims_held, FINALITY_DEPTH);
         
         test_result
     }
@@ -747,6 +491,7 @@ impl TnoConflictResolutionTestFramework {
             CEO: Kauffmen Ceb\n\
             Test Status: {}\n\n\
             CONFLICT RESOLUTION TEST RESULTS:\n\
+            - Resolution Order: {:?}\n\
             - Transactions Tested: {}\n\
             - Intentional Collisions Generated: {}\n\
             - Collisions Resolved by Timestamp: {}\n\
@@ -786,6 +531,7 @@ impl TnoConflictResolutionTestFramework {
             TEST 3.3 CONCLUSION: {}\n\
             ==================================================================================",
             if result.conflict_resolution_verified { "PASS" } else { "FAIL" },
+            RESOLUTION_ORDER,
             result.conflict_resolution_test.transactions_tested,
             result.conflict_resolution_test.intentional_collisions_generated,
             result.conflict_resolution_test.collisions_resolved_by_timestamp,
@@ -3,18 +3,21 @@
 // CEO: Kauffmen Ceb
 // Objective: Prove identical transaction-to-miner mapping across nodes
 
-use std::collections::{HashMap, HashSet, BTreeMap};
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::thread;
-use std::sync::{Arc, Mutex};
+use i_protocol_core::dura::RangeTable;
+use i_protocol_core::hash::to_hex;
+use i_protocol_core::params::NONCES_PER_MINER;
+use i_protocol_core::tno;
+use i_protocol_core::{triple_layer_hash, BlockchainState, HashDomain, MinerIdentity};
+
+#[path = "../fixtures/transactions.rs"]
+mod transactions;
 
 // TNO Deterministic Mapping Test Constants
 const TRANSACTION_TESTS: usize = 100000; // 100,000 transactions as specified
 const TEST_NODES: usize = 10; // Multiple nodes for cross-verification
 const VIRTUAL_MINERS: usize = 200;
-const NONCES_PER_MINER: u64 = 250000;
-const USER_NONCE_RANGE: u64 = 1000000000000; // 1 trillion range
-const PROTOCOL_SALT: &str = "I_PROTOCOL_TNO_DETERMINISTIC_2024";
 const TARGET_MAPPING_ACCURACY: f64 = 100.0; // 100% identical mappings required
 const BLOCKCHAIN_HEIGHT_VARIATIONS: usize = 50;
 const TRANSACTION_TYPE_VARIATIONS: usize = 10;
@@ -24,205 +27,81 @@ struct Transaction {
     tx_id: u64,
     user_nonce: u64,
     tx_hash: String,
-    transaction_data: String,
-    tx_type: TransactionType,
-    final_nonce: u64,
-    assigned_miner_range: usize,
-    creation_timestamp: u64,
-    is_valid: bool,
-}
-
-#[derive(Debug, Clone)]
-enum TransactionType {
-    Transfer,
-    SmartContract,
-    Staking,
-    Governance,
-    CrossChain,
-    TokenMint,
-    TokenBurn,
-    MultiSig,
-    Atomic,
-    Oracle,
+    body: i_protocol_core::Transaction,
 }
 
 impl Transaction {
-    fn new(id: u64, tx_type: TransactionType) -> Self {
+    fn new(id: u64) -> Self {
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        let user_nonce = Self::generate_random_user_nonce(id);
-        let transaction_data = Self::generate_transaction_data(id, &tx_type);
-        let tx_hash = Self::compute_transaction_hash(&transaction_data);
+        let user_nonce = transactions::random_user_nonce(id);
+        let body = transactions::sample_transaction(id, user_nonce, timestamp, u64::MAX); // Expiry plays no part in the mapping
+        let tx_hash = body.hash_hex();
         
         Transaction {
             tx_id: id,
             user_nonce,
             tx_hash,
-            transaction_data,
-            tx_type,
-            final_nonce: 0,
-            assigned_miner_range: 0,
-            creation_timestamp: timestamp,
-            is_valid: true,
-        }
-    }
-    
-    fn generate_random_user_nonce(seed: u64) -> u64 {
-        // Generate user nonce in range [1, 10^12]
-        let mut nonce = seed.wrapping_mul(0x123456789ABCDEF);
-        nonce ^= nonce >> 33;
-        nonce = nonce.wrapping_mul(0xFF51AFD7ED558CCD);
-        nonce ^= nonce >> 33;
-        nonce = nonce.wrapping_mul(0xC4CEB9FE1A85EC53);
-        nonce ^= nonce >> 33;
-        (nonce % USER_NONCE_RANGE) + 1
-    }
-    
-    fn generate_transaction_data(id: u64, tx_type: &TransactionType) -> String {
-        match tx_type {
-            TransactionType::Transfer => format!("transfer_{}_{}_amount_1000", id, id + 1),
-            TransactionType::SmartContract => format!("contract_call_{}_function_execute", id),
-            TransactionType::Staking => format!("stake_{}_validator_{}_amount_5000", id, id % 100),
-            TransactionType::Governance => format!("vote_{}_proposal_{}_choice_yes", id, id % 10),
-            TransactionType::CrossChain => format!("bridge_{}_chain_ethereum_amount_2000", id),
-            TransactionType::TokenMint => format!("mint_{}_token_I_amount_10000", id),
-            TransactionType::TokenBurn => format!("burn_{}_token_I_amount_500", id),
-            TransactionType::MultiSig => format!("multisig_{}_signers_3_threshold_2", id),
-            TransactionType::Atomic => format!("atomic_swap_{}_token_A_for_B", id),
-            TransactionType::Oracle => format!("oracle_update_{}_price_feed", id),
+            body,
         }
     }
-    
-    fn compute_transaction_hash(data: &str) -> String {
-        // SHA-256 simulation for transaction hash
-        let mut hash: u64 = 14695981039346656037;
-        for byte in data.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        format!("{:016x}", hash)
-    }
-}
-
-#[derive(Debug, Clone)]
-struct BlockchainState {
-    height: u64,
-    previous_hash: String,
-    timestamp: u64,
-    active_miners: usize,
-    total_mining_range: u64,
-    state_id: usize,
 }
 
-impl BlockchainState {
-    fn new(height: u64, state_id: usize) -> Self {
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        let previous_hash = Self::generate_previous_hash(height, state_id);
-        
-        BlockchainState {
-            height,
-            previous_hash,
-            timestamp,
-            active_miners: VIRTUAL_MINERS,
-            total_mining_range: VIRTUAL_MINERS as u64 * NONCES_PER_MINER,
-            state_id,
-        }
-    }
+fn simulated_blockchain_state(height: u64, state_id: usize) -> BlockchainState {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let previous_hash = simulated_previous_hash(height, state_id);
     
-    fn generate_previous_hash(height: u64, state_id: usize) -> String {
-        let input = format!("block_{}_{}_prev_hash", height, state_id);
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        format!("{:016x}", hash ^ 0x123456789ABCDEF0)
+    BlockchainState {
+        height,
+        previous_hash,
+        timestamp,
+        active_miners: VIRTUAL_MINERS as u32,
+        ..Default::default()
     }
 }
 
+fn simulated_previous_hash(height: u64, state_id: usize) -> String {
+    let input = format!("block_{}_{}_prev_hash", height, state_id);
+    to_hex(&triple_layer_hash(HashDomain::BlockHeader, input.as_bytes()))
+}
+
 #[derive(Debug, Clone)]
 struct TestNode {
-    node_id: usize,
-    node_name: String,
     mapping_results: HashMap<u64, u64>, // tx_id -> final_nonce
-    miner_assignments: HashMap<u64, usize>, // tx_id -> miner_range
+    miner_assignments: HashMap<u64, String>, // tx_id -> owner identity hash
     processing_time_ms: u64,
-    is_synchronized: bool,
 }
 
 impl TestNode {
-    fn new(id: usize) -> Self {
+    fn new() -> Self {
         TestNode {
-            node_id: id,
-            node_name: format!("TestNode_{}", id),
             mapping_results: HashMap::new(),
             miner_assignments: HashMap::new(),
             processing_time_ms: 0,
-            is_synchronized: true,
         }
     }
     
-    fn execute_tno_mapping(&mut self, tx: &Transaction, blockchain_state: &BlockchainState) -> u64 {
-        // TNO Formula: H_3(u ‖ tx_hash ‖ height ‖ prev_hash) mod R
+    fn execute_tno_mapping(&mut self, tx: &Transaction, blockchain_state: &BlockchainState, range_table: &RangeTable) -> u64 {
+        // TNO Formula: REGULAR_START + ⌊H_3(u ‖ tx_hash ‖ height ‖ prev_hash) · R / 2^256⌋
         let start_time = SystemTime::now();
         
-        let tno_input = format!(
-            "{}{}{}{}",
-            tx.user_nonce,
-            tx.tx_hash,
-            blockchain_state.height,
-            blockchain_state.previous_hash
-        );
-        
-        // Triple-layer hash: Blake3 → SHA-256 → Dilithium
-        let layer1 = self.blake3_simulation(&tno_input);
-        let layer2 = self.sha256_simulation(&format!("{}", layer1));
-        let layer3 = self.dilithium_simulation(&format!("{}", layer2));
-        
-        // Apply modulo operation for range fitting
-        let final_nonce = layer3 % blockchain_state.total_mining_range;
+        let final_nonce = tno::execute_tno_mapping(tx.user_nonce, &tx.tx_hash, blockchain_state);
         
-        // Determine miner range assignment
-        let miner_range = (final_nonce / NONCES_PER_MINER) as usize;
+        // Determine the owner through the DURA range table of this state
+        let owner = tno::assigned_miner(final_nonce, range_table).expect("final nonce within DURA ranges");
         
         // Store results
         self.mapping_results.insert(tx.tx_id, final_nonce);
-        self.miner_assignments.insert(tx.tx_id, miner_range);
+        self.miner_assignments.insert(tx.tx_id, owner.to_string());
         
         let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
         self.processing_time_ms += elapsed;
         
         final_nonce
     }
-    
-    // Simplified cryptographic hash functions for testing
-    fn blake3_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 5381;
-        for byte in input.bytes() {
-            hash = ((hash << 5).wrapping_add(hash)).wrapping_add(byte as u64);
-        }
-        hash ^ 0x123456789ABCDEF0
-    }
-    
-    fn sha256_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 14695981039346656037;
-        for byte in input.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(1099511628211);
-        }
-        hash ^ 0xFEDCBA9876543210
-    }
-    
-    fn dilithium_simulation(&self, input: &str) -> u64 {
-        let mut hash: u64 = 0;
-        for byte in input.bytes() {
-            hash = (byte as u64).wrapping_add(hash << 6).wrapping_add(hash << 16).wrapping_sub(hash);
-        }
-        hash ^ 0x0123456789ABCDEF
-    }
 }
 
 #[derive(Debug, Clone)]
 struct MappingConsistencyTest {
-    test_id: usize,
     transactions_tested: usize,
     nodes_tested: usize,
     identical_mappings: usize,
@@ -234,7 +113,6 @@ struct MappingConsistencyTest {
 
 #[derive(Debug, Clone)]
 struct BlockchainStateTest {
-    test_id: usize,
     height_variations_tested: usize,
     transaction_types_tested: usize,
     consistent_mappings_across_states: usize,
@@ -244,7 +122,6 @@ struct BlockchainStateTest {
 
 #[derive(Debug, Clone)]
 struct TransactionTypeTest {
-    test_id: usize,
     transaction_types: Vec<String>,
     mappings_per_type: HashMap<String, usize>,
     consistent_mappings_per_type: HashMap<String, usize>,
@@ -254,7 +131,6 @@ struct TransactionTypeTest {
 
 #[derive(Debug, Clone)]
 struct TnoMappingTestResult {
-    test_id: usize,
     consistency_test: MappingConsistencyTest,
     blockchain_state_test: BlockchainStateTest,
     transaction_type_test: TransactionTypeTest,
@@ -285,8 +161,8 @@ struct TnoDeterministicMappingEngine {
     test_nodes: Vec<TestNode>,
     transactions: Vec<Transaction>,
     blockchain_states: Vec<BlockchainState>,
+    range_tables: Vec<RangeTable>, // DURA ranges for each blockchain state
     mapping_results: HashMap<usize, HashMap<u64, u64>>, // node_id -> (tx_id -> final_nonce)
-    consistency_matrix: Vec<Vec<bool>>, // Cross-node consistency tracking
 }
 
 impl TnoDeterministicMappingEngine {
@@ -295,46 +171,36 @@ impl TnoDeterministicMappingEngine {
             test_nodes: Vec::new(),
             transactions: Vec::new(),
             blockchain_states: Vec::new(),
+            range_tables: Vec::new(),
             mapping_results: HashMap::new(),
-            consistency_matrix: Vec::new(),
         };
         
         // Initialize test nodes
         for i in 0..TEST_NODES {
-            engine.test_nodes.push(TestNode::new(i));
+            engine.test_nodes.push(TestNode::new());
             engine.mapping_results.insert(i, HashMap::new());
         }
         
-        // Generate blockchain states with variations
+        // Generate blockchain states with variations and their DURA ranges
+        let miners: Vec<MinerIdentity> = (0..VIRTUAL_MINERS as u32)
+            .map(|id| MinerIdentity::virtual_miner(id, 1_700_000_000))
+            .collect();
         for i in 0..BLOCKCHAIN_HEIGHT_VARIATIONS {
             let height = 1000 + (i as u64 * 100);
-            engine.blockchain_states.push(BlockchainState::new(height, i));
+            let blockchain_state = simulated_blockchain_state(height, i);
+            engine.range_tables.push(RangeTable::for_block(&blockchain_state.previous_hash, &miners));
+            engine.blockchain_states.push(blockchain_state);
         }
         
         // Generate transactions with various types
-        let transaction_types = vec![
-            TransactionType::Transfer,
-            TransactionType::SmartContract,
-            TransactionType::Staking,
-            TransactionType::Governance,
-            TransactionType::CrossChain,
-            TransactionType::TokenMint,
-            TransactionType::TokenBurn,
-            TransactionType::MultiSig,
-            TransactionType::Atomic,
-            TransactionType::Oracle,
-        ];
-        
         for i in 0..TRANSACTION_TESTS {
-            let tx_type = transaction_types[i % transaction_types.len()].clone();
-            engine.transactions.push(Transaction::new(i as u64, tx_type));
+            engine.transactions.push(Transaction::new(i as u64));
         }
         
         engine
     }
     
     fn test_mapping_consistency(&mut self) -> MappingConsistencyTest {
-        let start_time = SystemTime::now();
         let mut identical_mappings = 0;
         let mut mapping_discrepancies = 0;
         let mut total_processing_time = 0;
@@ -343,13 +209,14 @@ impl TnoDeterministicMappingEngine {
         
         // Use first blockchain state for consistency testing
         let blockchain_state = &self.blockchain_states[0];
+        let range_table = &self.range_tables[0];
         
         for tx in &self.transactions {
             let mut node_results = Vec::new();
             
             // Execute TNO mapping on all nodes
             for node in &mut self.test_nodes {
-                let final_nonce = node.execute_tno_mapping(tx, blockchain_state);
+                let final_nonce = node.execute_tno_mapping(tx, blockchain_state, range_table);
                 node_results.push(final_nonce);
                 total_processing_time += node.processing_time_ms;
             }
@@ -370,12 +237,10 @@ impl TnoDeterministicMappingEngine {
             }
         }
         
-        let elapsed = start_time.elapsed().unwrap().as_millis() as u64;
         let consistency_rate = (identical_mappings as f64 / self.transactions.len() as f64) * 100.0;
         let average_processing_time = total_processing_time as f64 / (TEST_NODES * self.transactions.len()) as f64;
         
         MappingConsistencyTest {
-            test_id: 1,
             transactions_tested: self.transactions.len(),
             nodes_tested: TEST_NODES,
             identical_mappings,
@@ -399,9 +264,9 @@ impl TnoDeterministicMappingEngine {
             let mut state_results = Vec::new();
             
             // Test same transaction across different blockchain states
-            for blockchain_state in &self.blockchain_states {
-                let mut temp_node = TestNode::new(999); // Temporary node for state testing
-                let final_nonce = temp_node.execute_tno_mapping(tx, blockchain_state);
+            for (blockchain_state, range_table) in self.blockchain_states.iter().zip(&self.range_tables) {
+                let mut temp_node = TestNode::new(); // Temporary node for state testing
+                let final_nonce = temp_node.execute_tno_mapping(tx, blockchain_state, range_table);
                 state_results.push(final_nonce);
             }
             
@@ -410,11 +275,11 @@ impl TnoDeterministicMappingEngine {
             total_state_tests += 1;
             
             // Verify deterministic behavior: same state should always produce same result
-            for blockchain_state in &self.blockchain_states {
+            for (blockchain_state, range_table) in self.blockchain_states.iter().zip(&self.range_tables) {
                 let mut results_for_state = Vec::new();
                 for _ in 0..3 { // Test same state multiple times
-                    let mut temp_node = TestNode::new(998);
-                    let result = temp_node.execute_tno_mapping(tx, blockchain_state);
+                    let mut temp_node = TestNode::new();
+                    let result = temp_node.execute_tno_mapping(tx, blockchain_state, range_table);
                     results_for_state.push(result);
                 }
                 
@@ -428,7 +293,6 @@ impl TnoDeterministicMappingEngine {
         let cross_state_consistency_rate = (consistent_mappings as f64 / (total_state_tests * BLOCKCHAIN_HEIGHT_VARIATIONS) as f64) * 100.0;
         
         BlockchainStateTest {
-            test_id: 2,
             height_variations_tested: BLOCKCHAIN_HEIGHT_VARIATIONS,
             transaction_types_tested: TRANSACTION_TYPE_VARIATIONS,
             consistent_mappings_across_states: consistent_mappings,
@@ -454,10 +318,11 @@ impl TnoDeterministicMappingEngine {
         }
         
         let blockchain_state = &self.blockchain_states[0];
+        let range_table = &self.range_tables[0];
         
         // Group transactions by type and test consistency
-        for (i, tx) in self.transactions.iter().enumerate() {
-            let tx_type_name = format!("{:?}", tx.tx_type);
+        for tx in self.transactions.iter() {
+            let tx_type_name = tx.body.kind.name().to_string();
             
             if let Some(count) = mappings_per_type.get_mut(&tx_type_name) {
                 *count += 1;
@@ -466,7 +331,7 @@ impl TnoDeterministicMappingEngine {
             // Test consistency across nodes for this transaction type
             let mut type_results = Vec::new();
             for node in &mut self.test_nodes {
-                let result = node.execute_tno_mapping(tx, blockchain_state);
+                let result = node.execute_tno_mapping(tx, blockchain_state, range_table);
                 type_results.push(result);
             }
             
@@ -494,7 +359,6 @@ impl TnoDeterministicMappingEngine {
         }
         
         TransactionTypeTest {
-            test_id: 3,
             transaction_types: transaction_types.iter().map(|s| s.to_string()).collect(),
             mappings_per_type,
             consistent_mappings_per_type,
@@ -610,7 +474,6 @@ impl TnoMappingTestFramework {
                                    transaction_type_test.type_independence_verified;
         
         let result = TnoMappingTestResult {
-            test_id: 1,
             consistency_test,
             blockchain_state_test,
             transaction_type_test,
@@ -699,7 +562,7 @@ impl TnoMappingTestFramework {
         println!("==================================================================================");
         println!("Formula: H_3(u ‖ tx_hash ‖ height ‖ prev_hash) mod R");
         println!("Triple-Layer Hash: Blake3 → SHA-256 → Dilithium");
-        println!("User Nonce Range: 1 to {} (1 trillion)", USER_NONCE_RANGE);
+        println!("User Nonce Range: 1 to {} (1 trillion)", transactions::USER_NONCE_RANGE);
         println!("Mining Range: {} nonces per miner", NONCES_PER_MINER);
         println!("Total Mining Range: {} nonces", VIRTUAL_MINERS as u64 * NONCES_PER_MINER);
         println!("Deterministic Mapping: {}", if result.deterministic_mapping_verified { "VERIFIED" } else { "FAILED" });
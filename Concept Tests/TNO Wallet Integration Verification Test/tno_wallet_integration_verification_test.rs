@@ -7,6 +7,10 @@ use std::collections::{HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::fs::File;
 use std::io::Write;
+use i_protocol_core::fees::MIN_TRANSACTION_AMOUNT;
+use i_protocol_core::signing::Ed25519Signer;
+use i_protocol_core::tno;
+use i_protocol_core::{transaction, BlockchainState, TransactionKind};
 
 // Constants for TNO Wallet Integration Test
 const USER_NONCE_RANGE: u64 = 1_000_000_000_000; // 1 trillion
@@ -15,6 +19,7 @@ const WALLET_SIMULATION_USERS: usize = 1_000;
 const FINAL_NONCE_OPACITY_TESTS: usize = 10_000;
 const AUTONOMOUS_MANAGEMENT_CYCLES: usize = 100;
 const SEAMLESS_EXPERIENCE_THRESHOLD: f64 = 99.99; // 99.99% success rate
+const WALLET_CHAIN_HEIGHT: u64 = 1000; // Chain tip the wallets build against
 
 #[derive(Debug, Clone, PartialEq)]
 enum WalletOperationType {
@@ -28,14 +33,12 @@ enum WalletOperationType {
 
 #[derive(Debug, Clone)]
 struct WalletTransaction {
-    id: u64,
     user_id: u64,
     operation_type: WalletOperationType,
     original_user_nonce: u64,
     current_user_nonce: u64,
     final_mining_nonce: Option<u64>,
     tx_hash: String,
-    creation_timestamp: u64,
     submission_timestamp: u64,
     retry_count: u32,
     user_visible_data: String,
@@ -50,18 +53,16 @@ impl WalletTransaction {
         let creation_timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
         let user_nonce = Self::generate_wallet_nonce(user_id, id);
         let transaction_data = Self::generate_user_visible_data(&operation_type, user_id);
-        let tx_hash = Self::compute_transaction_hash(&transaction_data, user_nonce);
+        let tx_hash = Self::build_transaction(id, &operation_type, user_id, user_nonce, creation_timestamp).hash_hex();
         let internal_state = Self::generate_internal_wallet_state(user_id, user_nonce);
         
         WalletTransaction {
-            id,
             user_id,
             operation_type,
             original_user_nonce: user_nonce,
             current_user_nonce: user_nonce,
             final_mining_nonce: None,
             tx_hash,
-            creation_timestamp,
             submission_timestamp: creation_timestamp,
             retry_count: 0,
             user_visible_data: transaction_data,
@@ -106,10 +107,35 @@ impl WalletTransaction {
         format!("WALLET_STATE:USER_{}:NONCE_{}:MANAGED_AUTONOMOUSLY", user_id, user_nonce)
     }
     
-    fn compute_transaction_hash(data: &str, user_nonce: u64) -> String {
-        // Simplified hash computation for testing
-        let combined = format!("{}{}", data, user_nonce);
-        format!("TX_HASH_{:016X}", combined.len() as u64 * user_nonce)
+    fn build_transaction(id: u64, operation_type: &WalletOperationType, user_id: u64, user_nonce: u64, timestamp: u64) -> transaction::Transaction {
+        // Sending wallet assembles and signs the transaction through the protocol builder; the fee follows from the amount
+        let wallet = format!("wallet_user_{}", user_id);
+        let counterparty = format!("wallet_peer_{}", id);
+        let (kind, sender, recipient) = match operation_type {
+            WalletOperationType::Send => (TransactionKind::Transfer, wallet, counterparty),
+            WalletOperationType::Receive => (TransactionKind::Transfer, counterparty, wallet),
+            WalletOperationType::Stake => (TransactionKind::Staking, wallet, format!("validator_{}", id % 100)),
+            WalletOperationType::Unstake => (
+                TransactionKind::SmartContract { function: "unstake".to_string(), call_data: Vec::new() },
+                wallet,
+                format!("validator_{}", id % 100),
+            ),
+            WalletOperationType::SmartContract => (
+                TransactionKind::SmartContract { function: "execute".to_string(), call_data: id.to_be_bytes().to_vec() },
+                wallet,
+                format!("contract_{}", id % 50),
+            ),
+            WalletOperationType::CrossChain => (TransactionKind::CrossChain { chain: "ethereum".to_string() }, wallet, counterparty),
+        };
+        let signer = Ed25519Signer::from_seed(blake3::hash(sender.as_bytes()).as_bytes());
+        transaction::Transaction::builder(kind)
+            .recipient(recipient)
+            .amount_i(MIN_TRANSACTION_AMOUNT + (id % 1000) as u128 * 1_000)
+            .user_nonce(user_nonce)
+            .timestamp(timestamp)
+            .expires_after(WALLET_CHAIN_HEIGHT)
+            .sign(&signer)
+            .expect("wallet transactions are well formed")
     }
     
     fn increment_user_nonce_autonomously(&mut self) {
@@ -145,7 +171,7 @@ impl WalletTransaction {
     
     fn verify_autonomous_management(&mut self) -> bool {
         // Verify wallet manages nonces autonomously without user intervention
-        let autonomous_indicators = vec![
+        let autonomous_indicators = [
             self.internal_wallet_state.contains("MANAGED_AUTONOMOUSLY"),
             self.user_visible_data == Self::generate_user_visible_data(&self.operation_type, self.user_id),
             self.current_user_nonce >= 1 && self.current_user_nonce <= USER_NONCE_RANGE,
@@ -169,22 +195,10 @@ struct WalletSimulator {
 
 #[derive(Debug, Clone)]
 struct WalletState {
-    user_id: u64,
-    current_nonce_pool: HashSet<u64>,
     last_used_nonce: u64,
     transaction_count: u32,
     seamless_operations: u32,
     failed_operations: u32,
-    autonomous_management_active: bool,
-}
-
-#[derive(Debug, Clone)]
-struct BlockchainState {
-    height: u64,
-    previous_hash: String,
-    timestamp: u64,
-    active_miners: usize,
-    total_mining_range: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -204,24 +218,21 @@ impl WalletSimulator {
         // Initialize wallet states for test users
         for user_id in 1..=WALLET_SIMULATION_USERS as u64 {
             user_wallets.insert(user_id, WalletState {
-                user_id,
-                current_nonce_pool: HashSet::new(),
                 last_used_nonce: 0,
                 transaction_count: 0,
                 seamless_operations: 0,
                 failed_operations: 0,
-                autonomous_management_active: true,
             });
         }
         
         WalletSimulator {
             user_wallets,
             blockchain_state: BlockchainState {
-                height: 1000,
+                height: WALLET_CHAIN_HEIGHT,
                 previous_hash: "GENESIS_HASH_TNO_WALLET_TEST".to_string(),
                 timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64,
-                active_miners: 1000,
-                total_mining_range: 250_000_000, // 1000 miners * 250,000 range each
+                active_miners: 1000, // 1000 miners * 250,000 range each
+                ..Default::default()
             },
             used_final_nonces: HashSet::new(),
             seamless_experience_metrics: SeamlessExperienceMetrics {
@@ -239,49 +250,7 @@ impl WalletSimulator {
     
     fn execute_tno_mapping(&self, tx: &WalletTransaction) -> u64 {
         // TNO Formula: H_3(u ‖ tx_hash ‖ height ‖ prev_hash) mod R
-        // Include transaction ID for additional uniqueness
-        let tno_input = format!(
-            "{}|{}|{}|{}|{}",
-            tx.current_user_nonce,
-            tx.tx_hash,
-            self.blockchain_state.height,
-            self.blockchain_state.previous_hash,
-            tx.id
-        );
-        
-        // Execute triple-layer hash for final nonce
-        let hash_result = self.triple_layer_hash(&tno_input);
-        hash_result % self.blockchain_state.total_mining_range
-    }
-    
-    fn triple_layer_hash(&self, input: &str) -> u64 {
-        // Proper triple-layer hash simulation that incorporates actual input content
-        let mut hash = 0u64;
-        
-        // Initialize with input content, not just length
-        for byte in input.bytes() {
-            hash = hash.wrapping_mul(31).wrapping_add(byte as u64);
-        }
-        
-        // Layer 1: Blake3 simulation
-        hash = hash.wrapping_mul(0x9E3779B97F4A7C15);
-        hash ^= hash >> 33;
-        hash = hash.wrapping_mul(0xFF51AFD7ED558CCD);
-        hash ^= hash >> 33;
-        
-        // Layer 2: SHA-256 simulation
-        hash = hash.wrapping_mul(0xC4CEB9FE1A85EC53);
-        hash ^= hash >> 33;
-        hash = hash.wrapping_mul(0x9E3779B97F4A7C15);
-        hash ^= hash >> 33;
-        
-        // Layer 3: Dilithium simulation
-        hash = hash.wrapping_mul(0xFF51AFD7ED558CCD);
-        hash ^= hash >> 33;
-        hash = hash.wrapping_mul(0xC4CEB9FE1A85EC53);
-        hash ^= hash >> 33;
-        
-        hash
+        tno::execute_tno_mapping(tx.current_user_nonce, &tx.tx_hash, &self.blockchain_state)
     }
     
     fn process_wallet_transaction(&mut self, tx: &mut WalletTransaction) -> bool {
@@ -356,14 +325,14 @@ impl WalletSimulator {
             (self.seamless_experience_metrics.successful_transactions as f64 / total) * 100.0;
     }
     
-    fn verify_nonce_opacity_batch(&mut self, transactions: &mut Vec<WalletTransaction>) {
+    fn verify_nonce_opacity_batch(&mut self, transactions: &mut [WalletTransaction]) {
         for tx in transactions.iter_mut() {
             let opacity_result = tx.verify_nonce_opacity();
             self.opacity_verification_results.push(opacity_result);
         }
     }
     
-    fn verify_autonomous_management_batch(&mut self, transactions: &mut Vec<WalletTransaction>) {
+    fn verify_autonomous_management_batch(&mut self, transactions: &mut [WalletTransaction]) {
         for tx in transactions.iter_mut() {
             let autonomous_result = tx.verify_autonomous_management();
             self.autonomous_management_results.push(autonomous_result);
@@ -431,14 +400,12 @@ fn execute_tno_wallet_integration_verification_test() -> TnoWalletIntegrationTes
     let mut all_transactions = Vec::new();
     
     // Generate test transactions across different operation types
-    let operation_types = vec![
-        WalletOperationType::Send,
+    let operation_types = [WalletOperationType::Send,
         WalletOperationType::Receive,
         WalletOperationType::Stake,
         WalletOperationType::Unstake,
         WalletOperationType::SmartContract,
-        WalletOperationType::CrossChain,
-    ];
+        WalletOperationType::CrossChain];
     
     println!("Phase 1: Generating {} wallet transactions across {} users...", WALLET_TEST_TRANSACTIONS, WALLET_SIMULATION_USERS);
     
@@ -459,11 +426,11 @@ fn execute_tno_wallet_integration_verification_test() -> TnoWalletIntegrationTes
     }
     
     println!("\nPhase 2: Verifying nonce opacity across {} transactions...", FINAL_NONCE_OPACITY_TESTS);
-    let mut opacity_test_transactions = all_transactions.iter().take(FINAL_NONCE_OPACITY_TESTS).cloned().collect();
+    let mut opacity_test_transactions: Vec<WalletTransaction> = all_transactions.iter().take(FINAL_NONCE_OPACITY_TESTS).cloned().collect();
     simulator.verify_nonce_opacity_batch(&mut opacity_test_transactions);
     
     println!("\nPhase 3: Verifying autonomous wallet management across {} cycles...", AUTONOMOUS_MANAGEMENT_CYCLES);
-    let mut management_test_transactions = all_transactions.iter().take(AUTONOMOUS_MANAGEMENT_CYCLES).cloned().collect();
+    let mut management_test_transactions: Vec<WalletTransaction> = all_transactions.iter().take(AUTONOMOUS_MANAGEMENT_CYCLES).cloned().collect();
     simulator.verify_autonomous_management_batch(&mut management_test_transactions);
     
     // Analyze results
@@ -522,7 +489,7 @@ fn analyze_seamless_nonce_selection(simulator: &WalletSimulator, transactions: &
     }
 }
 
-fn analyze_single_use_nonces(simulator: &WalletSimulator, transactions: &[WalletTransaction]) -> SingleUseNonceTest {
+fn analyze_single_use_nonces(_simulator: &WalletSimulator, transactions: &[WalletTransaction]) -> SingleUseNonceTest {
     let total_nonces = transactions.len() as u32;
     let mut nonce_usage_count = HashMap::new();
     
@@ -756,7 +723,3 @@ fn main() {
     
     println!("\nTNO Wallet Integration Verification Test: {}", test_result.overall_test_status);
 }
-
-// Add chrono dependency for timestamp
-// [dependencies]
-// chrono = "0.4"
\ No newline at end of file
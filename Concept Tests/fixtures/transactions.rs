@@ -0,0 +1,68 @@
+// Sample transactions shared by the TNO verification programs, assembled
+// through the library's TransactionBuilder so every program hashes the same bodies.
+
+use i_protocol_core::fees::MIN_TRANSACTION_AMOUNT;
+use i_protocol_core::signing::{Ed25519Signer, Signer};
+use i_protocol_core::{Transaction, TransactionKind};
+
+pub const USER_NONCE_RANGE: u64 = 1000000000000; // 1 trillion range
+
+// User nonce for transaction `seed`, spread over [1, 10^12] by a 64-bit mixer
+pub fn random_user_nonce(seed: u64) -> u64 {
+    let mut nonce = seed.wrapping_mul(0x123456789ABCDEF);
+    nonce ^= nonce >> 33;
+    nonce = nonce.wrapping_mul(0xFF51AFD7ED558CCD);
+    nonce ^= nonce >> 33;
+    nonce = nonce.wrapping_mul(0xC4CEB9FE1A85EC53);
+    nonce ^= nonce >> 33;
+    (nonce % USER_NONCE_RANGE) + 1
+}
+
+// Kind of transaction `id`, cycling through the ten kinds in tag order with a payload derived from `id`
+fn sample_kind(id: u64) -> TransactionKind {
+    match id % 10 {
+        0 => TransactionKind::Transfer,
+        1 => TransactionKind::SmartContract {
+            function: format!("execute_{}", id % 50),
+            call_data: id.to_be_bytes().to_vec(),
+        },
+        2 => TransactionKind::Staking,
+        3 => TransactionKind::Governance { proposal: id % 10, approve: id.is_multiple_of(2) },
+        4 => TransactionKind::CrossChain {
+            chain: if id.is_multiple_of(3) { "ethereum" } else { "polygon" }.to_string(),
+        },
+        5 => TransactionKind::TokenMint { token: "I".to_string() },
+        6 => TransactionKind::TokenBurn { token: "I".to_string() },
+        7 => {
+            let signers = (id % 5) as usize + 2;
+            TransactionKind::MultiSig {
+                signers: (0..signers).map(|k| format!("signer_{}_{}", id, k)).collect(),
+                threshold: ((id % 3) as usize + 2).min(signers) as u32,
+            }
+        }
+        8 => TransactionKind::Atomic {
+            counter_token: format!("token_{}", (id + 1) % 100),
+            counter_amount_i: MIN_TRANSACTION_AMOUNT + (id % 1000) as u128,
+        },
+        _ => TransactionKind::Oracle { feed: format!("price_feed_{}", id % 20), value: id as u128 },
+    }
+}
+
+// Transaction `id` as a wallet builds it, unsigned, from a sender key derived from `id`
+pub fn sample_transaction(id: u64, user_nonce: u64, timestamp: u64, expiry_height: u64) -> Transaction {
+    Transaction::builder(sample_kind(id))
+        .sender_key(sender(id).public_key())
+        .recipient(format!("addr_{:08x}", id + 1))
+        .amount_i(MIN_TRANSACTION_AMOUNT + (id % 10000) as u128 * 100)
+        .user_nonce(user_nonce)
+        .timestamp(timestamp)
+        .expiry_height(expiry_height)
+        .build()
+        .expect("sample transactions are well formed")
+}
+
+fn sender(id: u64) -> Ed25519Signer {
+    let mut seed = [0u8; 32];
+    seed[..8].copy_from_slice(&id.to_be_bytes());
+    Ed25519Signer::from_seed(&seed)
+}
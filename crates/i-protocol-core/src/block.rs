@@ -0,0 +1,150 @@
+//! Blocks and block headers.
+
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::{CanonicalEncode, PreimageEncoder, PreimageKind};
+use crate::hash::{serde_hex, to_hex, triple_layer_hash, Digest32, HashDomain};
+use crate::merkle::{merkle_root, MerkleProof, MerkleTree};
+use crate::state::BlockchainState;
+use crate::transaction::Transaction;
+
+/// Who produced a block and with which nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockProducer {
+    /// Regular miner that found `nonce` inside its DURA range.
+    RegularMiner {
+        /// Identity hash of the miner holding the range.
+        #[serde(with = "serde_hex")]
+        range_owner: Digest32,
+        nonce: u64,
+    },
+    /// System Miner fallback block (SysBlock).
+    SystemMiner { system_nonce: u64 },
+}
+
+/// Amounts the block credits to the active miner pool, in `i`.
+///
+/// The System Miner earns nothing; on SysBlocks the same amounts go to the
+/// participating miners.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coinbase {
+    /// Scheduled emission for the block's height.
+    pub block_reward: u128,
+    /// Miners' 50% share of the block's transaction fees.
+    pub miner_fees: u128,
+}
+
+/// Block header. The header hash commits to every field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub height: u64,
+    #[serde(with = "serde_hex")]
+    pub previous_hash: Digest32,
+    pub timestamp: u64,
+    /// Merkle root over the transaction hashes.
+    #[serde(with = "serde_hex")]
+    pub merkle_root: Digest32,
+    /// Number of regular miners that failed to produce this height.
+    pub failed_miner_count: u32,
+    pub producer: BlockProducer,
+    pub coinbase: Coinbase,
+}
+
+impl BlockHeader {
+    /// H₃ of the canonical header encoding.
+    pub fn hash(&self) -> Digest32 {
+        triple_layer_hash(HashDomain::BlockHeader, &self.canonical_bytes())
+    }
+
+    /// Winning nonce, whichever miner produced the block.
+    pub fn nonce(&self) -> u64 {
+        match self.producer {
+            BlockProducer::RegularMiner { nonce, .. } => nonce,
+            BlockProducer::SystemMiner { system_nonce } => system_nonce,
+        }
+    }
+
+    /// System Miner nonce, for SysBlocks only.
+    pub fn system_nonce(&self) -> Option<u64> {
+        match self.producer {
+            BlockProducer::SystemMiner { system_nonce } => Some(system_nonce),
+            BlockProducer::RegularMiner { .. } => None,
+        }
+    }
+
+    pub fn is_sys_block(&self) -> bool {
+        self.system_nonce().is_some()
+    }
+
+    /// Chain state this header was produced against, as read by DURA, TNO
+    /// and the System Miner.
+    pub fn chain_state(&self, active_miners: u32) -> BlockchainState {
+        BlockchainState {
+            height: self.height,
+            previous_hash: to_hex(&self.previous_hash),
+            timestamp: self.timestamp,
+            failed_miner_count: self.failed_miner_count,
+            active_miners,
+        }
+    }
+}
+
+impl CanonicalEncode for BlockHeader {
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut encoder = PreimageEncoder::new(PreimageKind::BlockHeader);
+        encoder
+            .u64(self.height)
+            .bytes(&self.previous_hash)
+            .u64(self.timestamp)
+            .bytes(&self.merkle_root)
+            .u32(self.failed_miner_count);
+        match self.producer {
+            BlockProducer::RegularMiner { range_owner, nonce } => {
+                encoder.u8(0).bytes(&range_owner).u64(nonce);
+            }
+            BlockProducer::SystemMiner { system_nonce } => {
+                encoder.u8(1).u64(system_nonce);
+            }
+        }
+        encoder
+            .u128(self.coinbase.block_reward)
+            .u128(self.coinbase.miner_fees)
+            .finish()
+    }
+}
+
+/// Header plus the ordered transaction list it commits to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Block {
+    pub header: BlockHeader,
+    pub transactions: Vec<Transaction>,
+}
+
+impl Block {
+    /// Merkle root over the block's transaction hashes.
+    pub fn compute_merkle_root(&self) -> Digest32 {
+        transactions_root(&self.transactions)
+    }
+
+    /// Whether the header's Merkle root matches the transactions.
+    pub fn merkle_root_matches(&self) -> bool {
+        self.header.merkle_root == self.compute_merkle_root()
+    }
+
+    /// Proof that the transaction at `index` is under the header's Merkle
+    /// root. Its leaf is [`Transaction::hash`].
+    pub fn transaction_proof(&self, index: usize) -> Option<MerkleProof> {
+        let leaves: Vec<Digest32> = self.transactions.iter().map(Transaction::hash).collect();
+        MerkleTree::new(&leaves).proof(index)
+    }
+
+    pub fn hash(&self) -> Digest32 {
+        self.header.hash()
+    }
+}
+
+/// Merkle root over `transactions` in block order.
+pub fn transactions_root(transactions: &[Transaction]) -> Digest32 {
+    let leaves: Vec<Digest32> = transactions.iter().map(Transaction::hash).collect();
+    merkle_root(&leaves)
+}
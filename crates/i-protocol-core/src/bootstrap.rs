@@ -0,0 +1,125 @@
+//! Bootstrap-phase range scaling.
+//!
+//! The network starts with a single range of [`NONCES_PER_MINER`] nonces and
+//! adds one range per active miner. The active set is read from
+//! [`MinerRegistry`] snapshots, so joins and leaves take effect after
+//! [`REGISTRY_ACTIVATION_DELAY`](crate::params::REGISTRY_ACTIVATION_DELAY)
+//! blocks, the same rule DURA follows.
+//!
+//! Bootstrap ends once [`BOOTSTRAP_MIN_MINERS`] miners have been active for
+//! [`BOOTSTRAP_SUSTAIN_BLOCKS`] consecutive blocks. Until then the active
+//! range never drops below one range, so TNO always has a range to map into;
+//! while no miner is active that range is unowned and blocks come from the
+//! System Miner.
+
+use crate::identity::MinerIdentity;
+use crate::layout::RangeLayout;
+use crate::params::{BOOTSTRAP_MIN_MINERS, BOOTSTRAP_SUSTAIN_BLOCKS, NONCES_PER_MINER};
+use crate::registry::{MinerRegistry, RegistryChange, RegistryError};
+
+/// Phase of the network at a height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkPhase {
+    Bootstrap,
+    /// Enough miners sustain the network; no single-range floor.
+    Sustained,
+}
+
+impl NetworkPhase {
+    /// Regular miner ranges in this phase with `miners` holding a range.
+    pub fn range_count(self, miners: u32) -> u32 {
+        match self {
+            NetworkPhase::Bootstrap => miners.max(1),
+            NetworkPhase::Sustained => miners,
+        }
+    }
+}
+
+/// Derives the active mining range for each height from the miner registry.
+#[derive(Debug, Clone, Default)]
+pub struct BootstrapController {
+    registry: MinerRegistry,
+}
+
+impl BootstrapController {
+    pub fn new() -> Self {
+        BootstrapController::default()
+    }
+
+    /// Controller over an existing registry.
+    pub fn with_registry(registry: MinerRegistry) -> Self {
+        BootstrapController { registry }
+    }
+
+    /// Applies the registry changes of the block at `height`; see
+    /// [`MinerRegistry::apply_block`].
+    pub fn apply_block(
+        &mut self,
+        height: u64,
+        changes: &[RegistryChange],
+    ) -> Result<(), RegistryError> {
+        self.registry.apply_block(height, changes)
+    }
+
+    pub fn registry(&self) -> &MinerRegistry {
+        &self.registry
+    }
+
+    /// Miners holding a range at `height`, in identity-hash order.
+    pub fn active_miners(&self, height: u64) -> Vec<MinerIdentity> {
+        self.registry.snapshot(height).miners
+    }
+
+    pub fn active_miner_count(&self, height: u64) -> u32 {
+        self.registry.snapshot(height).miners.len() as u32
+    }
+
+    /// First height of the sustained phase, given the changes applied so far.
+    ///
+    /// A later leave can only move this if it takes effect before the
+    /// returned height, so it is final once blocks up to that height are
+    /// applied.
+    pub fn bootstrap_end(&self) -> Option<u64> {
+        // The active count only changes at snapshot heights, so a run of
+        // heights at or above the threshold starts at one of them
+        let mut run_start = None;
+        for (height, count) in self.registry.active_counts() {
+            match run_start {
+                Some(start) if count < BOOTSTRAP_MIN_MINERS => {
+                    if height >= start + BOOTSTRAP_SUSTAIN_BLOCKS {
+                        break;
+                    }
+                    run_start = None;
+                }
+                None if count >= BOOTSTRAP_MIN_MINERS => run_start = Some(height),
+                _ => {}
+            }
+        }
+        run_start.map(|start| start + BOOTSTRAP_SUSTAIN_BLOCKS)
+    }
+
+    /// Phase at `height`. The sustained phase, once reached, is permanent.
+    pub fn phase(&self, height: u64) -> NetworkPhase {
+        match self.bootstrap_end() {
+            Some(end) if height >= end => NetworkPhase::Sustained,
+            _ => NetworkPhase::Bootstrap,
+        }
+    }
+
+    /// Regular miner ranges at `height`. This is the value to place in
+    /// [`BlockchainState::active_miners`](crate::BlockchainState::active_miners).
+    pub fn range_count(&self, height: u64) -> u32 {
+        self.phase(height)
+            .range_count(self.active_miner_count(height))
+    }
+
+    /// Total nonces assigned to regular miners at `height`, R in the TNO
+    /// formula.
+    pub fn get_active_mining_range(&self, height: u64) -> u64 {
+        u64::from(self.range_count(height)) * NONCES_PER_MINER
+    }
+
+    pub fn layout(&self, height: u64) -> RangeLayout {
+        RangeLayout::new(self.range_count(height) as usize)
+    }
+}
@@ -0,0 +1,376 @@
+//! Final-nonce collision index for TNO.
+//!
+//! Two transactions collide when TNO maps them to the same final nonce at
+//! the same height; transactions at different heights never do. The index
+//! therefore keeps claims per height, and only for heights that are not yet
+//! final. Finalizing a height drops it together with every height below, so
+//! memory is bounded by the unfinalized window rather than by chain history.
+//!
+//! # Resolution
+//!
+//! When two transactions contend for one final nonce, the index settles the
+//! contest by a [`ResolutionOrder`] over data every node derives alike. The
+//! default ranks contenders by transaction hash, so the outcome does not
+//! depend on arrival order or on any value the submitter may pick freely.
+//! The hash covers the signed body but not the signature bytes: a signer can
+//! produce many valid signatures for one body, while every change to the body
+//! also moves its final nonce. Beating an honest transaction therefore costs
+//! about 2·R hash evaluations for a segment of R nonces, and the loser
+//! retries at the next height, where TNO moves it to a fresh final nonce
+//! that the attacker must chase again.
+//!
+//! The original hierarchy, earliest submission timestamp first, remains as
+//! [`ResolutionOrder::LegacyTimestamp`]. The timestamp is chosen by the
+//! submitter, so under it a backdated transaction wins every collision.
+//!
+//! An index opened on a directory also appends every claim to a per-height
+//! log, so a restarted node rebuilds its window instead of forgetting which
+//! final nonces are taken:
+//!
+//! ```text
+//! <dir>/finalized       finalized height, u64 big-endian
+//! <dir>/<height>.claims  final_nonce:u64 ‖ tx_hash:32 ‖ timestamp:u64 per claim
+//! ```
+//!
+//! A displaced holder stays in the log; reloading settles each nonce again.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::hash::Digest32;
+use crate::transaction::Transaction;
+
+const FINALIZED_FILE: &str = "finalized";
+const CLAIMS_EXTENSION: &str = "claims";
+const RECORD_LEN: usize = 8 + 32 + 8;
+
+/// Reason a claim or finalization was refused.
+#[derive(Debug)]
+pub enum CollisionIndexError {
+    /// Claims at or below the finalized height are closed.
+    Finalized { height: u64, finalized: u64 },
+    /// The on-disk log could not be read or written.
+    Io(io::Error),
+}
+
+impl fmt::Display for CollisionIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollisionIndexError::Finalized { height, finalized } => {
+                write!(
+                    f,
+                    "height {height} is final (finalized through {finalized})"
+                )
+            }
+            CollisionIndexError::Io(error) => write!(f, "collision index log: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for CollisionIndexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CollisionIndexError::Io(error) => Some(error),
+            CollisionIndexError::Finalized { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for CollisionIndexError {
+    fn from(error: io::Error) -> Self {
+        CollisionIndexError::Io(error)
+    }
+}
+
+/// Order in which contending claims on one final nonce are settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResolutionOrder {
+    /// Lower transaction hash wins.
+    #[default]
+    TransactionHash,
+    /// Earlier submission timestamp wins, then lower transaction hash. The
+    /// timestamp is submitter-controlled; kept for legacy comparison only.
+    LegacyTimestamp,
+}
+
+impl ResolutionOrder {
+    /// Whether `challenger` takes the nonce from `holder`. Of two distinct
+    /// contenders exactly one prevails over the other; a contender never
+    /// prevails over itself.
+    pub fn prevails(self, challenger: &Contender, holder: &Contender) -> bool {
+        match self {
+            ResolutionOrder::TransactionHash => challenger.tx_hash < holder.tx_hash,
+            ResolutionOrder::LegacyTimestamp => {
+                (challenger.submission_timestamp, challenger.tx_hash)
+                    < (holder.submission_timestamp, holder.tx_hash)
+            }
+        }
+    }
+}
+
+/// What resolution knows about a transaction claiming a final nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contender {
+    pub tx_hash: Digest32,
+    /// Submitter-declared time, read only by
+    /// [`ResolutionOrder::LegacyTimestamp`].
+    pub submission_timestamp: u64,
+}
+
+impl Contender {
+    pub fn of(tx: &Transaction) -> Self {
+        Contender {
+            tx_hash: tx.hash(),
+            submission_timestamp: tx.timestamp,
+        }
+    }
+}
+
+/// Result of claiming a final nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Claim {
+    /// The nonce was free, or already held by the claimant, and now belongs
+    /// to the claimant.
+    Claimed,
+    /// The claimant prevailed and took the nonce; `loser` must retry.
+    Displaced { loser: Contender },
+    /// The nonce stays with `holder`; the claimant must retry.
+    Collision { holder: Contender },
+}
+
+#[derive(Debug)]
+struct ClaimLog {
+    dir: PathBuf,
+    files: HashMap<u64, File>,
+}
+
+impl ClaimLog {
+    fn path(&self, height: u64) -> PathBuf {
+        self.dir.join(format!("{height}.{CLAIMS_EXTENSION}"))
+    }
+
+    fn append(&mut self, height: u64, final_nonce: u64, claimant: &Contender) -> io::Result<()> {
+        if !self.files.contains_key(&height) {
+            let path = self.path(height);
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            self.files.insert(height, file);
+        }
+        let file = self.files.get_mut(&height).expect("opened above");
+        let mut record = [0u8; RECORD_LEN];
+        record[..8].copy_from_slice(&final_nonce.to_be_bytes());
+        record[8..40].copy_from_slice(&claimant.tx_hash);
+        record[40..].copy_from_slice(&claimant.submission_timestamp.to_be_bytes());
+        file.write_all(&record)
+    }
+
+    // The marker is replaced atomically before any log is removed, so a
+    // crash in between leaves only stale logs that the next open discards
+    fn finalize(&mut self, finalized: u64, pruned: impl Iterator<Item = u64>) -> io::Result<()> {
+        let marker = self.dir.join(FINALIZED_FILE);
+        let staged = self.dir.join(format!("{FINALIZED_FILE}.tmp"));
+        fs::write(&staged, finalized.to_be_bytes())?;
+        fs::rename(&staged, &marker)?;
+        for height in pruned {
+            self.files.remove(&height);
+            match fs::remove_file(self.path(height)) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Claims on final nonces for every height above the finalized one.
+#[derive(Debug, Default)]
+pub struct CollisionIndex {
+    heights: BTreeMap<u64, HashMap<u64, Contender>>,
+    finalized: Option<u64>,
+    order: ResolutionOrder,
+    log: Option<ClaimLog>,
+}
+
+impl CollisionIndex {
+    /// Index held in memory only, settling by transaction hash.
+    pub fn new() -> Self {
+        CollisionIndex::default()
+    }
+
+    /// Index held in memory only, settling by `order`.
+    pub fn with_order(order: ResolutionOrder) -> Self {
+        CollisionIndex {
+            order,
+            ..CollisionIndex::default()
+        }
+    }
+
+    /// Index backed by the logs in `dir`, settling by transaction hash.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, CollisionIndexError> {
+        CollisionIndex::open_with_order(dir, ResolutionOrder::default())
+    }
+
+    /// Index backed by the logs in `dir`, created if missing, settling by
+    /// `order`. Claims logged for unfinalized heights are loaded back; a torn
+    /// trailing record from an interrupted write is cut off.
+    pub fn open_with_order(
+        dir: impl AsRef<Path>,
+        order: ResolutionOrder,
+    ) -> Result<Self, CollisionIndexError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let finalized = match fs::read(dir.join(FINALIZED_FILE)) {
+            Ok(bytes) => {
+                let bytes: [u8; 8] = bytes.try_into().map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "malformed finalized marker")
+                })?;
+                Some(u64::from_be_bytes(bytes))
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut heights: BTreeMap<u64, HashMap<u64, Contender>> = BTreeMap::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(CLAIMS_EXTENSION) {
+                continue;
+            }
+            let Some(height) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            if finalized.is_some_and(|finalized| height <= finalized) {
+                fs::remove_file(&path)?;
+                continue;
+            }
+
+            let bytes = fs::read(&path)?;
+            let whole = bytes.len() - bytes.len() % RECORD_LEN;
+            if whole < bytes.len() {
+                // Cut the torn record so later appends stay aligned
+                OpenOptions::new()
+                    .write(true)
+                    .open(&path)?
+                    .set_len(whole as u64)?;
+            }
+            let claims = heights.entry(height).or_default();
+            for record in bytes[..whole].chunks_exact(RECORD_LEN) {
+                let final_nonce = u64::from_be_bytes(record[..8].try_into().expect("8 bytes"));
+                let claimant = Contender {
+                    tx_hash: record[8..40].try_into().expect("32 bytes"),
+                    submission_timestamp: u64::from_be_bytes(
+                        record[40..].try_into().expect("8 bytes"),
+                    ),
+                };
+                let holder = claims.entry(final_nonce).or_insert(claimant);
+                if order.prevails(&claimant, holder) {
+                    *holder = claimant;
+                }
+            }
+        }
+
+        Ok(CollisionIndex {
+            heights,
+            finalized,
+            order,
+            log: Some(ClaimLog {
+                dir,
+                files: HashMap::new(),
+            }),
+        })
+    }
+
+    /// Claims `final_nonce` at `height` for `claimant`. A free nonce is
+    /// taken; a held one goes to whichever contender the resolution order
+    /// prefers, and the other must retry. The holder claiming again, as on a
+    /// re-broadcast, is told it holds the nonce.
+    pub fn claim(
+        &mut self,
+        height: u64,
+        final_nonce: u64,
+        claimant: Contender,
+    ) -> Result<Claim, CollisionIndexError> {
+        if let Some(finalized) = self.finalized {
+            if height <= finalized {
+                return Err(CollisionIndexError::Finalized { height, finalized });
+            }
+        }
+        let previous = self.holder(height, final_nonce).copied();
+        if let Some(holder) = previous {
+            if holder == claimant {
+                return Ok(Claim::Claimed);
+            }
+            if !self.order.prevails(&claimant, &holder) {
+                return Ok(Claim::Collision { holder });
+            }
+        }
+
+        if let Some(log) = &mut self.log {
+            log.append(height, final_nonce, &claimant)?;
+        }
+        self.heights
+            .entry(height)
+            .or_default()
+            .insert(final_nonce, claimant);
+        Ok(match previous {
+            Some(loser) => Claim::Displaced { loser },
+            None => Claim::Claimed,
+        })
+    }
+
+    /// Contender holding `final_nonce` at `height`, if any.
+    pub fn holder(&self, height: u64, final_nonce: u64) -> Option<&Contender> {
+        self.heights.get(&height)?.get(&final_nonce)
+    }
+
+    /// Closes `height` and every height below it, dropping their claims.
+    /// Finalizing at or below the current finalized height is a no-op.
+    ///
+    /// The log is updated first; if that fails the index is left as it was
+    /// and the call may be repeated.
+    pub fn finalize(&mut self, height: u64) -> Result<(), CollisionIndexError> {
+        if self.finalized.is_some_and(|finalized| height <= finalized) {
+            return Ok(());
+        }
+        if let Some(log) = &mut self.log {
+            log.finalize(height, self.heights.range(..=height).map(|(&h, _)| h))?;
+        }
+        self.heights = match height.checked_add(1) {
+            Some(next) => self.heights.split_off(&next),
+            None => BTreeMap::new(),
+        };
+        self.finalized = Some(height);
+        Ok(())
+    }
+
+    /// Order this index settles contests by.
+    pub fn order(&self) -> ResolutionOrder {
+        self.order
+    }
+
+    /// Highest finalized height.
+    pub fn finalized(&self) -> Option<u64> {
+        self.finalized
+    }
+
+    /// Number of heights holding claims.
+    pub fn open_heights(&self) -> usize {
+        self.heights.len()
+    }
+
+    /// Number of claims held across all open heights.
+    pub fn len(&self) -> usize {
+        self.heights.values().map(HashMap::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heights.is_empty()
+    }
+}
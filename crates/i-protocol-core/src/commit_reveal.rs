@@ -0,0 +1,587 @@
+//! Commit–reveal rounds deciding which identities enter the master seed.
+//!
+//! Heights are split into consecutive rounds of [`COMMIT_PHASE_BLOCKS`]
+//! followed by [`REVEAL_PHASE_BLOCKS`]. During the commit phase a miner
+//! publishes
+//!
+//! ```text
+//! commitment = H₃(ID_Hash ‖ round ‖ nonce)
+//! ```
+//!
+//! for a fresh random 32-byte nonce, and during the reveal phase publishes
+//! the nonce. Both messages are signed with the identity's registered key,
+//! and only identities active in the [`MinerRegistry`] at the round's first
+//! height may take part, so no one can commit in another miner's name.
+//!
+//! Only identities whose reveal matches their commitment are in the round's
+//! outcome. Its master seed mixes in every revealed nonce and is keyed with
+//! the hash of a block past the reveal deadline, so no single participant
+//! knows the seed before the reveals and no reveal can be chosen with the
+//! resulting shuffle in view. [`RoundOutcome::assign_ranges`] runs DURA
+//! under that seed over the revealed identities alone.
+//!
+//! Withholding a reveal is the one remaining lever: it drops the identity
+//! from the outcome. It is paid for by exclusion — an identity that
+//! committed and did not reveal may not commit again for
+//! [`NON_REVEAL_EXCLUSION_ROUNDS`] rounds, and so sits out the master seeds
+//! those rounds produce.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use rand_chacha::rand_core::{CryptoRng, RngCore};
+
+use crate::dura::{assign_ranges_with_seed, DuraAssignment};
+use crate::encoding::{PreimageEncoder, PreimageKind};
+use crate::hash::{to_hex, triple_layer_hash, Digest32, HashDomain};
+use crate::identity::MinerIdentity;
+use crate::params::{COMMIT_PHASE_BLOCKS, NON_REVEAL_EXCLUSION_ROUNDS, REVEAL_PHASE_BLOCKS};
+use crate::registry::MinerRegistry;
+use crate::signing::{self, SignatureError, Signer};
+
+/// Bytes of the secret nonce behind a commitment.
+pub const COMMIT_NONCE_LEN: usize = 32;
+
+/// Heights in one round.
+pub const ROUND_BLOCKS: u64 = COMMIT_PHASE_BLOCKS + REVEAL_PHASE_BLOCKS;
+
+/// Phase of a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Commit,
+    Reveal,
+}
+
+/// Heights of one round. Each range is half-open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundWindow {
+    pub round: u64,
+    pub commit_start: u64,
+    pub reveal_start: u64,
+    /// First height after the reveal phase.
+    pub reveal_end: u64,
+}
+
+impl RoundWindow {
+    pub fn new(round: u64) -> Self {
+        let commit_start = round * ROUND_BLOCKS;
+        RoundWindow {
+            round,
+            commit_start,
+            reveal_start: commit_start + COMMIT_PHASE_BLOCKS,
+            reveal_end: commit_start + ROUND_BLOCKS,
+        }
+    }
+
+    /// Round containing `height`.
+    pub fn at(height: u64) -> Self {
+        RoundWindow::new(height / ROUND_BLOCKS)
+    }
+
+    pub fn phase(&self, height: u64) -> Option<Phase> {
+        if (self.commit_start..self.reveal_start).contains(&height) {
+            Some(Phase::Commit)
+        } else if (self.reveal_start..self.reveal_end).contains(&height) {
+            Some(Phase::Reveal)
+        } else {
+            None
+        }
+    }
+}
+
+/// Commitment = H₃(ID_Hash ‖ round ‖ nonce).
+pub fn commitment(identity_hash: &str, round: u64, nonce: &[u8; COMMIT_NONCE_LEN]) -> Digest32 {
+    let preimage = PreimageEncoder::new(PreimageKind::Commitment)
+        .str(identity_hash)
+        .u64(round)
+        .bytes(nonce)
+        .finish();
+    triple_layer_hash(HashDomain::Commitment, &preimage)
+}
+
+/// A miner's side of one round: the nonce it keeps until the reveal phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSecret {
+    pub identity_hash: String,
+    pub round: u64,
+    pub nonce: [u8; COMMIT_NONCE_LEN],
+}
+
+impl CommitSecret {
+    /// Draws a fresh nonce for `round`. `rng` must be a cryptographic RNG
+    /// seeded from system entropy: the nonce is mixed into the round's master
+    /// seed, and one anyone can derive lets them compute that seed before
+    /// the reveals.
+    pub fn generate<R: RngCore + CryptoRng>(identity_hash: &str, round: u64, rng: &mut R) -> Self {
+        let mut nonce = [0; COMMIT_NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+        CommitSecret {
+            identity_hash: identity_hash.to_string(),
+            round,
+            nonce,
+        }
+    }
+
+    pub fn commitment(&self) -> Digest32 {
+        commitment(&self.identity_hash, self.round, &self.nonce)
+    }
+
+    /// Message to publish in the commit phase, signed with the identity key.
+    pub fn commit_message<S: Signer + ?Sized>(&self, signer: &S) -> RoundMessage {
+        let mut message = RoundMessage::Commit {
+            identity_hash: self.identity_hash.clone(),
+            commitment: self.commitment(),
+            signature: Vec::new(),
+        };
+        message.sign(self.round, signer);
+        message
+    }
+
+    /// Message to publish in the reveal phase, signed with the identity key.
+    pub fn reveal_message<S: Signer + ?Sized>(&self, signer: &S) -> RoundMessage {
+        let mut message = RoundMessage::Reveal {
+            identity_hash: self.identity_hash.clone(),
+            nonce: self.nonce,
+            signature: Vec::new(),
+        };
+        message.sign(self.round, signer);
+        message
+    }
+}
+
+/// Commit–reveal message carried in a block. The round is the one
+/// containing the block's height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundMessage {
+    Commit {
+        identity_hash: String,
+        commitment: Digest32,
+        /// Signature over [`RoundMessage::signing_message`].
+        signature: Vec<u8>,
+    },
+    Reveal {
+        identity_hash: String,
+        nonce: [u8; COMMIT_NONCE_LEN],
+        /// Signature over [`RoundMessage::signing_message`].
+        signature: Vec<u8>,
+    },
+}
+
+impl RoundMessage {
+    pub fn identity_hash(&self) -> &str {
+        match self {
+            RoundMessage::Commit { identity_hash, .. }
+            | RoundMessage::Reveal { identity_hash, .. } => identity_hash,
+        }
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        match self {
+            RoundMessage::Commit { signature, .. } | RoundMessage::Reveal { signature, .. } => {
+                signature
+            }
+        }
+    }
+
+    /// Bytes the signature covers: the message without its signature, bound
+    /// to `round`.
+    pub fn signing_message(&self, round: u64) -> Vec<u8> {
+        let mut encoder = PreimageEncoder::new(PreimageKind::RoundMessage);
+        match self {
+            RoundMessage::Commit {
+                identity_hash,
+                commitment,
+                ..
+            } => encoder
+                .u8(1)
+                .str(identity_hash)
+                .u64(round)
+                .bytes(commitment),
+            RoundMessage::Reveal {
+                identity_hash,
+                nonce,
+                ..
+            } => encoder.u8(2).str(identity_hash).u64(round).bytes(nonce),
+        };
+        encoder.finish()
+    }
+
+    /// Signs the message for `round`, replacing any previous signature.
+    pub fn sign<S: Signer + ?Sized>(&mut self, round: u64, signer: &S) {
+        let signed = signer.sign(&self.signing_message(round));
+        match self {
+            RoundMessage::Commit { signature, .. } | RoundMessage::Reveal { signature, .. } => {
+                *signature = signed
+            }
+        }
+    }
+}
+
+/// Reason a block's round messages were rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitRevealError {
+    HeightNotIncreasing {
+        last: u64,
+        found: u64,
+    },
+    /// The message does not belong in the phase `height` falls in.
+    WrongPhase {
+        height: u64,
+        phase: Phase,
+    },
+    /// The identity is not in the registry's active set for the round.
+    NotActive {
+        identity_hash: String,
+        round: u64,
+    },
+    /// The signature does not verify under the identity's registered key.
+    InvalidSignature {
+        identity_hash: String,
+        error: SignatureError,
+    },
+    /// The identity withheld a reveal and may not commit before `until_round`.
+    Excluded {
+        identity_hash: String,
+        until_round: u64,
+    },
+    AlreadyCommitted {
+        identity_hash: String,
+    },
+    NotCommitted {
+        identity_hash: String,
+    },
+    AlreadyRevealed {
+        identity_hash: String,
+    },
+    /// The nonce does not open the identity's commitment.
+    CommitmentMismatch {
+        identity_hash: String,
+    },
+}
+
+impl fmt::Display for CommitRevealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitRevealError::HeightNotIncreasing { last, found } => {
+                write!(f, "round block {found} not after {last}")
+            }
+            CommitRevealError::WrongPhase { height, phase } => {
+                write!(f, "height {height} is in the {phase:?} phase")
+            }
+            CommitRevealError::NotActive {
+                identity_hash,
+                round,
+            } => write!(f, "miner {identity_hash} is not active in round {round}"),
+            CommitRevealError::InvalidSignature {
+                identity_hash,
+                error,
+            } => write!(f, "round message of miner {identity_hash}: {error}"),
+            CommitRevealError::Excluded {
+                identity_hash,
+                until_round,
+            } => write!(
+                f,
+                "miner {identity_hash} withheld a reveal and is excluded until round {until_round}"
+            ),
+            CommitRevealError::AlreadyCommitted { identity_hash } => {
+                write!(f, "miner {identity_hash} already committed this round")
+            }
+            CommitRevealError::NotCommitted { identity_hash } => {
+                write!(f, "miner {identity_hash} did not commit this round")
+            }
+            CommitRevealError::AlreadyRevealed { identity_hash } => {
+                write!(f, "miner {identity_hash} already revealed this round")
+            }
+            CommitRevealError::CommitmentMismatch { identity_hash } => {
+                write!(
+                    f,
+                    "reveal of miner {identity_hash} does not match its commitment"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CommitRevealError {}
+
+/// Closed round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundOutcome {
+    pub round: u64,
+    /// Nonces of the identities with a matching reveal, by identity hash.
+    pub revealed: BTreeMap<String, [u8; COMMIT_NONCE_LEN]>,
+    /// Identities that committed and did not reveal, in ascending order.
+    pub withheld: Vec<String>,
+}
+
+impl RoundOutcome {
+    /// Revealed identity hashes in ascending order.
+    pub fn revealed_identities(&self) -> Vec<String> {
+        self.revealed.keys().cloned().collect()
+    }
+
+    /// Master seed H₃(prev_hash ‖ ID_Hash₁ ‖ nonce₁ ‖ … ‖ ID_Hash_n ‖ nonce_n)
+    /// over the reveals in ascending identity-hash order, as hex.
+    /// `previous_hash` must be the hash of a block at or after the round's
+    /// reveal deadline.
+    pub fn master_seed(&self, previous_hash: &str) -> String {
+        to_hex(&self.master_seed_digest(previous_hash))
+    }
+
+    /// Raw 256-bit form of [`RoundOutcome::master_seed`].
+    pub fn master_seed_digest(&self, previous_hash: &str) -> Digest32 {
+        let mut encoder = PreimageEncoder::new(PreimageKind::RoundSeed);
+        encoder.str(previous_hash).u64(self.revealed.len() as u64);
+        for (identity_hash, nonce) in &self.revealed {
+            encoder.str(identity_hash).bytes(nonce);
+        }
+        triple_layer_hash(HashDomain::MasterSeed, &encoder.finish())
+    }
+
+    /// DURA over the revealed identities, shuffled by the round's master
+    /// seed. Identities that withheld or never committed get no range;
+    /// [`MinerRange::miner_id`](crate::dura::MinerRange::miner_id) indexes
+    /// [`RoundOutcome::revealed_identities`].
+    pub fn assign_ranges(&self, previous_hash: &str) -> DuraAssignment {
+        assign_ranges_with_seed(
+            &self.master_seed_digest(previous_hash),
+            &self.revealed_identities(),
+        )
+    }
+
+    /// Revealed identity hashes also in `active_miners`, in ascending order.
+    pub fn active_identities(&self, active_miners: &[MinerIdentity]) -> Vec<String> {
+        let active: BTreeSet<String> = active_miners
+            .iter()
+            .map(MinerIdentity::identity_hash)
+            .collect();
+        self.revealed
+            .keys()
+            .filter(|identity_hash| active.contains(*identity_hash))
+            .cloned()
+            .collect()
+    }
+
+    /// [`RoundOutcome::assign_ranges`] over the revealed identities still in
+    /// `active_miners`, so a miner deactivated since it revealed holds no
+    /// range. The master seed still covers every reveal;
+    /// [`MinerRange::miner_id`](crate::dura::MinerRange::miner_id) indexes
+    /// [`RoundOutcome::active_identities`].
+    pub fn assign_active_ranges(
+        &self,
+        previous_hash: &str,
+        active_miners: &[MinerIdentity],
+    ) -> DuraAssignment {
+        assign_ranges_with_seed(
+            &self.master_seed_digest(previous_hash),
+            &self.active_identities(active_miners),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct OpenRound {
+    commitments: BTreeMap<String, Digest32>,
+    revealed: BTreeMap<String, [u8; COMMIT_NONCE_LEN]>,
+}
+
+/// Commit–reveal state across rounds, fed one block at a time.
+#[derive(Debug, Clone, Default)]
+pub struct CommitReveal {
+    open: BTreeMap<u64, OpenRound>,
+    outcomes: BTreeMap<u64, RoundOutcome>,
+    /// First round each withholding identity may commit in again.
+    excluded_until: BTreeMap<String, u64>,
+    last_height: Option<u64>,
+}
+
+impl CommitReveal {
+    pub fn new() -> Self {
+        CommitReveal::default()
+    }
+
+    /// Applies the round messages of the block at `height`, in block order,
+    /// after closing every round whose reveal phase ended before it. Either
+    /// every message applies or none does.
+    ///
+    /// Senders must be active in `registry` at the round's first height and
+    /// sign with the key they registered.
+    pub fn apply_block(
+        &mut self,
+        height: u64,
+        messages: &[RoundMessage],
+        registry: &MinerRegistry,
+    ) -> Result<(), CommitRevealError> {
+        if let Some(last) = self.last_height {
+            if height <= last {
+                return Err(CommitRevealError::HeightNotIncreasing {
+                    last,
+                    found: height,
+                });
+            }
+        }
+
+        // Stage only what a block can change; closed outcomes are appended
+        // once every message has applied
+        let mut open = self.open.clone();
+        let mut excluded_until = self.excluded_until.clone();
+        let closed = close_rounds(&mut open, &mut excluded_until, height);
+        let window = RoundWindow::at(height);
+        let phase = window.phase(height).expect("height within its round");
+        for message in messages {
+            verify_sender(registry, &window, message)?;
+            apply_message(
+                &mut open,
+                &excluded_until,
+                window.round,
+                height,
+                phase,
+                message,
+            )?;
+        }
+
+        self.open = open;
+        self.excluded_until = excluded_until;
+        self.outcomes
+            .extend(closed.into_iter().map(|outcome| (outcome.round, outcome)));
+        self.last_height = Some(height);
+        Ok(())
+    }
+
+    /// Outcome of `round`. `None` until a block past its reveal phase is
+    /// applied, and for rounds in which nobody committed.
+    pub fn outcome(&self, round: u64) -> Option<&RoundOutcome> {
+        self.outcomes.get(&round)
+    }
+
+    /// Whether `identity_hash` may not commit in `round`.
+    pub fn is_excluded(&self, identity_hash: &str, round: u64) -> bool {
+        self.excluded_until
+            .get(identity_hash)
+            .is_some_and(|&until| round < until)
+    }
+}
+
+// The sender is active for the round and signed with its registered key
+fn verify_sender(
+    registry: &MinerRegistry,
+    window: &RoundWindow,
+    message: &RoundMessage,
+) -> Result<(), CommitRevealError> {
+    let identity_hash = message.identity_hash();
+    let identity = registry
+        .identity(identity_hash)
+        .filter(|_| registry.is_active(identity_hash, window.commit_start))
+        .ok_or_else(|| CommitRevealError::NotActive {
+            identity_hash: identity_hash.to_string(),
+            round: window.round,
+        })?;
+    signing::verify(
+        &identity.public_key,
+        &message.signing_message(window.round),
+        message.signature(),
+    )
+    .map_err(|error| CommitRevealError::InvalidSignature {
+        identity_hash: identity_hash.to_string(),
+        error,
+    })
+}
+
+fn apply_message(
+    open_rounds: &mut BTreeMap<u64, OpenRound>,
+    excluded_until: &BTreeMap<String, u64>,
+    round: u64,
+    height: u64,
+    phase: Phase,
+    message: &RoundMessage,
+) -> Result<(), CommitRevealError> {
+    match (message, phase) {
+        (
+            RoundMessage::Commit {
+                identity_hash,
+                commitment,
+                ..
+            },
+            Phase::Commit,
+        ) => {
+            if let Some(&until_round) = excluded_until.get(identity_hash) {
+                if round < until_round {
+                    return Err(CommitRevealError::Excluded {
+                        identity_hash: identity_hash.clone(),
+                        until_round,
+                    });
+                }
+            }
+            let open = open_rounds.entry(round).or_default();
+            if open.commitments.contains_key(identity_hash) {
+                return Err(CommitRevealError::AlreadyCommitted {
+                    identity_hash: identity_hash.clone(),
+                });
+            }
+            open.commitments.insert(identity_hash.clone(), *commitment);
+            Ok(())
+        }
+        (
+            RoundMessage::Reveal {
+                identity_hash,
+                nonce,
+                ..
+            },
+            Phase::Reveal,
+        ) => {
+            let not_committed = || CommitRevealError::NotCommitted {
+                identity_hash: identity_hash.clone(),
+            };
+            let open = open_rounds.get_mut(&round).ok_or_else(not_committed)?;
+            let committed = open
+                .commitments
+                .get(identity_hash)
+                .ok_or_else(not_committed)?;
+            if open.revealed.contains_key(identity_hash) {
+                return Err(CommitRevealError::AlreadyRevealed {
+                    identity_hash: identity_hash.clone(),
+                });
+            }
+            if *committed != commitment(identity_hash, round, nonce) {
+                return Err(CommitRevealError::CommitmentMismatch {
+                    identity_hash: identity_hash.clone(),
+                });
+            }
+            open.revealed.insert(identity_hash.clone(), *nonce);
+            Ok(())
+        }
+        _ => Err(CommitRevealError::WrongPhase { height, phase }),
+    }
+}
+
+// Rounds whose reveal phase ends at or before `height` take no more
+// messages; their outcomes and exclusions are fixed here
+fn close_rounds(
+    open_rounds: &mut BTreeMap<u64, OpenRound>,
+    excluded_until: &mut BTreeMap<String, u64>,
+    height: u64,
+) -> Vec<RoundOutcome> {
+    let current = RoundWindow::at(height).round;
+    let still_open = open_rounds.split_off(&current);
+    std::mem::replace(open_rounds, still_open)
+        .into_iter()
+        .map(|(round, open)| {
+            let withheld: Vec<String> = open
+                .commitments
+                .into_keys()
+                .filter(|identity_hash| !open.revealed.contains_key(identity_hash))
+                .collect();
+            for identity_hash in &withheld {
+                excluded_until.insert(
+                    identity_hash.clone(),
+                    round + 1 + NON_REVEAL_EXCLUSION_ROUNDS,
+                );
+            }
+            RoundOutcome {
+                round,
+                revealed: open.revealed,
+                withheld,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,154 @@
+//! Difficulty target and retargeting.
+//!
+//! A regular block is valid only if the leading 64 bits of its header hash,
+//! read big-endian, are at most the [`DifficultyTarget`] threshold, so each
+//! attempt succeeds with probability p ≈ threshold / 2⁶⁴.
+//!
+//! With `n` active miners each hashing `W` headers inside the window τ, a
+//! height falls to the System Miner with probability
+//! q = (1 − p)^(n·W) ≈ e^(−p·n·W). The target for a height is the p giving
+//! q = [`TARGET_SYSBLOCK_RATE_PERCENT`]:
+//!
+//! ```text
+//! p = −ln(q*) / (n · W)
+//! ```
+//!
+//! The miner count is known exactly at every height; `W` is not, so it is
+//! estimated. The estimate starts at one full range per miner
+//! ([`NONCES_PER_MINER`]) and every [`RETARGET_INTERVAL`] blocks is rescaled
+//! from the observed SysBlock share q̂:
+//!
+//! ```text
+//! W' = W · ln(q̂) / ln(q*)
+//! ```
+//!
+//! bounded by [`MAX_RETARGET_FACTOR`] either way. Per-miner work therefore
+//! stays constant while the total range grows with the miner count. All
+//! arithmetic is integer so every node derives the same target.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::{digest_prefix_u64, Digest32};
+use crate::params::{
+    MAX_RETARGET_FACTOR, NONCES_PER_MINER, RETARGET_INTERVAL, TARGET_SYSBLOCK_RATE_PERCENT,
+};
+
+/// ln 2 in Q32 fixed point.
+const LN_2_Q32: u128 = 2_977_044_472;
+
+/// Threshold on the leading 64 bits of a header hash.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct DifficultyTarget(u64);
+
+impl DifficultyTarget {
+    /// Every hash qualifies.
+    pub const MAX: DifficultyTarget = DifficultyTarget(u64::MAX);
+
+    pub const fn from_threshold(threshold: u64) -> Self {
+        DifficultyTarget(threshold)
+    }
+
+    pub const fn threshold(&self) -> u64 {
+        self.0
+    }
+
+    /// Target for `active_miners` miners each hashing `work_per_miner`
+    /// headers per window.
+    pub fn for_network(active_miners: u32, work_per_miner: u64) -> Self {
+        let hashes_per_window =
+            u128::from(active_miners.max(1)) * u128::from(work_per_miner.max(1));
+        // −ln(q*) in Q64, spread over the expected attempts
+        let neg_ln_q64 = neg_log2_q32(TARGET_SYSBLOCK_RATE_PERCENT, 100) * LN_2_Q32;
+        let threshold = neg_ln_q64 / hashes_per_window;
+        DifficultyTarget(u64::try_from(threshold).unwrap_or(u64::MAX))
+    }
+
+    pub fn is_met_by(&self, hash: &Digest32) -> bool {
+        digest_prefix_u64(hash) <= self.0
+    }
+
+    /// Full-width form for [`crate::miner::meets_target`]: the threshold
+    /// followed by all-ones bytes.
+    pub fn to_digest(&self) -> Digest32 {
+        let mut digest = [0xff; 32];
+        digest[..8].copy_from_slice(&self.0.to_be_bytes());
+        digest
+    }
+
+    /// Chance that one header hash qualifies.
+    pub fn success_probability(&self) -> f64 {
+        (self.0 as f64 + 1.0) / 2f64.powi(64)
+    }
+}
+
+/// Effective work estimate carried from epoch to epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Difficulty {
+    /// Estimated header hashes one miner completes inside τ.
+    pub work_per_miner: u64,
+}
+
+impl Default for Difficulty {
+    /// Every miner sweeps its full range once per window.
+    fn default() -> Self {
+        Difficulty {
+            work_per_miner: NONCES_PER_MINER,
+        }
+    }
+}
+
+impl Difficulty {
+    /// Target for a height with `active_miners` regular miners.
+    pub fn target(&self, active_miners: u32) -> DifficultyTarget {
+        DifficultyTarget::for_network(active_miners, self.work_per_miner)
+    }
+
+    /// Whether the block at `height` closes an epoch.
+    pub fn is_retarget_height(height: u64) -> bool {
+        height > 0 && height.is_multiple_of(RETARGET_INTERVAL)
+    }
+
+    /// Rescales the work estimate after an epoch of `blocks` blocks of which
+    /// `sys_blocks` came from the System Miner.
+    pub fn retarget(&self, blocks: u64, sys_blocks: u64) -> Difficulty {
+        if blocks == 0 {
+            return *self;
+        }
+        let sys_blocks = sys_blocks.min(blocks);
+        // Smoothed share (2s + 1) / (2b + 2) stays strictly inside (0, 1)
+        let observed = neg_log2_q32(2 * sys_blocks + 1, 2 * blocks + 2);
+        let wanted = neg_log2_q32(TARGET_SYSBLOCK_RATE_PERCENT, 100);
+
+        let work = u128::from(self.work_per_miner);
+        let scaled = work * observed / wanted;
+        let bound = u128::from(MAX_RETARGET_FACTOR);
+        let clamped = scaled.clamp((work / bound).max(1), work * bound);
+        Difficulty {
+            work_per_miner: u64::try_from(clamped).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+// −log₂(num / den) in Q32 for 0 < num < den
+fn neg_log2_q32(num: u64, den: u64) -> u128 {
+    u128::from(log2_q32(den) - log2_q32(num))
+}
+
+// log₂ x in Q32 for x ≥ 1, by repeated squaring of the mantissa
+fn log2_q32(x: u64) -> u64 {
+    debug_assert!(x > 0);
+    let integer = 63 - u64::from(x.leading_zeros());
+    // Mantissa x / 2^integer in [1, 2), Q63
+    let mut mantissa = u128::from(x) << (63 - integer);
+    let mut fraction = 0u64;
+    for bit in (0..32).rev() {
+        mantissa = (mantissa * mantissa) >> 63;
+        if mantissa >= 1 << 64 {
+            mantissa >>= 1;
+            fraction |= 1 << bit;
+        }
+    }
+    (integer << 32) | fraction
+}
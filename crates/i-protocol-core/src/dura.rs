@@ -0,0 +1,200 @@
+//! Deterministic Unique Range Assignment.
+//!
+//! Every block, DURA derives a master seed from the previous block hash and
+//! the sorted identity hashes of the active miners, shuffles the miners with
+//! a ChaCha20 stream keyed by it, and hands out consecutive nonce ranges of
+//! [`NONCES_PER_MINER`](crate::params::NONCES_PER_MINER) in shuffled order,
+//! after the System Miner segment (see [`crate::layout`]).
+//!
+//! [`assign_ranges`] keys the shuffle from public data alone. Once the
+//! commit–reveal rounds decide the participants,
+//! [`RoundOutcome::assign_ranges`](crate::commit_reveal::RoundOutcome::assign_ranges)
+//! runs the same assignment under the round's master seed instead, through
+//! [`assign_ranges_with_seed`].
+//!
+//! Published shuffle vectors live in `test-vectors/dura_shuffle.json`; other
+//! node implementations should reproduce them exactly.
+
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::commit_reveal::RoundOutcome;
+use crate::encoding::{PreimageEncoder, PreimageKind};
+use crate::hash::{to_hex, triple_layer_hash, Digest32, HashDomain};
+use crate::identity::MinerIdentity;
+use crate::layout::RangeLayout;
+
+/// Nonce range assigned to one miner for one block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinerRange {
+    /// Index of the miner in the slice passed to [`assign_ranges`].
+    pub miner_id: u32,
+    pub identity_hash: String,
+    pub start_nonce: u64,
+    pub end_nonce: u64,
+    pub range_size: u64,
+    /// Position of the miner after the shuffle.
+    pub position: usize,
+}
+
+/// Result of a DURA run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuraAssignment {
+    pub master_seed: String,
+    /// Ranges in ascending nonce order.
+    pub ranges: Vec<MinerRange>,
+}
+
+impl DuraAssignment {
+    /// Owner lookup over these ranges.
+    pub fn range_table(&self) -> RangeTable {
+        RangeTable {
+            ranges: self.ranges.clone(),
+        }
+    }
+}
+
+/// DURA ranges of one block, searchable by nonce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeTable {
+    /// Contiguous, in ascending nonce order as [`assign_ranges`] emits them.
+    ranges: Vec<MinerRange>,
+}
+
+impl RangeTable {
+    /// Public-seed DURA over `miners` for the block following
+    /// `previous_hash`, the assignment in force until the first commit–reveal
+    /// round closes.
+    pub fn for_block(previous_hash: &str, miners: &[MinerIdentity]) -> Self {
+        assign_ranges(previous_hash, miners).range_table()
+    }
+
+    /// DURA over the revealed identities of `outcome` still in
+    /// `active_miners`, for the block following `previous_hash`, under the
+    /// round's master seed.
+    pub fn for_round(
+        previous_hash: &str,
+        outcome: &RoundOutcome,
+        active_miners: &[MinerIdentity],
+    ) -> Self {
+        outcome
+            .assign_active_ranges(previous_hash, active_miners)
+            .range_table()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Range containing `nonce`, found by binary search. `None` for the
+    /// System Miner segment and past the last range.
+    pub fn range_of(&self, nonce: u64) -> Option<&MinerRange> {
+        let index = self.ranges.partition_point(|range| range.end_nonce < nonce);
+        self.ranges
+            .get(index)
+            .filter(|range| range.start_nonce <= nonce)
+    }
+
+    /// Identity hash of the miner whose range contains `nonce`.
+    pub fn owner_of(&self, nonce: u64) -> Option<&str> {
+        self.range_of(nonce)
+            .map(|range| range.identity_hash.as_str())
+    }
+}
+
+/// Master Seed = H₃(prev_hash ‖ sort(ID_Hash₁, …, ID_Hash_n)) as hex.
+///
+/// The identity hashes are sorted here, so callers may pass them in any order.
+pub fn master_seed(previous_hash: &str, identity_hashes: &[String]) -> String {
+    to_hex(&master_seed_digest(previous_hash, identity_hashes))
+}
+
+/// Raw 256-bit form of [`master_seed`], used as the ChaCha20 key.
+pub fn master_seed_digest(previous_hash: &str, identity_hashes: &[String]) -> Digest32 {
+    let mut sorted = identity_hashes.to_vec();
+    sorted.sort();
+
+    let preimage = PreimageEncoder::new(PreimageKind::MasterSeed)
+        .str(previous_hash)
+        .strs(&sorted)
+        .finish();
+    triple_layer_hash(HashDomain::MasterSeed, &preimage)
+}
+
+/// Fisher–Yates permutation of `0..miner_count` driven by `master_seed`.
+///
+/// The PRNG is ChaCha20 (20 rounds, 64-bit block counter and stream id both
+/// starting at zero) keyed with the 32-byte seed. Each draw takes the next
+/// eight keystream bytes as a little-endian `u64`. For i = n−1 down to 1 the
+/// swap index j ∈ [0, i] is drawn by rejection sampling: values at or above
+/// the largest multiple of i + 1 that fits in 2⁶⁴ are discarded, the rest are
+/// reduced modulo i + 1. No index is favoured.
+pub fn deterministic_shuffle(master_seed: &Digest32, miner_count: usize) -> Vec<usize> {
+    let mut rng = ChaCha20Rng::from_seed(*master_seed);
+    let mut indices: Vec<usize> = (0..miner_count).collect();
+
+    for i in (1..miner_count).rev() {
+        let j = uniform_index(&mut rng, i as u64 + 1);
+        indices.swap(i, j as usize);
+    }
+
+    indices
+}
+
+// Unbiased draw from [0, bound) by rejection sampling
+fn uniform_index(rng: &mut ChaCha20Rng, bound: u64) -> u64 {
+    // 2⁶⁴ mod bound values at the top of the u64 range would skew the result
+    let rejected = (u64::MAX % bound + 1) % bound;
+    let limit = u64::MAX - rejected;
+    loop {
+        let value = rng.next_u64();
+        if value <= limit {
+            return value % bound;
+        }
+    }
+}
+
+/// Runs DURA over `miners` for the block following `previous_hash`.
+///
+/// Range_i = [10,001 + i × 250,000, 10,000 + (i + 1) × 250,000] for shuffled
+/// position i.
+pub fn assign_ranges(previous_hash: &str, miners: &[MinerIdentity]) -> DuraAssignment {
+    let identity_hashes: Vec<String> = miners.iter().map(MinerIdentity::identity_hash).collect();
+    let seed = master_seed_digest(previous_hash, &identity_hashes);
+    assign_ranges_with_seed(&seed, &identity_hashes)
+}
+
+/// Runs DURA over the miners with `identity_hashes`, shuffled by a master
+/// seed derived elsewhere. [`MinerRange::miner_id`] indexes
+/// `identity_hashes`.
+pub fn assign_ranges_with_seed(seed: &Digest32, identity_hashes: &[String]) -> DuraAssignment {
+    let shuffled_indices = deterministic_shuffle(seed, identity_hashes.len());
+    let layout = RangeLayout::new(identity_hashes.len());
+
+    let ranges = shuffled_indices
+        .iter()
+        .enumerate()
+        .map(|(position, &original_index)| {
+            let range = layout
+                .regular_range(position)
+                .expect("position within layout");
+            MinerRange {
+                miner_id: original_index as u32,
+                identity_hash: identity_hashes[original_index].clone(),
+                start_nonce: range.start,
+                end_nonce: range.end,
+                range_size: range.size(),
+                position,
+            }
+        })
+        .collect();
+
+    DuraAssignment {
+        master_seed: to_hex(seed),
+        ranges,
+    }
+}
@@ -0,0 +1,55 @@
+//! `I` token supply and the 100-year emission schedule.
+//!
+//! All amounts are in `i` subunits unless stated otherwise.
+
+/// 1 I = 10¹² i.
+pub const SUBUNIT_RATIO: u128 = 1_000_000_000_000;
+
+/// Theoretical maximum supply, in whole `I`.
+pub const THEORETICAL_SUPPLY_I: u64 = 1_000_000;
+
+/// Rounding dust burned once in the genesis block (5.6517 I).
+pub const GENESIS_DUST_BURN: u128 = 5_651_700_000_000;
+
+/// Supply that will ever circulate after the genesis burn.
+pub const EFFECTIVE_TOTAL_SUPPLY: u128 = 999_994_348_300_000_000;
+
+/// One-time Golden Block mint during the Genesis Event (10,000 I).
+pub const GOLDEN_BLOCK_REWARD: u128 = 10_000 * SUBUNIT_RATIO;
+
+/// Supply distributed through standard block rewards.
+pub const STANDARD_MINING_SUPPLY: u128 = 989_994_348_300_000_000;
+
+/// Block reward during the first halving period (0.003923045138 I).
+///
+/// The specification lists this as `3_923_045_138_888`, which is the same
+/// figure in 10⁻¹⁵ I; read as `i` it would emit ~495 million I in the first
+/// halving period alone. It is truncated here to `i` precision.
+pub const INITIAL_BLOCK_REWARD: u128 = 3_923_045_138;
+
+/// Blocks per halving period (two years of 0.5 s blocks).
+pub const HALVING_INTERVAL: u64 = 126_144_000;
+
+/// Halving periods in the 100-year schedule.
+pub const HALVING_PERIODS: u64 = 50;
+
+/// Standard block reward at `height`.
+pub fn block_reward(height: u64) -> u128 {
+    let period = height / HALVING_INTERVAL;
+    if period >= HALVING_PERIODS {
+        return 0;
+    }
+    INITIAL_BLOCK_REWARD >> period
+}
+
+/// Standard rewards emitted by blocks `0..height`.
+pub fn cumulative_emission(height: u64) -> u128 {
+    let full_periods = (height / HALVING_INTERVAL).min(HALVING_PERIODS);
+    let mut total: u128 = (0..full_periods)
+        .map(|period| (INITIAL_BLOCK_REWARD >> period) * HALVING_INTERVAL as u128)
+        .sum();
+    if full_periods < HALVING_PERIODS {
+        total += block_reward(height) * (height % HALVING_INTERVAL) as u128;
+    }
+    total
+}
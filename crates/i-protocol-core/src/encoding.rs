@@ -0,0 +1,122 @@
+//! Canonical binary encoding for hash preimages.
+//!
+//! Building preimages with `format!` concatenation is ambiguous: the tuple
+//! (12, "3ab") and the tuple (123, "ab") both render as `"123ab"` and hash to
+//! the same value. Every preimage in the protocol is instead produced by a
+//! [`PreimageEncoder`]:
+//!
+//! ```text
+//! preimage = version:u8 ‖ kind:u8 ‖ field₁ ‖ field₂ ‖ …
+//! integer  = fixed-width big-endian
+//! bytes    = len:u32 big-endian ‖ raw bytes
+//! list     = count:u32 big-endian ‖ item₁ ‖ item₂ ‖ …
+//! ```
+//!
+//! Each [`PreimageKind`] has a fixed field order, so two preimages are equal
+//! only if they are of the same kind and every field is equal.
+
+/// Version byte leading every preimage.
+pub const ENCODING_VERSION: u8 = 1;
+
+/// Structure a preimage encodes. Written after the version byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum PreimageKind {
+    BlockchainState = 1,
+    SystemNonce = 2,
+    TnoMapping = 3,
+    MinerIdentity = 4,
+    /// Retired ad-hoc identity layout, kept so its tag is never reused.
+    ComponentIdentity = 5,
+    MasterSeed = 6,
+    Commitment = 7,
+    SecretNonce = 8,
+    Transaction = 9,
+    BlockHeader = 10,
+    MempoolOrder = 11,
+    ForkChoiceRule = 12,
+    SysBlockConflict = 13,
+    IdentityRegistration = 14,
+    MinerDeregistration = 15,
+    RoundMessage = 16,
+    RoundSeed = 17,
+}
+
+/// Builder for a canonical preimage.
+#[derive(Debug, Clone)]
+pub struct PreimageEncoder {
+    buf: Vec<u8>,
+}
+
+impl PreimageEncoder {
+    pub fn new(kind: PreimageKind) -> Self {
+        PreimageEncoder {
+            buf: vec![ENCODING_VERSION, kind as u8],
+        }
+    }
+
+    pub fn u8(&mut self, value: u8) -> &mut Self {
+        self.buf.push(value);
+        self
+    }
+
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn u128(&mut self, value: u128) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        self.u8(value as u8)
+    }
+
+    /// Length-prefixed byte string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is longer than `u32::MAX` bytes.
+    pub fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        let len = u32::try_from(value.len()).expect("preimage field exceeds u32::MAX bytes");
+        self.u32(len);
+        self.buf.extend_from_slice(value);
+        self
+    }
+
+    /// Length-prefixed UTF-8 string.
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.bytes(value.as_bytes())
+    }
+
+    /// Count-prefixed list of length-prefixed strings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` holds more than `u32::MAX` items.
+    pub fn strs<S: AsRef<str>>(&mut self, values: &[S]) -> &mut Self {
+        let count = u32::try_from(values.len()).expect("preimage list exceeds u32::MAX items");
+        self.u32(count);
+        for value in values {
+            self.str(value.as_ref());
+        }
+        self
+    }
+
+    /// Takes the encoded preimage out of the encoder.
+    pub fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+/// Types with a canonical preimage.
+pub trait CanonicalEncode {
+    fn canonical_bytes(&self) -> Vec<u8>;
+}
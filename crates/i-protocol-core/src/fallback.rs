@@ -0,0 +1,143 @@
+//! System Miner fallback activation.
+//!
+//! Each node starts a round when it begins waiting for the next block and
+//! activates the System Miner if no valid block has arrived once τ has
+//! elapsed on its own [`Clock`]. Nothing is compared across nodes, so clock
+//! drift and wall-clock adjustments cannot move the decision.
+
+use std::time::Duration;
+
+use crate::clock::Clock;
+use crate::params::REGULAR_MINING_WINDOW_MS;
+
+/// Result of [`FallbackDriver::poll`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activation {
+    /// No round in progress.
+    Idle,
+    /// Window still open.
+    Pending { remaining: Duration },
+    /// τ elapsed with no valid block: produce a SysBlock now. Returned once
+    /// per round.
+    Fire,
+    /// A valid block arrived in time; the System Miner stays idle.
+    BlockReceived,
+    /// [`Activation::Fire`] was already returned for this round.
+    Fired,
+}
+
+/// Timing of a valid block relative to the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockArrival {
+    /// Arrived before τ elapsed; the round is closed.
+    InTime,
+    /// Arrived at or after τ; it does not stop the fallback.
+    Late,
+    /// No round in progress.
+    NoRound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoundState {
+    Open,
+    BlockReceived,
+    Fired,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Round {
+    started: Duration,
+    state: RoundState,
+}
+
+/// Decides when the System Miner activates.
+#[derive(Debug, Clone)]
+pub struct FallbackDriver<C> {
+    clock: C,
+    window: Duration,
+    round: Option<Round>,
+}
+
+impl<C: Clock> FallbackDriver<C> {
+    /// Driver with the protocol window τ.
+    pub fn new(clock: C) -> Self {
+        FallbackDriver::with_window(clock, Duration::from_millis(REGULAR_MINING_WINDOW_MS))
+    }
+
+    pub fn with_window(clock: C, window: Duration) -> Self {
+        FallbackDriver {
+            clock,
+            window,
+            round: None,
+        }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Opens a new round at the current time.
+    pub fn start_round(&mut self) {
+        self.round = Some(Round {
+            started: self.clock.now(),
+            state: RoundState::Open,
+        });
+    }
+
+    /// Time the current round's window closes.
+    pub fn deadline(&self) -> Option<Duration> {
+        self.round.map(|round| round.started + self.window)
+    }
+
+    /// Time elapsed in the current round.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.round
+            .map(|round| self.clock.now().saturating_sub(round.started))
+    }
+
+    pub fn poll(&mut self) -> Activation {
+        let now = self.clock.now();
+        let window = self.window;
+        let Some(round) = self.round.as_mut() else {
+            return Activation::Idle;
+        };
+
+        match round.state {
+            RoundState::BlockReceived => Activation::BlockReceived,
+            RoundState::Fired => Activation::Fired,
+            RoundState::Open => {
+                let deadline = round.started + window;
+                if now >= deadline {
+                    round.state = RoundState::Fired;
+                    Activation::Fire
+                } else {
+                    Activation::Pending {
+                        remaining: deadline - now,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records a valid block for the current round.
+    pub fn on_valid_block(&mut self) -> BlockArrival {
+        let now = self.clock.now();
+        let window = self.window;
+        let Some(round) = self.round.as_mut() else {
+            return BlockArrival::NoRound;
+        };
+
+        match round.state {
+            RoundState::Open if now < round.started + window => {
+                round.state = RoundState::BlockReceived;
+                BlockArrival::InTime
+            }
+            RoundState::BlockReceived => BlockArrival::InTime,
+            RoundState::Open | RoundState::Fired => BlockArrival::Late,
+        }
+    }
+}
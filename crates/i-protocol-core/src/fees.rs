@@ -0,0 +1,50 @@
+//! Transaction fee model (v7.2) from the tokenomics specification.
+//!
+//! All amounts are in `i` subunits (1 I = 10¹² i, 1 USD = 10⁶ i).
+
+/// Smallest transaction amount the protocol accepts ($0.01).
+pub const MIN_TRANSACTION_AMOUNT: u128 = 10_000;
+
+/// Amount from which the proportional 1% fee applies ($1.00).
+pub const PROPORTIONAL_FEE_THRESHOLD: u128 = 1_000_000;
+
+/// Flat fee charged on microtransactions ($0.01).
+pub const FLAT_FEE: u128 = 10_000;
+
+/// Hard ceiling on any fee ($10,000).
+pub const MAX_FEE: u128 = 10_000_000_000;
+
+/// Fee owed on a transaction of `txn_amount_i`.
+///
+/// Returns 0 for amounts below [`MIN_TRANSACTION_AMOUNT`], which the protocol
+/// rejects outright.
+pub fn calculate_fee(txn_amount_i: u128) -> u128 {
+    match txn_amount_i {
+        0..=9_999 => 0,
+        10_000..=999_999 => FLAT_FEE,
+        _ => (txn_amount_i / 100).min(MAX_FEE),
+    }
+}
+
+/// On-chain allocation of a fee.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSplit {
+    /// 50% to active miners.
+    pub miners: u128,
+    /// 30% to the Network Development Fund.
+    pub development_fund: u128,
+    /// 20% permanently burned.
+    pub burn: u128,
+}
+
+/// Splits `fee` 50/30/20. Rounding dust from the integer division is burned,
+/// so the three parts always sum to `fee`.
+pub fn split_fee(fee: u128) -> FeeSplit {
+    let miners = fee / 2;
+    let development_fund = fee * 3 / 10;
+    FeeSplit {
+        miners,
+        development_fund,
+        burn: fee - miners - development_fund,
+    }
+}
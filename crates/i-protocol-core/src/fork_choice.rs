@@ -0,0 +1,183 @@
+//! Fork choice between competing blocks at the same height.
+//!
+//! The specification resolves competing blocks without a vote:
+//!
+//! - **FCR** between regular blocks: argmin `Blake3(merkle_root ‖ nonce)`.
+//! - **SCRF** between SysBlocks: argmin `Blake3(merkle_root ‖ timestamp)`.
+//!
+//! A SysBlock only stands in for a missing regular block, so a valid regular
+//! block for the same height displaces it even when it arrives later. Keys
+//! that tie fall back to the header hash.
+//!
+//! [`ForkChoice`] applies these rules height by height: at every height the
+//! canonical block is the preferred candidate among those extending the
+//! canonical block below it.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::block::BlockHeader;
+use crate::encoding::{PreimageEncoder, PreimageKind};
+use crate::hash::Digest32;
+
+/// FCR key of a regular block.
+pub fn fcr_key(header: &BlockHeader) -> Digest32 {
+    let preimage = PreimageEncoder::new(PreimageKind::ForkChoiceRule)
+        .bytes(&header.merkle_root)
+        .u64(header.nonce())
+        .finish();
+    *blake3::hash(&preimage).as_bytes()
+}
+
+/// SCRF key of a SysBlock.
+pub fn scrf_key(header: &BlockHeader) -> Digest32 {
+    let preimage = PreimageEncoder::new(PreimageKind::SysBlockConflict)
+        .bytes(&header.merkle_root)
+        .u64(header.timestamp)
+        .finish();
+    *blake3::hash(&preimage).as_bytes()
+}
+
+/// Orders two blocks for the same height; [`Ordering::Less`] means `a` wins.
+pub fn compare(a: &BlockHeader, b: &BlockHeader) -> Ordering {
+    match (a.is_sys_block(), b.is_sys_block()) {
+        (false, true) => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (false, false) => fcr_key(a).cmp(&fcr_key(b)),
+        (true, true) => scrf_key(a).cmp(&scrf_key(b)),
+    }
+    .then_with(|| a.hash().cmp(&b.hash()))
+}
+
+/// Effect of offering a block to [`ForkChoice::insert`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForkEvent {
+    /// The block became the new tip.
+    Extended { height: u64, hash: Digest32 },
+    /// The canonical chain switched branches from `height` upwards.
+    Reorg(Reorg),
+    /// Stored as a competing block; the canonical chain is unchanged.
+    Stored,
+    /// Already known.
+    Duplicate,
+    /// Parent unknown; the block was not stored.
+    Orphan,
+}
+
+/// Canonical chain change from `height` upwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reorg {
+    pub height: u64,
+    /// Previously canonical blocks, lowest first.
+    pub disconnected: Vec<Digest32>,
+    /// Newly canonical blocks, lowest first.
+    pub connected: Vec<Digest32>,
+}
+
+/// Competing tips per height and the canonical chain above a root block.
+#[derive(Debug, Clone)]
+pub struct ForkChoice {
+    root_height: u64,
+    headers: HashMap<Digest32, BlockHeader>,
+    candidates: BTreeMap<u64, Vec<Digest32>>,
+    /// Canonical hash per height, `root_height` included.
+    canonical: BTreeMap<u64, Digest32>,
+}
+
+impl ForkChoice {
+    /// Starts from an accepted block at `root_height`, e.g. genesis.
+    pub fn new(root_hash: Digest32, root_height: u64) -> Self {
+        ForkChoice {
+            root_height,
+            headers: HashMap::new(),
+            candidates: BTreeMap::new(),
+            canonical: BTreeMap::from([(root_height, root_hash)]),
+        }
+    }
+
+    /// Canonical tip as (height, hash).
+    pub fn tip(&self) -> (u64, Digest32) {
+        let (&height, &hash) = self.canonical.last_key_value().expect("root is canonical");
+        (height, hash)
+    }
+
+    pub fn canonical_hash(&self, height: u64) -> Option<Digest32> {
+        self.canonical.get(&height).copied()
+    }
+
+    pub fn header(&self, hash: &Digest32) -> Option<&BlockHeader> {
+        self.headers.get(hash)
+    }
+
+    /// Blocks known at `height`, canonical or not.
+    pub fn candidates(&self, height: u64) -> &[Digest32] {
+        self.candidates.get(&height).map_or(&[], Vec::as_slice)
+    }
+
+    /// Offers an already validated block.
+    pub fn insert(&mut self, header: BlockHeader) -> ForkEvent {
+        let hash = header.hash();
+        if self.headers.contains_key(&hash) {
+            return ForkEvent::Duplicate;
+        }
+        if header.height <= self.root_height
+            || !self.is_known(header.height - 1, &header.previous_hash)
+        {
+            return ForkEvent::Orphan;
+        }
+
+        let height = header.height;
+        let extends_canonical = self.canonical.get(&(height - 1)) == Some(&header.previous_hash);
+        self.headers.insert(hash, header);
+        self.candidates.entry(height).or_default().push(hash);
+        if !extends_canonical {
+            return ForkEvent::Stored;
+        }
+
+        let before: Vec<Digest32> = self.canonical.range(height..).map(|(_, &h)| h).collect();
+        self.rebuild_from(height);
+        let after: Vec<Digest32> = self.canonical.range(height..).map(|(_, &h)| h).collect();
+
+        if before == after {
+            ForkEvent::Stored
+        } else if before.is_empty() {
+            ForkEvent::Extended { height, hash }
+        } else {
+            ForkEvent::Reorg(Reorg {
+                height,
+                disconnected: before,
+                connected: after,
+            })
+        }
+    }
+
+    fn is_known(&self, height: u64, hash: &Digest32) -> bool {
+        if height == self.root_height {
+            return self.canonical.get(&height) == Some(hash);
+        }
+        self.headers.get(hash).is_some_and(|h| h.height == height)
+    }
+
+    // Re-selects the canonical block at every height from `height` up
+    fn rebuild_from(&mut self, height: u64) {
+        self.canonical.split_off(&height);
+        let mut height = height;
+        loop {
+            let parent = self.canonical[&(height - 1)];
+            let best = self
+                .candidates(height)
+                .iter()
+                .map(|hash| &self.headers[hash])
+                .filter(|header| header.previous_hash == parent)
+                .min_by(|a, b| compare(a, b))
+                .map(BlockHeader::hash);
+            match best {
+                Some(hash) => {
+                    self.canonical.insert(height, hash);
+                    height += 1;
+                }
+                None => break,
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Triple-layer hash H₃: Blake3 → SHA-256 → post-quantum layer.
+//!
+//! The consensus specification defines H₃ as a chain of Blake3 (primary),
+//! SHA-256 (fallback) and a quantum-resistant standby layer. CRYSTALS-Dilithium
+//! is a signature scheme rather than a hash function, so the third layer is
+//! SHA3-256 (Keccak), the NIST post-quantum-safe hash paired with ML-DSA.
+//!
+//! Every use of H₃ is bound to a [`HashDomain`]. The domain keys the Blake3
+//! layer through its key-derivation mode, so an identity hash can never be
+//! replayed as a TNO preimage or a System Miner nonce and vice versa.
+
+use sha2::{Digest, Sha256};
+use sha3::Sha3_256;
+
+/// 32-byte output of [`triple_layer_hash`].
+pub type Digest32 = [u8; 32];
+
+/// Protocol contexts in which H₃ is evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashDomain {
+    /// DURA 8-component miner identity hash.
+    Identity,
+    /// DURA commit–reveal commitments over an identity hash.
+    Commitment,
+    /// DURA master seed over the previous block hash and sorted identities.
+    MasterSeed,
+    /// TNO user-nonce transposition.
+    Tno,
+    /// System Miner fallback nonce.
+    SystemNonce,
+    /// Transaction hash over the unsigned transaction body.
+    Transaction,
+    /// Block header hash.
+    BlockHeader,
+    /// Merkle tree leaves and interior nodes.
+    Merkle,
+}
+
+impl HashDomain {
+    pub const ALL: [HashDomain; 8] = [
+        HashDomain::Identity,
+        HashDomain::Commitment,
+        HashDomain::MasterSeed,
+        HashDomain::Tno,
+        HashDomain::SystemNonce,
+        HashDomain::Transaction,
+        HashDomain::BlockHeader,
+        HashDomain::Merkle,
+    ];
+
+    /// Blake3 key-derivation context string for this domain.
+    pub const fn context(self) -> &'static str {
+        match self {
+            HashDomain::Identity => "I Protocol H3 v1 identity",
+            HashDomain::Commitment => "I Protocol H3 v1 commitment",
+            HashDomain::MasterSeed => "I Protocol H3 v1 master seed",
+            HashDomain::Tno => "I Protocol H3 v1 TNO",
+            HashDomain::SystemNonce => "I Protocol H3 v1 system nonce",
+            HashDomain::Transaction => "I Protocol H3 v1 transaction",
+            HashDomain::BlockHeader => "I Protocol H3 v1 block header",
+            HashDomain::Merkle => "I Protocol H3 v1 merkle",
+        }
+    }
+}
+
+/// H₃(domain, input) = SHA3-256(SHA-256(Blake3_derive_key(domain, input))).
+pub fn triple_layer_hash(domain: HashDomain, input: &[u8]) -> Digest32 {
+    let layer1 = blake3::Hasher::new_derive_key(domain.context())
+        .update(input)
+        .finalize();
+    let layer2 = Sha256::digest(layer1.as_bytes());
+    let layer3 = Sha3_256::digest(layer2);
+    layer3.into()
+}
+
+/// Interprets the leading eight bytes of a digest as a big-endian integer.
+pub fn digest_prefix_u64(digest: &Digest32) -> u64 {
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(prefix)
+}
+
+/// Lower-case hexadecimal rendering of a digest.
+pub fn to_hex(digest: &Digest32) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Parses 64 hexadecimal characters back into a digest.
+pub fn from_hex(hex: &str) -> Option<Digest32> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut digest = [0u8; 32];
+    for (byte, pair) in digest.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let pair = std::str::from_utf8(pair).ok()?;
+        *byte = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(digest)
+}
+
+/// Serde adapter storing a [`Digest32`] as lower-case hex.
+pub mod serde_hex {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::{from_hex, to_hex, Digest32};
+
+    pub fn serialize<S: Serializer>(digest: &Digest32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(digest))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Digest32, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        from_hex(&hex).ok_or_else(|| D::Error::custom("expected 64 hexadecimal characters"))
+    }
+}
@@ -0,0 +1,398 @@
+//! DURA miner identities.
+//!
+//! The specification builds the identity hash from eight components:
+//!
+//! ```text
+//! ID_Hash = H₃(pk ‖ addr ‖ ts ‖ sig ‖ salt ‖ nonce ‖ ts₂)
+//! ```
+//!
+//! where `pk` is the public key. The eighth component, the private key, never
+//! leaves the miner: it enters the identity only through `sig`, the unique
+//! signature made with it over the other components
+//! ([`MinerIdentity::registration_message`]). Earlier drafts hashed the
+//! private key itself; an identity hash is public, so that layout is retired
+//! and its encodings are rejected by [`MinerIdentity::from_canonical_bytes`].
+//!
+//! Every component has a validator, and [`MinerIdentity::validate`] runs them
+//! all. The canonical encoding carries [`IDENTITY_VERSION`] after the
+//! preimage header so the layout can change without ambiguity.
+
+use std::fmt;
+
+use crate::encoding::{CanonicalEncode, PreimageEncoder, PreimageKind, ENCODING_VERSION};
+use crate::hash::{to_hex, triple_layer_hash, HashDomain};
+use crate::signing::{self, Ed25519Signer, SignatureError, SignatureScheme, Signer};
+
+/// Identity layout written after the preimage header.
+///
+/// The retired private-key layout had no version byte; its first field's
+/// length prefix reads as version 0.
+pub const IDENTITY_VERSION: u8 = 1;
+
+pub const SALT_LEN: usize = 32;
+
+pub const MAX_WALLET_ADDRESS_LEN: usize = 128;
+
+/// Identity a miner submits to join DURA range assignment.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MinerIdentity {
+    pub public_key: Vec<u8>,
+    pub wallet_address: String,
+    pub creation_timestamp: u64,
+    /// Signature made with the private key matching `public_key`.
+    pub unique_signature: Vec<u8>,
+    pub salt: Vec<u8>,
+    pub identity_nonce: u64,
+    pub submission_timestamp: u64,
+}
+
+/// One of the eight specification components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdentityComponent {
+    /// Held by the miner; proven through [`IdentityComponent::UniqueSignature`].
+    PrivateKey,
+    PublicKey,
+    WalletAddress,
+    CreationTimestamp,
+    UniqueSignature,
+    Salt,
+    IdentityNonce,
+    SubmissionTimestamp,
+}
+
+impl IdentityComponent {
+    pub const ALL: [IdentityComponent; 8] = [
+        IdentityComponent::PrivateKey,
+        IdentityComponent::PublicKey,
+        IdentityComponent::WalletAddress,
+        IdentityComponent::CreationTimestamp,
+        IdentityComponent::UniqueSignature,
+        IdentityComponent::Salt,
+        IdentityComponent::IdentityNonce,
+        IdentityComponent::SubmissionTimestamp,
+    ];
+}
+
+/// Reason an identity was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentityError {
+    /// Public key length matches no accepted scheme.
+    PublicKeyLength {
+        len: usize,
+    },
+    /// Signature length does not match the public key's scheme.
+    SignatureLength {
+        expected: usize,
+        found: usize,
+    },
+    /// The signature does not verify under the public key.
+    Signature(SignatureError),
+    /// Empty, too long, or not printable ASCII without whitespace.
+    InvalidWalletAddress,
+    ZeroCreationTimestamp,
+    SubmittedBeforeCreation {
+        creation: u64,
+        submission: u64,
+    },
+    SaltLength {
+        len: usize,
+    },
+    /// All-zero salt, the value an unset buffer has.
+    ZeroSalt,
+    /// Preimage header from another encoding version or preimage kind.
+    UnexpectedHeader {
+        version: u8,
+        kind: u8,
+    },
+    UnsupportedVersion {
+        found: u8,
+    },
+    /// Truncated fields, trailing bytes or a non-UTF-8 address.
+    Malformed,
+}
+
+impl IdentityError {
+    /// Component the error is about, if it is a validation failure.
+    pub fn component(&self) -> Option<IdentityComponent> {
+        match self {
+            IdentityError::PublicKeyLength { .. } => Some(IdentityComponent::PublicKey),
+            IdentityError::SignatureLength { .. } | IdentityError::Signature(_) => {
+                Some(IdentityComponent::UniqueSignature)
+            }
+            IdentityError::InvalidWalletAddress => Some(IdentityComponent::WalletAddress),
+            IdentityError::ZeroCreationTimestamp => Some(IdentityComponent::CreationTimestamp),
+            IdentityError::SubmittedBeforeCreation { .. } => {
+                Some(IdentityComponent::SubmissionTimestamp)
+            }
+            IdentityError::SaltLength { .. } | IdentityError::ZeroSalt => {
+                Some(IdentityComponent::Salt)
+            }
+            IdentityError::UnexpectedHeader { .. }
+            | IdentityError::UnsupportedVersion { .. }
+            | IdentityError::Malformed => None,
+        }
+    }
+}
+
+impl fmt::Display for IdentityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentityError::PublicKeyLength { len } => {
+                write!(f, "public key of {len} bytes matches no signature scheme")
+            }
+            IdentityError::SignatureLength { expected, found } => {
+                write!(f, "signature of {found} bytes, expected {expected}")
+            }
+            IdentityError::Signature(error) => write!(f, "unique signature: {error}"),
+            IdentityError::InvalidWalletAddress => write!(f, "invalid wallet address"),
+            IdentityError::ZeroCreationTimestamp => write!(f, "creation timestamp is zero"),
+            IdentityError::SubmittedBeforeCreation {
+                creation,
+                submission,
+            } => write!(
+                f,
+                "submission timestamp {submission} before creation timestamp {creation}"
+            ),
+            IdentityError::SaltLength { len } => {
+                write!(f, "salt of {len} bytes, expected {SALT_LEN}")
+            }
+            IdentityError::ZeroSalt => write!(f, "salt is all zero"),
+            IdentityError::UnexpectedHeader { version, kind } => write!(
+                f,
+                "preimage header version {version} kind {kind} is not a miner identity"
+            ),
+            IdentityError::UnsupportedVersion { found } => {
+                write!(f, "identity layout version {found} not supported")
+            }
+            IdentityError::Malformed => write!(f, "malformed identity encoding"),
+        }
+    }
+}
+
+impl std::error::Error for IdentityError {}
+
+/// Public key validator: the length of a [`SignatureScheme`] key.
+pub fn validate_public_key(public_key: &[u8]) -> Result<SignatureScheme, IdentityError> {
+    SignatureScheme::from_public_key(public_key).ok_or(IdentityError::PublicKeyLength {
+        len: public_key.len(),
+    })
+}
+
+/// Signature validator: `signature` verifies over `message` under
+/// `public_key`. Stands in for the private key, which is only ever shown
+/// through this signature.
+pub fn validate_signature(
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), IdentityError> {
+    let expected = validate_public_key(public_key)?.signature_len();
+    if signature.len() != expected {
+        return Err(IdentityError::SignatureLength {
+            expected,
+            found: signature.len(),
+        });
+    }
+    signing::verify(public_key, message, signature).map_err(IdentityError::Signature)
+}
+
+/// Wallet address validator: 1 to [`MAX_WALLET_ADDRESS_LEN`] bytes of
+/// printable ASCII without whitespace.
+pub fn validate_wallet_address(address: &str) -> Result<(), IdentityError> {
+    let printable = address.bytes().all(|byte| byte.is_ascii_graphic());
+    if address.is_empty() || address.len() > MAX_WALLET_ADDRESS_LEN || !printable {
+        return Err(IdentityError::InvalidWalletAddress);
+    }
+    Ok(())
+}
+
+/// Timestamp validator: a creation time is set and submission does not
+/// precede it.
+pub fn validate_timestamps(creation: u64, submission: u64) -> Result<(), IdentityError> {
+    if creation == 0 {
+        return Err(IdentityError::ZeroCreationTimestamp);
+    }
+    if submission < creation {
+        return Err(IdentityError::SubmittedBeforeCreation {
+            creation,
+            submission,
+        });
+    }
+    Ok(())
+}
+
+/// Salt validator: [`SALT_LEN`] bytes, not all zero.
+pub fn validate_salt(salt: &[u8]) -> Result<(), IdentityError> {
+    if salt.len() != SALT_LEN {
+        return Err(IdentityError::SaltLength { len: salt.len() });
+    }
+    if salt.iter().all(|&byte| byte == 0) {
+        return Err(IdentityError::ZeroSalt);
+    }
+    Ok(())
+}
+
+/// Identity nonce validator. Every value is valid: the nonce only separates
+/// identities that share all other components.
+pub fn validate_identity_nonce(_nonce: u64) -> Result<(), IdentityError> {
+    Ok(())
+}
+
+impl MinerIdentity {
+    /// Identity for `signer`'s key, signed over the other components.
+    pub fn new<S: Signer + ?Sized>(
+        signer: &S,
+        wallet_address: String,
+        creation_timestamp: u64,
+        salt: Vec<u8>,
+        identity_nonce: u64,
+        submission_timestamp: u64,
+    ) -> Self {
+        let mut identity = MinerIdentity {
+            public_key: signer.public_key(),
+            wallet_address,
+            creation_timestamp,
+            unique_signature: Vec::new(),
+            salt,
+            identity_nonce,
+            submission_timestamp,
+        };
+        identity.unique_signature = signer.sign(&identity.registration_message());
+        identity
+    }
+
+    /// Deterministic identity for virtual miner `id`, as used by the
+    /// verification programs.
+    pub fn virtual_miner(id: u32, base_timestamp: u64) -> Self {
+        MinerIdentity::new(
+            &MinerIdentity::virtual_signer(id),
+            format!("addr_{:08x}", id),
+            base_timestamp + (id as u64 * 1000),
+            virtual_bytes("salt", id).to_vec(),
+            id as u64,
+            base_timestamp + (id as u64 * 1000) + 500,
+        )
+    }
+
+    /// Key behind [`MinerIdentity::virtual_miner`] `id`.
+    pub fn virtual_signer(id: u32) -> Ed25519Signer {
+        Ed25519Signer::from_seed(&virtual_bytes("secret key", id))
+    }
+
+    /// Message the unique signature covers: every other component.
+    pub fn registration_message(&self) -> Vec<u8> {
+        PreimageEncoder::new(PreimageKind::IdentityRegistration)
+            .u8(IDENTITY_VERSION)
+            .bytes(&self.public_key)
+            .str(&self.wallet_address)
+            .u64(self.creation_timestamp)
+            .bytes(&self.salt)
+            .u64(self.identity_nonce)
+            .u64(self.submission_timestamp)
+            .finish()
+    }
+
+    /// Runs every component validator, verifying the signature last.
+    pub fn validate(&self) -> Result<(), IdentityError> {
+        validate_public_key(&self.public_key)?;
+        validate_wallet_address(&self.wallet_address)?;
+        validate_timestamps(self.creation_timestamp, self.submission_timestamp)?;
+        validate_salt(&self.salt)?;
+        validate_identity_nonce(self.identity_nonce)?;
+        validate_signature(
+            &self.public_key,
+            &self.registration_message(),
+            &self.unique_signature,
+        )
+    }
+
+    /// Identity hash H₃(pk ‖ addr ‖ ts ‖ sig ‖ salt ‖ nonce ‖ ts₂) as hex.
+    pub fn identity_hash(&self) -> String {
+        to_hex(&triple_layer_hash(
+            HashDomain::Identity,
+            &self.canonical_bytes(),
+        ))
+    }
+
+    /// Decodes and validates a canonical identity encoding.
+    pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, IdentityError> {
+        let mut reader = Reader { bytes };
+        let version = reader.u8()?;
+        let kind = reader.u8()?;
+        if version != ENCODING_VERSION || kind != PreimageKind::MinerIdentity as u8 {
+            return Err(IdentityError::UnexpectedHeader { version, kind });
+        }
+        let found = reader.u8()?;
+        if found != IDENTITY_VERSION {
+            return Err(IdentityError::UnsupportedVersion { found });
+        }
+
+        let identity = MinerIdentity {
+            public_key: reader.bytes()?.to_vec(),
+            wallet_address: String::from_utf8(reader.bytes()?.to_vec())
+                .map_err(|_| IdentityError::Malformed)?,
+            creation_timestamp: reader.u64()?,
+            unique_signature: reader.bytes()?.to_vec(),
+            salt: reader.bytes()?.to_vec(),
+            identity_nonce: reader.u64()?,
+            submission_timestamp: reader.u64()?,
+        };
+        if !reader.bytes.is_empty() {
+            return Err(IdentityError::Malformed);
+        }
+        identity.validate()?;
+        Ok(identity)
+    }
+}
+
+impl CanonicalEncode for MinerIdentity {
+    fn canonical_bytes(&self) -> Vec<u8> {
+        PreimageEncoder::new(PreimageKind::MinerIdentity)
+            .u8(IDENTITY_VERSION)
+            .bytes(&self.public_key)
+            .str(&self.wallet_address)
+            .u64(self.creation_timestamp)
+            .bytes(&self.unique_signature)
+            .bytes(&self.salt)
+            .u64(self.identity_nonce)
+            .u64(self.submission_timestamp)
+            .finish()
+    }
+}
+
+// Key material for virtual miners
+fn virtual_bytes(label: &str, id: u32) -> [u8; 32] {
+    let context = format!("I Protocol virtual miner {label}");
+    blake3::derive_key(&context, &id.to_be_bytes())
+}
+
+// Cursor over a canonical encoding
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], IdentityError> {
+        if self.bytes.len() < len {
+            return Err(IdentityError::Malformed);
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, IdentityError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, IdentityError> {
+        let raw = self.take(8)?;
+        Ok(u64::from_be_bytes(raw.try_into().expect("eight bytes")))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], IdentityError> {
+        let raw = self.take(4)?;
+        let len = u32::from_be_bytes(raw.try_into().expect("four bytes"));
+        self.take(len as usize)
+    }
+}
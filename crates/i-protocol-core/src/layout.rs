@@ -0,0 +1,101 @@
+//! Layout of the nonce space.
+//!
+//! The space opens with the segment reserved for the System Miner, followed
+//! by one range of [`NONCES_PER_MINER`] per regular miner in DURA position
+//! order:
+//!
+//! ```text
+//! [1, 10,000]                                     System Miner
+//! [10,001 + i × 250,000, 10,000 + (i + 1) × 250,000]  regular miner at position i
+//! ```
+
+use crate::params::{
+    NONCES_PER_MINER, REGULAR_MINER_RANGE_START, SYSTEM_MINER_RANGE_END, SYSTEM_MINER_RANGE_START,
+};
+
+/// Inclusive nonce range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonceRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl NonceRange {
+    /// Number of nonces in the range.
+    pub fn size(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    pub fn contains(&self, nonce: u64) -> bool {
+        (self.start..=self.end).contains(&nonce)
+    }
+
+    pub fn overlaps(&self, other: &NonceRange) -> bool {
+        self.start.max(other.start) <= self.end.min(other.end)
+    }
+}
+
+/// Segment of the nonce space a nonce belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NonceSegment {
+    System,
+    /// Range of the regular miner at this DURA position.
+    Regular(usize),
+}
+
+/// Nonce space for one block with a given number of regular miners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeLayout {
+    regular_miners: usize,
+}
+
+impl RangeLayout {
+    pub fn new(regular_miners: usize) -> Self {
+        RangeLayout { regular_miners }
+    }
+
+    pub fn regular_miners(&self) -> usize {
+        self.regular_miners
+    }
+
+    /// Segment reserved for the System Miner.
+    pub const fn system_range() -> NonceRange {
+        NonceRange {
+            start: SYSTEM_MINER_RANGE_START,
+            end: SYSTEM_MINER_RANGE_END,
+        }
+    }
+
+    /// Range of the regular miner at `position`, if there is one.
+    pub fn regular_range(&self, position: usize) -> Option<NonceRange> {
+        if position >= self.regular_miners {
+            return None;
+        }
+        let start = REGULAR_MINER_RANGE_START + position as u64 * NONCES_PER_MINER;
+        Some(NonceRange {
+            start,
+            end: start + NONCES_PER_MINER - 1,
+        })
+    }
+
+    /// Span covering every regular miner range, if there are any.
+    pub fn regular_segment(&self) -> Option<NonceRange> {
+        let last = self.regular_range(self.regular_miners.checked_sub(1)?)?;
+        Some(NonceRange {
+            start: REGULAR_MINER_RANGE_START,
+            end: last.end,
+        })
+    }
+
+    /// Segment containing `nonce`, or `None` outside the layout.
+    pub fn segment_of(&self, nonce: u64) -> Option<NonceSegment> {
+        if Self::system_range().contains(nonce) {
+            return Some(NonceSegment::System);
+        }
+        if nonce < REGULAR_MINER_RANGE_START {
+            return None;
+        }
+        let position = ((nonce - REGULAR_MINER_RANGE_START) / NONCES_PER_MINER) as usize;
+        (position < self.regular_miners).then_some(NonceSegment::Regular(position))
+    }
+}
@@ -0,0 +1,38 @@
+//! I Protocol core consensus primitives.
+//!
+//! Shared by the concept tests under `Concept Tests/` so that every
+//! verification run measures the same algorithms a node would execute.
+
+pub mod block;
+pub mod bootstrap;
+pub mod clock;
+pub mod collision;
+pub mod commit_reveal;
+pub mod difficulty;
+pub mod dura;
+pub mod emission;
+pub mod encoding;
+pub mod fallback;
+pub mod fees;
+pub mod fork_choice;
+pub mod hash;
+pub mod identity;
+pub mod layout;
+pub mod lifecycle;
+pub mod mempool;
+pub mod merkle;
+pub mod miner;
+pub mod params;
+pub mod registry;
+pub mod signing;
+pub mod state;
+pub mod system_miner;
+pub mod tno;
+pub mod transaction;
+pub mod validation;
+
+pub use block::{Block, BlockHeader, BlockProducer, Coinbase};
+pub use hash::{triple_layer_hash, Digest32, HashDomain};
+pub use identity::MinerIdentity;
+pub use state::BlockchainState;
+pub use transaction::{Transaction, TransactionKind};
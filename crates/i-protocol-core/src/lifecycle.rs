@@ -0,0 +1,365 @@
+//! Transaction lifecycle from submission to inclusion or expiry.
+//!
+//! A submitted transaction is mapped by TNO once per height at most. When it
+//! loses a final-nonce contest, or its block leaves it out, it waits for the
+//! next height instead of being remapped within the current one: TNO mixes
+//! the height and previous block hash into the digest, so the same signed
+//! transaction lands on a fresh final nonce without a new user nonce or a new
+//! signature. Retries are bounded by the expiry height fixed at submission;
+//! a transaction still outstanding past it expires.
+//!
+//! ```text
+//! Pending ─► Mapped ─► Included
+//!    │         │  ▲
+//!    ▼         ▼  │
+//!    └──► Retrying{h} ─► Expired
+//! ```
+//!
+//! Every transition is published to subscribers, so a wallet backend can
+//! follow its transactions without polling. Settled entries are dropped once
+//! their height is final, which also ends their subscriptions.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::collision::Claim;
+use crate::hash::{to_hex, Digest32};
+
+/// Where a transaction stands on its way into a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatus {
+    /// Submitted and not yet mapped.
+    Pending,
+    /// Holds `final_nonce` at `height`.
+    Mapped {
+        height: u64,
+        final_nonce: u64,
+    },
+    /// Lost its final nonce or was left out; mapped again at `height`.
+    Retrying {
+        height: u64,
+    },
+    Included {
+        height: u64,
+    },
+    /// Not included by its expiry height, which is `height`.
+    Expired {
+        height: u64,
+    },
+}
+
+impl TxStatus {
+    /// Whether the transaction has left the lifecycle.
+    pub fn is_settled(&self) -> bool {
+        matches!(self, TxStatus::Included { .. } | TxStatus::Expired { .. })
+    }
+}
+
+/// Status change published to subscribers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusUpdate {
+    pub tx_hash: Digest32,
+    pub status: TxStatus,
+}
+
+/// Reason a lifecycle event was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LifecycleError {
+    HeightNotIncreasing {
+        last: u64,
+        found: u64,
+    },
+    AlreadyTracked {
+        tx_hash: Digest32,
+    },
+    /// The expiry height lies below the height being built.
+    ExpiryPassed {
+        tx_hash: Digest32,
+        expiry_height: u64,
+    },
+    Untracked {
+        tx_hash: Digest32,
+    },
+    /// A claim for a transaction not due for mapping at this height.
+    NotDue {
+        tx_hash: Digest32,
+        status: TxStatus,
+    },
+    /// Inclusion of a transaction not mapped at this height.
+    NotMapped {
+        tx_hash: Digest32,
+        status: TxStatus,
+    },
+}
+
+impl fmt::Display for LifecycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleError::HeightNotIncreasing { last, found } => {
+                write!(f, "lifecycle height {found} not after {last}")
+            }
+            LifecycleError::AlreadyTracked { tx_hash } => {
+                write!(f, "transaction {} already tracked", to_hex(tx_hash))
+            }
+            LifecycleError::ExpiryPassed {
+                tx_hash,
+                expiry_height,
+            } => write!(
+                f,
+                "transaction {} expired at height {expiry_height}",
+                to_hex(tx_hash)
+            ),
+            LifecycleError::Untracked { tx_hash } => {
+                write!(f, "transaction {} not tracked", to_hex(tx_hash))
+            }
+            LifecycleError::NotDue { tx_hash, status } => write!(
+                f,
+                "transaction {} not due for mapping ({status:?})",
+                to_hex(tx_hash)
+            ),
+            LifecycleError::NotMapped { tx_hash, status } => write!(
+                f,
+                "transaction {} not mapped at this height ({status:?})",
+                to_hex(tx_hash)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LifecycleError {}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    status: TxStatus,
+    expiry_height: u64,
+}
+
+/// Lifecycle of every transaction submitted and not yet final.
+#[derive(Debug)]
+pub struct TxTracker {
+    height: u64,
+    entries: BTreeMap<Digest32, Entry>,
+    subscribers: HashMap<Digest32, Vec<Sender<StatusUpdate>>>,
+    all_subscribers: Vec<Sender<StatusUpdate>>,
+}
+
+impl TxTracker {
+    /// Tracker building the block at `height`.
+    pub fn new(height: u64) -> Self {
+        TxTracker {
+            height,
+            entries: BTreeMap::new(),
+            subscribers: HashMap::new(),
+            all_subscribers: Vec::new(),
+        }
+    }
+
+    /// Height of the block being built.
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    /// Tracks a new transaction, to be included by `expiry_height`.
+    pub fn submit(&mut self, tx_hash: Digest32, expiry_height: u64) -> Result<(), LifecycleError> {
+        if self.entries.contains_key(&tx_hash) {
+            return Err(LifecycleError::AlreadyTracked { tx_hash });
+        }
+        if expiry_height < self.height {
+            return Err(LifecycleError::ExpiryPassed {
+                tx_hash,
+                expiry_height,
+            });
+        }
+        self.entries.insert(
+            tx_hash,
+            Entry {
+                status: TxStatus::Pending,
+                expiry_height,
+            },
+        );
+        self.publish(tx_hash, TxStatus::Pending);
+        Ok(())
+    }
+
+    /// Transactions to map at the current height, in hash order.
+    pub fn due(&self) -> Vec<Digest32> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| self.is_due(entry.status))
+            .map(|(tx_hash, _)| *tx_hash)
+            .collect()
+    }
+
+    /// Records the collision index's answer to `tx_hash` claiming
+    /// `final_nonce` at the current height. A transaction that lost, or
+    /// that the claim displaced while pending or mapped, retries at the next
+    /// height; a displaced transaction already included or expired keeps its
+    /// status.
+    pub fn record_claim(
+        &mut self,
+        tx_hash: Digest32,
+        final_nonce: u64,
+        claim: &Claim,
+    ) -> Result<(), LifecycleError> {
+        let status = self
+            .status(&tx_hash)
+            .ok_or(LifecycleError::Untracked { tx_hash })?;
+        if !self.is_due(status) {
+            return Err(LifecycleError::NotDue { tx_hash, status });
+        }
+
+        let mapped = TxStatus::Mapped {
+            height: self.height,
+            final_nonce,
+        };
+        let retrying = TxStatus::Retrying {
+            height: self.height + 1,
+        };
+        match claim {
+            Claim::Claimed => self.set(tx_hash, mapped),
+            Claim::Collision { .. } => self.set(tx_hash, retrying),
+            Claim::Displaced { loser } => {
+                self.set(tx_hash, mapped);
+                if let Some(TxStatus::Pending | TxStatus::Mapped { .. }) =
+                    self.status(&loser.tx_hash)
+                {
+                    self.set(loser.tx_hash, retrying);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks `tx_hash`, mapped at the current height, as included in its
+    /// block.
+    pub fn include(&mut self, tx_hash: Digest32) -> Result<(), LifecycleError> {
+        let status = self
+            .status(&tx_hash)
+            .ok_or(LifecycleError::Untracked { tx_hash })?;
+        match status {
+            TxStatus::Mapped { height, .. } if height == self.height => {
+                self.set(tx_hash, TxStatus::Included { height });
+                Ok(())
+            }
+            _ => Err(LifecycleError::NotMapped { tx_hash, status }),
+        }
+    }
+
+    /// Moves on to building `height`. Transactions mapped earlier but not
+    /// included retry at `height`; those whose expiry height has passed
+    /// expire.
+    pub fn advance(&mut self, height: u64) -> Result<(), LifecycleError> {
+        if height <= self.height {
+            return Err(LifecycleError::HeightNotIncreasing {
+                last: self.height,
+                found: height,
+            });
+        }
+        self.height = height;
+
+        let changes: Vec<(Digest32, TxStatus)> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| !entry.status.is_settled())
+            .filter_map(|(tx_hash, entry)| {
+                if entry.expiry_height < height {
+                    Some((
+                        *tx_hash,
+                        TxStatus::Expired {
+                            height: entry.expiry_height,
+                        },
+                    ))
+                } else if let TxStatus::Mapped { .. } = entry.status {
+                    Some((*tx_hash, TxStatus::Retrying { height }))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for (tx_hash, status) in changes {
+            self.set(tx_hash, status);
+        }
+        Ok(())
+    }
+
+    /// Drops transactions settled at or below `height`, ending their
+    /// subscriptions.
+    pub fn finalize(&mut self, height: u64) {
+        let subscribers = &mut self.subscribers;
+        self.entries.retain(|tx_hash, entry| {
+            let keep = match entry.status {
+                TxStatus::Included { height: at } | TxStatus::Expired { height: at } => at > height,
+                _ => true,
+            };
+            if !keep {
+                subscribers.remove(tx_hash);
+            }
+            keep
+        });
+    }
+
+    pub fn status(&self, tx_hash: &Digest32) -> Option<TxStatus> {
+        self.entries.get(tx_hash).map(|entry| entry.status)
+    }
+
+    /// Status changes of the tracked `tx_hash`, starting with its current
+    /// status. The stream ends once the transaction is settled and final.
+    pub fn subscribe(
+        &mut self,
+        tx_hash: Digest32,
+    ) -> Result<Receiver<StatusUpdate>, LifecycleError> {
+        let status = self
+            .status(&tx_hash)
+            .ok_or(LifecycleError::Untracked { tx_hash })?;
+        let (sender, receiver) = channel();
+        // The receiver is held above, so the send cannot fail
+        let _ = sender.send(StatusUpdate { tx_hash, status });
+        self.subscribers.entry(tx_hash).or_default().push(sender);
+        Ok(receiver)
+    }
+
+    /// Status changes of every transaction from now on.
+    pub fn subscribe_all(&mut self) -> Receiver<StatusUpdate> {
+        let (sender, receiver) = channel();
+        self.all_subscribers.push(sender);
+        receiver
+    }
+
+    /// Number of transactions tracked.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn is_due(&self, status: TxStatus) -> bool {
+        match status {
+            TxStatus::Pending => true,
+            TxStatus::Retrying { height } => height <= self.height,
+            _ => false,
+        }
+    }
+
+    fn set(&mut self, tx_hash: Digest32, status: TxStatus) {
+        if let Some(entry) = self.entries.get_mut(&tx_hash) {
+            entry.status = status;
+        }
+        self.publish(tx_hash, status);
+    }
+
+    // Subscribers whose receiver was dropped are forgotten
+    fn publish(&mut self, tx_hash: Digest32, status: TxStatus) {
+        let update = StatusUpdate { tx_hash, status };
+        if let Some(senders) = self.subscribers.get_mut(&tx_hash) {
+            senders.retain(|sender| sender.send(update).is_ok());
+            if senders.is_empty() {
+                self.subscribers.remove(&tx_hash);
+            }
+        }
+        self.all_subscribers
+            .retain(|sender| sender.send(update).is_ok());
+    }
+}
@@ -0,0 +1,90 @@
+//! Pending transactions and the Deterministic Mempool Ordering Function.
+//!
+//! A SysBlock has no miner to choose its transactions, so every node must
+//! derive the same set in the same order from its own mempool. DMOF includes
+//! only transactions stamped at or before the fallback decision point
+//! t₀ + τ and orders them by
+//! `Blake3(tx.signature ‖ tx.timestamp ‖ tx.nonce)`, with the fields
+//! canonically encoded. Equal keys fall back to the transaction hash.
+
+use std::collections::BTreeMap;
+
+use crate::encoding::{PreimageEncoder, PreimageKind};
+use crate::hash::Digest32;
+use crate::params::REGULAR_MINING_WINDOW_MS;
+use crate::transaction::Transaction;
+
+/// DMOF sort key of `tx`.
+pub fn dmof_key(tx: &Transaction) -> Digest32 {
+    let preimage = PreimageEncoder::new(PreimageKind::MempoolOrder)
+        .bytes(&tx.signature)
+        .u64(tx.timestamp)
+        .u64(tx.user_nonce)
+        .finish();
+    *blake3::hash(&preimage).as_bytes()
+}
+
+/// Latest transaction timestamp a SysBlock for a round starting at `t0` may
+/// include: t₀ + τ.
+pub fn sysblock_cutoff(t0: u64) -> u64 {
+    t0 + REGULAR_MINING_WINDOW_MS
+}
+
+/// Transactions waiting for a block, keyed by hash.
+///
+/// Arrival order is not recorded, so two nodes holding the same transactions
+/// hold the same mempool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mempool {
+    transactions: BTreeMap<Digest32, Transaction>,
+}
+
+impl Mempool {
+    pub fn new() -> Self {
+        Mempool::default()
+    }
+
+    /// Adds `tx`. Returns `false` if a transaction with the same hash is
+    /// already pending.
+    pub fn insert(&mut self, tx: Transaction) -> bool {
+        let hash = tx.hash();
+        if self.transactions.contains_key(&hash) {
+            return false;
+        }
+        self.transactions.insert(hash, tx);
+        true
+    }
+
+    pub fn remove(&mut self, hash: &Digest32) -> Option<Transaction> {
+        self.transactions.remove(hash)
+    }
+
+    pub fn contains(&self, hash: &Digest32) -> bool {
+        self.transactions.contains_key(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions.values()
+    }
+
+    /// DMOF: the transactions a SysBlock includes for `cutoff`, in canonical
+    /// order.
+    pub fn canonical_sysblock_set(&self, cutoff: u64) -> Vec<Transaction> {
+        let mut selected: Vec<(Digest32, &Digest32, &Transaction)> = self
+            .transactions
+            .iter()
+            .filter(|(_, tx)| tx.timestamp <= cutoff)
+            .map(|(hash, tx)| (dmof_key(tx), hash, tx))
+            .collect();
+        selected.sort_unstable_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+        selected.into_iter().map(|(_, _, tx)| tx.clone()).collect()
+    }
+}
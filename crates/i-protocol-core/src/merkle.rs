@@ -0,0 +1,250 @@
+//! Binary Merkle trees over 32-byte leaves.
+//!
+//! Leaves and interior nodes are hashed with H₃ in the [`HashDomain::Merkle`]
+//! domain behind distinct prefix bytes, so a leaf can never be passed off as
+//! an interior node. An odd node at the end of a level is promoted unchanged
+//! rather than paired with itself.
+//!
+//! A [`MerkleProof`] carries the sibling hashes from a leaf up to the root.
+//! Its compact encoding is
+//!
+//! ```text
+//! proof = leaf_count:u32 ‖ leaf_index:u32 ‖ sibling₁ ‖ sibling₂ ‖ …
+//! ```
+//!
+//! with big-endian integers and 32-byte siblings, leaf first. The sibling
+//! count is implied by the leaf count and index, since promoted nodes have
+//! no sibling at their level.
+
+use std::fmt;
+
+use crate::hash::{triple_layer_hash, Digest32, HashDomain};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Root of an empty tree.
+pub const EMPTY_ROOT: Digest32 = [0u8; 32];
+
+/// Hash of a leaf value.
+pub fn leaf_hash(leaf: &Digest32) -> Digest32 {
+    let mut input = [0u8; 33];
+    input[0] = LEAF_PREFIX;
+    input[1..].copy_from_slice(leaf);
+    triple_layer_hash(HashDomain::Merkle, &input)
+}
+
+/// Hash of an interior node over its two children.
+pub fn node_hash(left: &Digest32, right: &Digest32) -> Digest32 {
+    let mut input = [0u8; 65];
+    input[0] = NODE_PREFIX;
+    input[1..33].copy_from_slice(left);
+    input[33..].copy_from_slice(right);
+    triple_layer_hash(HashDomain::Merkle, &input)
+}
+
+/// Merkle root over `leaves` in order.
+pub fn merkle_root(leaves: &[Digest32]) -> Digest32 {
+    if leaves.is_empty() {
+        return EMPTY_ROOT;
+    }
+
+    let mut level: Vec<Digest32> = leaves.iter().map(leaf_hash).collect();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+// Parents of `level`, with an odd last node promoted unchanged
+fn next_level(level: &[Digest32]) -> Vec<Digest32> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [odd] => *odd,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Tree with every level kept, for producing inclusion proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// Leaf hashes first, root last.
+    levels: Vec<Vec<Digest32>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: &[Digest32]) -> Self {
+        let mut levels = vec![leaves.iter().map(leaf_hash).collect::<Vec<_>>()];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            levels.push(next_level(level));
+        }
+        MerkleTree { levels }
+    }
+
+    /// Same value as [`merkle_root`] over the leaves.
+    pub fn root(&self) -> Digest32 {
+        match self.levels.last().map(Vec::as_slice) {
+            Some([root]) => *root,
+            _ => EMPTY_ROOT,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inclusion proof for the leaf at `index`.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.len() {
+            return None;
+        }
+        let leaf_count = u32::try_from(self.len()).expect("tree exceeds u32::MAX leaves");
+
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+            position /= 2;
+        }
+        Some(MerkleProof {
+            leaf_count,
+            leaf_index: index as u32,
+            siblings,
+        })
+    }
+}
+
+/// Reason a compact proof could not be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleProofError {
+    IndexOutOfRange {
+        index: u32,
+        count: u32,
+    },
+    /// Encoded length does not match the siblings the position requires.
+    Length {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for MerkleProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MerkleProofError::IndexOutOfRange { index, count } => {
+                write!(f, "leaf {index} out of range for {count} leaves")
+            }
+            MerkleProofError::Length { expected, found } => {
+                write!(f, "proof of {found} bytes, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MerkleProofError {}
+
+/// Inclusion proof of one leaf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub leaf_count: u32,
+    pub leaf_index: u32,
+    /// Sibling hashes from the leaf level upwards.
+    pub siblings: Vec<Digest32>,
+}
+
+impl MerkleProof {
+    /// Root the proof leads to from `leaf`, or `None` if the siblings do not
+    /// fit the leaf's position.
+    pub fn compute_root(&self, leaf: &Digest32) -> Option<Digest32> {
+        if self.leaf_index >= self.leaf_count
+            || self.siblings.len() != sibling_count(self.leaf_index, self.leaf_count)
+        {
+            return None;
+        }
+
+        let mut hash = leaf_hash(leaf);
+        let mut siblings = self.siblings.iter();
+        let mut position = self.leaf_index;
+        let mut width = self.leaf_count;
+        while width > 1 {
+            if position % 2 == 1 {
+                hash = node_hash(siblings.next()?, &hash);
+            } else if position + 1 < width {
+                hash = node_hash(&hash, siblings.next()?);
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        Some(hash)
+    }
+
+    /// Whether `leaf` is included under `root`.
+    pub fn verify(&self, root: &Digest32, leaf: &Digest32) -> bool {
+        self.compute_root(leaf).as_ref() == Some(root)
+    }
+
+    /// Compact encoding; see the module documentation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + 32 * self.siblings.len());
+        bytes.extend_from_slice(&self.leaf_count.to_be_bytes());
+        bytes.extend_from_slice(&self.leaf_index.to_be_bytes());
+        for sibling in &self.siblings {
+            bytes.extend_from_slice(sibling);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleProofError> {
+        let length_error = |expected| MerkleProofError::Length {
+            expected,
+            found: bytes.len(),
+        };
+        if bytes.len() < 8 {
+            return Err(length_error(8));
+        }
+        let leaf_count = u32::from_be_bytes(bytes[..4].try_into().expect("4 bytes"));
+        let leaf_index = u32::from_be_bytes(bytes[4..8].try_into().expect("4 bytes"));
+        if leaf_index >= leaf_count {
+            return Err(MerkleProofError::IndexOutOfRange {
+                index: leaf_index,
+                count: leaf_count,
+            });
+        }
+
+        let expected = 8 + 32 * sibling_count(leaf_index, leaf_count);
+        if bytes.len() != expected {
+            return Err(length_error(expected));
+        }
+        let siblings = bytes[8..]
+            .chunks_exact(32)
+            .map(|chunk| chunk.try_into().expect("32 bytes"))
+            .collect();
+        Ok(MerkleProof {
+            leaf_count,
+            leaf_index,
+            siblings,
+        })
+    }
+}
+
+// Levels at which the node on the path from `index` has a sibling
+fn sibling_count(mut index: u32, mut width: u32) -> usize {
+    let mut count = 0;
+    while width > 1 {
+        if index % 2 == 1 || index + 1 < width {
+            count += 1;
+        }
+        index /= 2;
+        width = width.div_ceil(2);
+    }
+    count
+}
@@ -0,0 +1,223 @@
+//! Proof-of-work search over an assigned DURA range.
+//!
+//! A regular miner writes its identity and each nonce of its range into a
+//! header template and stops at the first header hash at or below the
+//! target. Hashes and targets compare as 256-bit big-endian integers.
+
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::block::{BlockHeader, BlockProducer};
+use crate::dura::MinerRange;
+use crate::encoding::CanonicalEncode;
+use crate::hash::{from_hex, triple_layer_hash, Digest32, HashDomain};
+
+// Bytes following the nonce in the canonical header encoding: the two u128
+// coinbase amounts
+const COINBASE_LEN: usize = 32;
+
+/// `hash ≤ target`.
+pub fn meets_target(hash: &Digest32, target: &Digest32) -> bool {
+    hash <= target
+}
+
+/// How a search ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningOutcome {
+    /// Sealed header whose hash meets the target.
+    Found(BlockHeader),
+    /// Every nonce in the range was tried.
+    Exhausted,
+    /// Stopped through the cancel flag.
+    Cancelled,
+}
+
+/// Result of a search with its throughput.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MiningReport {
+    pub outcome: MiningOutcome,
+    /// Header hashes computed.
+    pub hashes: u64,
+    pub elapsed: Duration,
+}
+
+impl MiningReport {
+    pub fn header(&self) -> Option<&BlockHeader> {
+        match &self.outcome {
+            MiningOutcome::Found(header) => Some(header),
+            MiningOutcome::Exhausted | MiningOutcome::Cancelled => None,
+        }
+    }
+
+    pub fn hashes_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.hashes as f64 / secs
+    }
+}
+
+/// Searches `range` in ascending order for a header meeting `target`.
+///
+/// The template's producer is replaced by the range owner; every other
+/// field is kept. `cancel` is checked before each hash.
+///
+/// # Panics
+///
+/// Panics if `range.identity_hash` is not a 64-character hex digest.
+pub fn mine_range(
+    template: &BlockHeader,
+    range: &MinerRange,
+    target: &Digest32,
+    cancel: &AtomicBool,
+) -> MiningReport {
+    let range_owner = from_hex(&range.identity_hash).expect("DURA identity hashes are hex");
+    search(
+        template,
+        range_owner,
+        range.start_nonce..=range.end_nonce,
+        target,
+        || cancel.load(Ordering::Relaxed),
+    )
+}
+
+/// Result of [`mine_range_parallel`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParallelReport {
+    pub outcome: MiningOutcome,
+    /// One report per worker, in nonce order of their slices.
+    pub workers: Vec<MiningReport>,
+    pub elapsed: Duration,
+}
+
+impl ParallelReport {
+    pub fn header(&self) -> Option<&BlockHeader> {
+        match &self.outcome {
+            MiningOutcome::Found(header) => Some(header),
+            MiningOutcome::Exhausted | MiningOutcome::Cancelled => None,
+        }
+    }
+
+    /// Header hashes computed by all workers.
+    pub fn hashes(&self) -> u64 {
+        self.workers.iter().map(|worker| worker.hashes).sum()
+    }
+
+    pub fn hashes_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.hashes() as f64 / secs
+    }
+}
+
+/// [`mine_range`] with the range split into `workers` contiguous slices,
+/// each searched on its own thread.
+///
+/// The first worker to find a header stops the others. When several find
+/// one before stopping, the lowest nonce is returned. `workers` is clamped to
+/// at least one and at most the range size.
+///
+/// # Panics
+///
+/// Panics if `range.identity_hash` is not a 64-character hex digest.
+pub fn mine_range_parallel(
+    template: &BlockHeader,
+    range: &MinerRange,
+    target: &Digest32,
+    cancel: &AtomicBool,
+    workers: usize,
+) -> ParallelReport {
+    let started = Instant::now();
+    let range_owner = from_hex(&range.identity_hash).expect("DURA identity hashes are hex");
+    let size = range.end_nonce - range.start_nonce + 1;
+    let workers = (workers as u64).clamp(1, size);
+    let slice = size.div_ceil(workers);
+    let found = AtomicBool::new(false);
+
+    let reports: Vec<MiningReport> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                let start = range.start_nonce + worker * slice;
+                let end = (start + slice - 1).min(range.end_nonce);
+                let found = &found;
+                scope.spawn(move || {
+                    let report = search(template, range_owner, start..=end, target, || {
+                        found.load(Ordering::Relaxed) || cancel.load(Ordering::Relaxed)
+                    });
+                    if report.header().is_some() {
+                        found.store(true, Ordering::Relaxed);
+                    }
+                    report
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("mining worker panicked"))
+            .collect()
+    });
+
+    let outcome = match reports.iter().find_map(MiningReport::header) {
+        Some(header) => MiningOutcome::Found(*header),
+        None if reports
+            .iter()
+            .all(|r| r.outcome == MiningOutcome::Exhausted) =>
+        {
+            MiningOutcome::Exhausted
+        }
+        None => MiningOutcome::Cancelled,
+    };
+    ParallelReport {
+        outcome,
+        workers: reports,
+        elapsed: started.elapsed(),
+    }
+}
+
+fn search(
+    template: &BlockHeader,
+    range_owner: Digest32,
+    nonces: RangeInclusive<u64>,
+    target: &Digest32,
+    stop: impl Fn() -> bool,
+) -> MiningReport {
+    let started = Instant::now();
+    let mut header = *template;
+    header.producer = BlockProducer::RegularMiner {
+        range_owner,
+        nonce: *nonces.start(),
+    };
+
+    // Only the nonce changes between attempts, so the encoding is built once
+    // and patched in place
+    let mut preimage = header.canonical_bytes();
+    let nonce_at = preimage.len() - COINBASE_LEN - 8;
+
+    let mut hashes = 0;
+    let mut outcome = MiningOutcome::Exhausted;
+    for nonce in nonces {
+        if stop() {
+            outcome = MiningOutcome::Cancelled;
+            break;
+        }
+        preimage[nonce_at..nonce_at + 8].copy_from_slice(&nonce.to_be_bytes());
+        let hash = triple_layer_hash(HashDomain::BlockHeader, &preimage);
+        hashes += 1;
+        if meets_target(&hash, target) {
+            header.producer = BlockProducer::RegularMiner { range_owner, nonce };
+            outcome = MiningOutcome::Found(header);
+            break;
+        }
+    }
+
+    MiningReport {
+        outcome,
+        hashes,
+        elapsed: started.elapsed(),
+    }
+}
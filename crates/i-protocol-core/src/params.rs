@@ -0,0 +1,61 @@
+//! Protocol parameters shared by every consensus component.
+//!
+//! Values follow the consensus specification (Final/1). Nonce ranges are
+//! inclusive on both ends.
+
+/// First nonce reserved for the System Miner.
+pub const SYSTEM_MINER_RANGE_START: u64 = 1;
+
+/// Last nonce reserved for the System Miner.
+pub const SYSTEM_MINER_RANGE_END: u64 = 10_000;
+
+/// First nonce available to regular miners.
+pub const REGULAR_MINER_RANGE_START: u64 = 10_001;
+
+/// Width of the nonce range DURA assigns to each regular miner.
+pub const NONCES_PER_MINER: u64 = 250_000;
+
+/// Protocol salt appended to the System Miner nonce preimage.
+pub const SYSTEM_MINER_SALT: &str = "I_PROTOCOL_SYSTEM_MINER_SALT_2024";
+
+/// Target block interval in milliseconds.
+pub const BLOCK_TIME_MS: u64 = 500;
+
+/// Window τ in which regular miners may submit before the System Miner
+/// activates, in milliseconds.
+pub const REGULAR_MINING_WINDOW_MS: u64 = 250;
+
+/// Share of heights, in percent, that difficulty adjustment aims to leave to
+/// the System Miner. Regular miners produce the remaining 91%.
+pub const TARGET_SYSBLOCK_RATE_PERCENT: u64 = 9;
+
+/// Blocks per difficulty epoch.
+pub const RETARGET_INTERVAL: u64 = 1_000;
+
+/// Largest factor by which one retarget may raise or lower difficulty.
+pub const MAX_RETARGET_FACTOR: u64 = 4;
+
+/// Active miners the network must reach to leave the bootstrap phase.
+pub const BOOTSTRAP_MIN_MINERS: u32 = 100;
+
+/// Consecutive blocks [`BOOTSTRAP_MIN_MINERS`] must stay active before the
+/// bootstrap phase ends.
+pub const BOOTSTRAP_SUSTAIN_BLOCKS: u64 = 1_000;
+
+/// Blocks between a registry change being included and it taking effect.
+/// A join or leave in the block at height `h` changes the active set from
+/// `h + REGISTRY_ACTIVATION_DELAY`.
+pub const REGISTRY_ACTIVATION_DELAY: u64 = 10;
+
+/// Blocks in the commit phase of a DURA identity round.
+pub const COMMIT_PHASE_BLOCKS: u64 = 20;
+
+/// Blocks in the reveal phase that follows the commit phase.
+pub const REVEAL_PHASE_BLOCKS: u64 = 20;
+
+/// Rounds a miner that committed without revealing may not commit in.
+pub const NON_REVEAL_EXCLUSION_ROUNDS: u64 = 4;
+
+/// Blocks after submission within which a transaction must be included.
+/// A transaction losing every final-nonce contest until then expires.
+pub const TX_EXPIRY_BLOCKS: u64 = 64;
@@ -0,0 +1,301 @@
+//! On-chain registry of miner identities.
+//!
+//! Miners enter and exit DURA through join and leave changes carried in
+//! blocks. A change included at height `h` takes effect at
+//! `h + REGISTRY_ACTIVATION_DELAY`, so the set a block is mined against is
+//! fixed well before any node needs it. The registry keeps a snapshot of the
+//! active set for every height where it changes; snapshots list miners in
+//! identity-hash order, so every node feeds the identical list into the
+//! master seed however it learned of the changes.
+//!
+//! A leave must be signed by the departing identity's key, so only a miner
+//! can retire itself. Identities are never reused, so a leave signature
+//! cannot be replayed against a later registration.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::dura::{assign_ranges, DuraAssignment};
+use crate::encoding::{PreimageEncoder, PreimageKind};
+use crate::identity::{IdentityError, MinerIdentity};
+use crate::params::REGISTRY_ACTIVATION_DELAY;
+use crate::signing::{self, SignatureError, Signer};
+
+/// Registry change carried in a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryChange {
+    Join(MinerIdentity),
+    /// Departure of the miner registered under `identity_hash`, signed over
+    /// [`leave_message`] with its identity key.
+    Leave {
+        identity_hash: String,
+        signature: Vec<u8>,
+    },
+}
+
+impl RegistryChange {
+    /// Leave for `identity`, signed by `signer`.
+    pub fn leave<S: Signer + ?Sized>(identity: &MinerIdentity, signer: &S) -> Self {
+        let identity_hash = identity.identity_hash();
+        RegistryChange::Leave {
+            signature: signer.sign(&leave_message(&identity_hash)),
+            identity_hash,
+        }
+    }
+}
+
+/// Message a leave signature covers.
+pub fn leave_message(identity_hash: &str) -> Vec<u8> {
+    PreimageEncoder::new(PreimageKind::MinerDeregistration)
+        .str(identity_hash)
+        .finish()
+}
+
+/// Reason a block's registry changes were rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    HeightNotIncreasing {
+        last: u64,
+        found: u64,
+    },
+    /// A joining identity failed a component validator.
+    InvalidIdentity {
+        identity_hash: String,
+        error: IdentityError,
+    },
+    /// The identity joined before; identities are never reused.
+    AlreadyRegistered {
+        identity_hash: String,
+    },
+    NotRegistered {
+        identity_hash: String,
+    },
+    AlreadyLeft {
+        identity_hash: String,
+    },
+    /// A leave not signed by the departing identity's key.
+    InvalidLeaveSignature {
+        identity_hash: String,
+        error: SignatureError,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::HeightNotIncreasing { last, found } => {
+                write!(f, "registry block {found} not after {last}")
+            }
+            RegistryError::InvalidIdentity {
+                identity_hash,
+                error,
+            } => write!(f, "miner {identity_hash} has an invalid identity: {error}"),
+            RegistryError::AlreadyRegistered { identity_hash } => {
+                write!(f, "miner {identity_hash} already registered")
+            }
+            RegistryError::NotRegistered { identity_hash } => {
+                write!(f, "miner {identity_hash} not registered")
+            }
+            RegistryError::AlreadyLeft { identity_hash } => {
+                write!(f, "miner {identity_hash} already left")
+            }
+            RegistryError::InvalidLeaveSignature {
+                identity_hash,
+                error,
+            } => write!(f, "leave for miner {identity_hash}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Active miner set at one height.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistrySnapshot {
+    pub height: u64,
+    /// Active miners in ascending identity-hash order.
+    pub miners: Vec<MinerIdentity>,
+}
+
+impl RegistrySnapshot {
+    pub fn identity_hashes(&self) -> Vec<String> {
+        self.miners
+            .iter()
+            .map(MinerIdentity::identity_hash)
+            .collect()
+    }
+
+    /// Public-seed DURA over this set for the block following
+    /// `previous_hash`. Once a commit–reveal round has closed, ranges come
+    /// from [`RoundOutcome::assign_ranges`](crate::commit_reveal::RoundOutcome::assign_ranges)
+    /// instead.
+    pub fn assign_ranges(&self, previous_hash: &str) -> DuraAssignment {
+        assign_ranges(previous_hash, &self.miners)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    identity: MinerIdentity,
+    left: bool,
+}
+
+/// Miner identities and the active set they produce at each height.
+#[derive(Debug, Clone, Default)]
+pub struct MinerRegistry {
+    /// Every identity ever registered, by identity hash.
+    entries: BTreeMap<String, Entry>,
+    /// Active set from each height at which it changed, keyed by hash.
+    active: BTreeMap<u64, BTreeMap<String, MinerIdentity>>,
+    last_height: Option<u64>,
+}
+
+impl MinerRegistry {
+    pub fn new() -> Self {
+        MinerRegistry::default()
+    }
+
+    /// Miners active from the first block, validated as joins are.
+    pub fn with_genesis(
+        miners: impl IntoIterator<Item = MinerIdentity>,
+    ) -> Result<Self, RegistryError> {
+        let mut entries = BTreeMap::new();
+        let mut active = BTreeMap::new();
+        for identity in miners {
+            register(&mut entries, &mut active, &identity)?;
+        }
+        Ok(MinerRegistry {
+            entries,
+            active: BTreeMap::from([(0, active)]),
+            last_height: None,
+        })
+    }
+
+    /// Applies the registry changes of the block at `height`, in block
+    /// order. Either every change applies or none does.
+    pub fn apply_block(
+        &mut self,
+        height: u64,
+        changes: &[RegistryChange],
+    ) -> Result<(), RegistryError> {
+        if let Some(last) = self.last_height {
+            if height <= last {
+                return Err(RegistryError::HeightNotIncreasing {
+                    last,
+                    found: height,
+                });
+            }
+        }
+
+        let mut entries = self.entries.clone();
+        let mut active = self.latest_active();
+        for change in changes {
+            match change {
+                RegistryChange::Join(identity) => {
+                    register(&mut entries, &mut active, identity)?;
+                }
+                RegistryChange::Leave {
+                    identity_hash,
+                    signature,
+                } => {
+                    let entry = entries.get_mut(identity_hash).ok_or_else(|| {
+                        RegistryError::NotRegistered {
+                            identity_hash: identity_hash.clone(),
+                        }
+                    })?;
+                    if entry.left {
+                        return Err(RegistryError::AlreadyLeft {
+                            identity_hash: identity_hash.clone(),
+                        });
+                    }
+                    let message = leave_message(identity_hash);
+                    signing::verify(&entry.identity.public_key, &message, signature).map_err(
+                        |error| RegistryError::InvalidLeaveSignature {
+                            identity_hash: identity_hash.clone(),
+                            error,
+                        },
+                    )?;
+                    entry.left = true;
+                    active.remove(identity_hash);
+                }
+            }
+        }
+
+        self.entries = entries;
+        self.last_height = Some(height);
+        if !changes.is_empty() {
+            self.active
+                .insert(height + REGISTRY_ACTIVATION_DELAY, active);
+        }
+        Ok(())
+    }
+
+    /// Active set at `height`.
+    pub fn snapshot(&self, height: u64) -> RegistrySnapshot {
+        let miners = self
+            .active
+            .range(..=height)
+            .next_back()
+            .map(|(_, active)| active.values().cloned().collect())
+            .unwrap_or_default();
+        RegistrySnapshot { height, miners }
+    }
+
+    /// Size of the active set from each height at which it changed, in
+    /// height order.
+    pub fn active_counts(&self) -> impl Iterator<Item = (u64, u32)> + '_ {
+        self.active
+            .iter()
+            .map(|(&height, active)| (height, active.len() as u32))
+    }
+
+    /// Whether `identity_hash` is in the active set at `height`.
+    pub fn is_active(&self, identity_hash: &str, height: u64) -> bool {
+        self.active
+            .range(..=height)
+            .next_back()
+            .is_some_and(|(_, active)| active.contains_key(identity_hash))
+    }
+
+    pub fn is_registered(&self, identity_hash: &str) -> bool {
+        self.entries.contains_key(identity_hash)
+    }
+
+    /// Identity registered under `identity_hash`, active or not.
+    pub fn identity(&self, identity_hash: &str) -> Option<&MinerIdentity> {
+        self.entries.get(identity_hash).map(|entry| &entry.identity)
+    }
+
+    // Active set after every change applied so far
+    fn latest_active(&self) -> BTreeMap<String, MinerIdentity> {
+        self.active
+            .last_key_value()
+            .map(|(_, active)| active.clone())
+            .unwrap_or_default()
+    }
+}
+
+// Adds a new, valid identity to the registry and the active set
+fn register(
+    entries: &mut BTreeMap<String, Entry>,
+    active: &mut BTreeMap<String, MinerIdentity>,
+    identity: &MinerIdentity,
+) -> Result<(), RegistryError> {
+    let identity_hash = identity.identity_hash();
+    if let Err(error) = identity.validate() {
+        return Err(RegistryError::InvalidIdentity {
+            identity_hash,
+            error,
+        });
+    }
+    if entries.contains_key(&identity_hash) {
+        return Err(RegistryError::AlreadyRegistered { identity_hash });
+    }
+    let entry = Entry {
+        identity: identity.clone(),
+        left: false,
+    };
+    entries.insert(identity_hash.clone(), entry);
+    active.insert(identity_hash, identity.clone());
+    Ok(())
+}
@@ -0,0 +1,239 @@
+//! Signatures for identity registration and transaction authorization.
+//!
+//! A [`Signer`] holds a private key and signs messages; a [`Verifier`] checks
+//! signatures against a public key. Ed25519 is always available. ML-DSA-65
+//! (FIPS 204, the standardised CRYSTALS-Dilithium) is built with the `ml-dsa`
+//! feature, which compiles the vendored mldsa-native C implementation.
+//!
+//! Identities and transactions carry raw key and signature bytes. The scheme
+//! is recognised from the public key length, so [`verify`] needs no tag.
+
+use std::fmt;
+
+use ed25519_dalek::Signer as _;
+
+/// Supported signature schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureScheme {
+    Ed25519,
+    MlDsa65,
+}
+
+impl SignatureScheme {
+    pub const ALL: [SignatureScheme; 2] = [SignatureScheme::Ed25519, SignatureScheme::MlDsa65];
+
+    pub const fn public_key_len(self) -> usize {
+        match self {
+            SignatureScheme::Ed25519 => 32,
+            SignatureScheme::MlDsa65 => 1952,
+        }
+    }
+
+    pub const fn signature_len(self) -> usize {
+        match self {
+            SignatureScheme::Ed25519 => 64,
+            SignatureScheme::MlDsa65 => 3309,
+        }
+    }
+
+    /// Scheme whose public keys have `public_key`'s length.
+    pub fn from_public_key(public_key: &[u8]) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.public_key_len() == public_key.len())
+    }
+
+    /// Whether this build can verify the scheme.
+    pub const fn is_enabled(self) -> bool {
+        match self {
+            SignatureScheme::Ed25519 => true,
+            SignatureScheme::MlDsa65 => cfg!(feature = "ml-dsa"),
+        }
+    }
+}
+
+/// Reason a signature was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    /// Public key length matches no scheme.
+    UnknownScheme { key_len: usize },
+    /// The scheme's backend is not compiled in.
+    SchemeDisabled(SignatureScheme),
+    /// Not a valid public key of its scheme.
+    MalformedKey,
+    /// The signature does not verify.
+    Invalid,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::UnknownScheme { key_len } => {
+                write!(
+                    f,
+                    "public key of {key_len} bytes matches no signature scheme"
+                )
+            }
+            SignatureError::SchemeDisabled(scheme) => {
+                write!(f, "{scheme:?} signatures not enabled in this build")
+            }
+            SignatureError::MalformedKey => write!(f, "malformed public key"),
+            SignatureError::Invalid => write!(f, "invalid signature"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+/// Private key that signs protocol messages.
+pub trait Signer {
+    fn scheme(&self) -> SignatureScheme;
+
+    fn public_key(&self) -> Vec<u8>;
+
+    fn sign(&self, message: &[u8]) -> Vec<u8>;
+}
+
+/// Public key that checks signatures.
+pub trait Verifier {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SignatureError>;
+}
+
+/// Ed25519 (RFC 8032) private key.
+pub struct Ed25519Signer {
+    key: ed25519_dalek::SigningKey,
+}
+
+impl Ed25519Signer {
+    /// Key from its 32-byte RFC 8032 secret.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        Ed25519Signer {
+            key: ed25519_dalek::SigningKey::from_bytes(seed),
+        }
+    }
+}
+
+impl Signer for Ed25519Signer {
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Ed25519
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.key.verifying_key().to_bytes().to_vec()
+    }
+
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.key.sign(message).to_bytes().to_vec()
+    }
+}
+
+/// Ed25519 public key.
+pub struct Ed25519Verifier {
+    key: ed25519_dalek::VerifyingKey,
+}
+
+impl Ed25519Verifier {
+    pub fn from_bytes(public_key: &[u8]) -> Result<Self, SignatureError> {
+        let bytes: &[u8; 32] = public_key
+            .try_into()
+            .map_err(|_| SignatureError::MalformedKey)?;
+        let key = ed25519_dalek::VerifyingKey::from_bytes(bytes)
+            .map_err(|_| SignatureError::MalformedKey)?;
+        Ok(Ed25519Verifier { key })
+    }
+}
+
+impl Verifier for Ed25519Verifier {
+    /// Strict verification: non-canonical signatures and small-order keys are
+    /// rejected, so a signature has exactly one valid encoding.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SignatureError> {
+        let signature =
+            ed25519_dalek::Signature::from_slice(signature).map_err(|_| SignatureError::Invalid)?;
+        self.key
+            .verify_strict(message, &signature)
+            .map_err(|_| SignatureError::Invalid)
+    }
+}
+
+/// ML-DSA-65 private key.
+#[cfg(feature = "ml-dsa")]
+pub struct MlDsaSigner {
+    key: mysten_mldsa_native_rs::SigningKey,
+    public_key: mysten_mldsa_native_rs::VerifyingKey,
+}
+
+#[cfg(feature = "ml-dsa")]
+impl MlDsaSigner {
+    /// Key pair expanded from its 32-byte FIPS 204 seed.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let (key, public_key) = mysten_mldsa_native_rs::SigningKeySeed::from(*seed).expand();
+        MlDsaSigner { key, public_key }
+    }
+}
+
+#[cfg(feature = "ml-dsa")]
+impl Signer for MlDsaSigner {
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::MlDsa65
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.public_key.as_bytes().to_vec()
+    }
+
+    /// FIPS 204 deterministic variant with an empty context string.
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let rnd = [0; mysten_mldsa_native_rs::RND_LENGTH];
+        self.key
+            .sign(message, &[], &rnd)
+            .expect("empty context is within bounds")
+            .as_bytes()
+            .to_vec()
+    }
+}
+
+/// ML-DSA-65 public key.
+#[cfg(feature = "ml-dsa")]
+pub struct MlDsaVerifier {
+    key: mysten_mldsa_native_rs::VerifyingKey,
+}
+
+#[cfg(feature = "ml-dsa")]
+impl MlDsaVerifier {
+    pub fn from_bytes(public_key: &[u8]) -> Result<Self, SignatureError> {
+        let key = mysten_mldsa_native_rs::VerifyingKey::from_bytes(public_key)
+            .map_err(|_| SignatureError::MalformedKey)?;
+        Ok(MlDsaVerifier { key })
+    }
+}
+
+#[cfg(feature = "ml-dsa")]
+impl Verifier for MlDsaVerifier {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SignatureError> {
+        let signature = mysten_mldsa_native_rs::Signature::from_bytes(signature)
+            .map_err(|_| SignatureError::Invalid)?;
+        self.key
+            .verify(message, &[], &signature)
+            .map_err(|_| SignatureError::Invalid)
+    }
+}
+
+/// Verifier for `public_key`, with the scheme read from its length.
+pub fn verifier(public_key: &[u8]) -> Result<Box<dyn Verifier>, SignatureError> {
+    let scheme =
+        SignatureScheme::from_public_key(public_key).ok_or(SignatureError::UnknownScheme {
+            key_len: public_key.len(),
+        })?;
+    match scheme {
+        SignatureScheme::Ed25519 => Ok(Box::new(Ed25519Verifier::from_bytes(public_key)?)),
+        #[cfg(feature = "ml-dsa")]
+        SignatureScheme::MlDsa65 => Ok(Box::new(MlDsaVerifier::from_bytes(public_key)?)),
+        #[cfg(not(feature = "ml-dsa"))]
+        SignatureScheme::MlDsa65 => Err(SignatureError::SchemeDisabled(scheme)),
+    }
+}
+
+/// Checks `signature` over `message` under `public_key`.
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), SignatureError> {
+    verifier(public_key)?.verify(message, signature)
+}
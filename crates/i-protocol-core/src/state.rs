@@ -0,0 +1,38 @@
+//! Chain state consumed by the nonce assignment algorithms.
+
+use crate::encoding::{CanonicalEncode, PreimageEncoder, PreimageKind};
+use crate::params::NONCES_PER_MINER;
+
+/// Snapshot of the chain tip that DURA, TNO and the System Miner read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockchainState {
+    /// Height of the block being produced.
+    pub height: u64,
+    /// Hash of the previous block.
+    pub previous_hash: String,
+    /// Block timestamp.
+    pub timestamp: u64,
+    /// Number of regular miners that failed to produce the previous block.
+    pub failed_miner_count: u32,
+    /// Number of regular miners holding a DURA range.
+    pub active_miners: u32,
+}
+
+impl BlockchainState {
+    /// Total number of nonces assigned to regular miners, R in the TNO formula.
+    pub fn total_mining_range(&self) -> u64 {
+        self.active_miners as u64 * NONCES_PER_MINER
+    }
+}
+
+impl CanonicalEncode for BlockchainState {
+    fn canonical_bytes(&self) -> Vec<u8> {
+        PreimageEncoder::new(PreimageKind::BlockchainState)
+            .u64(self.height)
+            .str(&self.previous_hash)
+            .u64(self.timestamp)
+            .u32(self.failed_miner_count)
+            .u32(self.active_miners)
+            .finish()
+    }
+}
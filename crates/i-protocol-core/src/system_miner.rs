@@ -0,0 +1,35 @@
+//! System Miner fallback nonce.
+//!
+//! When no regular miner submits within τ, the System Miner produces the
+//! block with a nonce every node can recompute from the chain state:
+//! H₃(prev_hash ‖ timestamp ‖ fail_count ‖ height ‖ salt) mapped into the
+//! reserved range [`SYSTEM_MINER_RANGE_START`, `SYSTEM_MINER_RANGE_END`].
+
+use crate::encoding::{PreimageEncoder, PreimageKind};
+use crate::hash::{digest_prefix_u64, triple_layer_hash, HashDomain};
+use crate::params::{SYSTEM_MINER_RANGE_END, SYSTEM_MINER_RANGE_START, SYSTEM_MINER_SALT};
+use crate::state::BlockchainState;
+
+/// Canonical preimage prev_hash ‖ timestamp ‖ fail_count ‖ height ‖ salt.
+pub fn system_nonce_preimage(state: &BlockchainState) -> Vec<u8> {
+    PreimageEncoder::new(PreimageKind::SystemNonce)
+        .str(&state.previous_hash)
+        .u64(state.timestamp)
+        .u32(state.failed_miner_count)
+        .u64(state.height)
+        .str(SYSTEM_MINER_SALT)
+        .finish()
+}
+
+/// Deterministic System Miner nonce for `state`.
+pub fn system_nonce(state: &BlockchainState) -> u64 {
+    let preimage = system_nonce_preimage(state);
+    let hash = digest_prefix_u64(&triple_layer_hash(HashDomain::SystemNonce, &preimage));
+
+    (hash % (SYSTEM_MINER_RANGE_END - SYSTEM_MINER_RANGE_START + 1)) + SYSTEM_MINER_RANGE_START
+}
+
+/// Whether `nonce` lies in the range reserved for the System Miner.
+pub fn is_system_nonce(nonce: u64) -> bool {
+    (SYSTEM_MINER_RANGE_START..=SYSTEM_MINER_RANGE_END).contains(&nonce)
+}
@@ -0,0 +1,83 @@
+//! Transaction Nonce Obfuscation.
+//!
+//! TNO transposes a user-chosen nonce into a final nonce that selects the
+//! miner responsible for the transaction. The full 256-bit digest
+//! d = H₃(user_nonce ‖ tx_hash ‖ height ‖ prev_hash) is reduced into the
+//! regular segment [REGULAR_MINER_RANGE_START, active_end] of R nonces by
+//! a wide multiply:
+//!
+//! ```text
+//! final_nonce = REGULAR_MINER_RANGE_START + ⌊d · R / 2²⁵⁶⌋
+//! ```
+//!
+//! # Bias
+//!
+//! The reduction is monotone in d, so the digests mapping to any run of m
+//! consecutive nonces number either ⌊m · 2²⁵⁶ / R⌋ or ⌈m · 2²⁵⁶ / R⌉. For a
+//! single nonce (m = 1) the probability therefore differs from 1/R by less
+//! than 2⁻²⁵⁶, and the statistical distance from uniform is below
+//! R · 2⁻²⁵⁷ < 2⁻¹⁹³ since R < 2⁶⁴. A DURA position spans
+//! m = [`NONCES_PER_MINER`](crate::params::NONCES_PER_MINER) consecutive
+//! nonces, so with n miners each position is selected with probability
+//! within 2⁻²⁵⁶ of 1/n. The former `prefix_u64 mod R` reduction instead
+//! favoured the first 2⁶⁴ mod R nonces whenever R was not a power of two.
+//!
+//! The nonce is routed to its miner through the block's DURA
+//! [`RangeTable`], so the shuffle decides which identity owns each position.
+
+use crate::dura::RangeTable;
+use crate::encoding::{PreimageEncoder, PreimageKind};
+use crate::hash::{triple_layer_hash, Digest32, HashDomain};
+use crate::layout::RangeLayout;
+use crate::state::BlockchainState;
+
+/// Canonical transaction preimage user_nonce ‖ tx_hash ‖ height ‖ prev_hash.
+pub fn tno_preimage(user_nonce: u64, tx_hash: &str, state: &BlockchainState) -> Vec<u8> {
+    PreimageEncoder::new(PreimageKind::TnoMapping)
+        .u64(user_nonce)
+        .str(tx_hash)
+        .u64(state.height)
+        .str(&state.previous_hash)
+        .finish()
+}
+
+/// 256-bit TNO digest H₃(user_nonce ‖ tx_hash ‖ height ‖ prev_hash).
+pub fn tno_digest(user_nonce: u64, tx_hash: &str, state: &BlockchainState) -> Digest32 {
+    triple_layer_hash(HashDomain::Tno, &tno_preimage(user_nonce, tx_hash, state))
+}
+
+/// ⌊x · size / 2^(8 · len)⌋ for the big-endian integer x in `bytes`, which
+/// lies in `[0, size)`.
+///
+/// Works for any input width so that the bias bound can be checked
+/// exhaustively on narrow inputs; TNO applies it to 32-byte digests.
+pub fn wide_reduce(bytes: &[u8], size: u64) -> u64 {
+    // Schoolbook multiply from the least significant byte; the carry out of
+    // the most significant byte is the integer part of the product
+    bytes.iter().rev().fold(0u64, |carry, &byte| {
+        ((byte as u128 * size as u128 + carry as u128) >> 8) as u64
+    })
+}
+
+/// Final nonce for a transaction mapped against `state`, in the regular
+/// segment of the active miners.
+///
+/// # Panics
+///
+/// Panics if `state` has no active miners.
+pub fn execute_tno_mapping(user_nonce: u64, tx_hash: &str, state: &BlockchainState) -> u64 {
+    let segment = RangeLayout::new(state.active_miners as usize)
+        .regular_segment()
+        .expect("TNO mapping requires active miners");
+    let digest = tno_digest(user_nonce, tx_hash, state);
+
+    segment.start + wide_reduce(&digest, segment.size())
+}
+
+/// Identity hash of the miner whose DURA range contains `final_nonce`.
+///
+/// `table` must come from the same active set as the state the nonce was
+/// mapped against; `None` means the nonce lies outside it.
+pub fn assigned_miner(final_nonce: u64, table: &RangeTable) -> Option<&str> {
+    table.owner_of(final_nonce)
+}
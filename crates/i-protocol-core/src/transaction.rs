@@ -0,0 +1,350 @@
+//! Transactions carried in blocks.
+//!
+//! Every [`TransactionKind`] moves `amount_i` subunits from the sender and
+//! pays the fee the iPAC rule ([`calculate_fee`]) charges on that amount, so
+//! fees stay predictable whatever the transaction does. Wallets assemble
+//! transactions through [`TransactionBuilder`], which fills in the fee and
+//! refuses transactions a block validator would reject on their own merits.
+//!
+//! The sender is its public key, and the key is part of the signed body: a
+//! signature only authorizes a transaction for the account of the key that
+//! made it.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::{CanonicalEncode, PreimageEncoder, PreimageKind};
+use crate::fees::{calculate_fee, MIN_TRANSACTION_AMOUNT};
+use crate::hash::{to_hex, triple_layer_hash, Digest32, HashDomain};
+use crate::params::TX_EXPIRY_BLOCKS;
+use crate::signing::{self, SignatureError, Signer};
+
+/// What a transaction does with its amount. The recipient is the account or
+/// contract the kind addresses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionKind {
+    /// Pays the recipient.
+    Transfer,
+    /// Calls `function` on the contract at the recipient.
+    SmartContract {
+        function: String,
+        call_data: Vec<u8>,
+    },
+    /// Stakes with the validator at the recipient.
+    Staking,
+    /// Votes on `proposal`, weighted by the amount.
+    Governance { proposal: u64, approve: bool },
+    /// Bridges the amount to the recipient address on `chain`.
+    CrossChain { chain: String },
+    /// Mints the amount of `token` to the recipient.
+    TokenMint { token: String },
+    /// Burns the amount of `token`.
+    TokenBurn { token: String },
+    /// Pays the recipient once `threshold` of `signers` approve.
+    MultiSig {
+        signers: Vec<String>,
+        threshold: u32,
+    },
+    /// Swaps the amount for `counter_amount_i` of `counter_token` held by
+    /// the recipient, or neither side moves.
+    Atomic {
+        counter_token: String,
+        counter_amount_i: u128,
+    },
+    /// Publishes `value` to the oracle `feed` at the recipient.
+    Oracle { feed: String, value: u128 },
+}
+
+impl TransactionKind {
+    /// Tag leading the kind in the canonical encoding.
+    pub fn tag(&self) -> u8 {
+        match self {
+            TransactionKind::Transfer => 1,
+            TransactionKind::SmartContract { .. } => 2,
+            TransactionKind::Staking => 3,
+            TransactionKind::Governance { .. } => 4,
+            TransactionKind::CrossChain { .. } => 5,
+            TransactionKind::TokenMint { .. } => 6,
+            TransactionKind::TokenBurn { .. } => 7,
+            TransactionKind::MultiSig { .. } => 8,
+            TransactionKind::Atomic { .. } => 9,
+            TransactionKind::Oracle { .. } => 10,
+        }
+    }
+
+    /// Variant name, without the payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransactionKind::Transfer => "Transfer",
+            TransactionKind::SmartContract { .. } => "SmartContract",
+            TransactionKind::Staking => "Staking",
+            TransactionKind::Governance { .. } => "Governance",
+            TransactionKind::CrossChain { .. } => "CrossChain",
+            TransactionKind::TokenMint { .. } => "TokenMint",
+            TransactionKind::TokenBurn { .. } => "TokenBurn",
+            TransactionKind::MultiSig { .. } => "MultiSig",
+            TransactionKind::Atomic { .. } => "Atomic",
+            TransactionKind::Oracle { .. } => "Oracle",
+        }
+    }
+
+    /// Checks the invariants of the kind's own fields.
+    pub fn validate(&self) -> Result<(), TransactionError> {
+        if let TransactionKind::MultiSig { signers, threshold } = self {
+            if *threshold == 0 || *threshold as usize > signers.len() {
+                return Err(TransactionError::InvalidThreshold {
+                    threshold: *threshold,
+                    signers: signers.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn encode(&self, encoder: &mut PreimageEncoder) {
+        encoder.u8(self.tag());
+        match self {
+            TransactionKind::Transfer | TransactionKind::Staking => {}
+            TransactionKind::SmartContract {
+                function,
+                call_data,
+            } => {
+                encoder.str(function).bytes(call_data);
+            }
+            TransactionKind::Governance { proposal, approve } => {
+                encoder.u64(*proposal).bool(*approve);
+            }
+            TransactionKind::CrossChain { chain } => {
+                encoder.str(chain);
+            }
+            TransactionKind::TokenMint { token } | TransactionKind::TokenBurn { token } => {
+                encoder.str(token);
+            }
+            TransactionKind::MultiSig { signers, threshold } => {
+                encoder.strs(signers).u32(*threshold);
+            }
+            TransactionKind::Atomic {
+                counter_token,
+                counter_amount_i,
+            } => {
+                encoder.str(counter_token).u128(*counter_amount_i);
+            }
+            TransactionKind::Oracle { feed, value } => {
+                encoder.str(feed).u128(*value);
+            }
+        }
+    }
+}
+
+/// Reason a transaction is malformed on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError {
+    /// A builder field without a default was never set.
+    MissingField(&'static str),
+    AmountBelowMinimum {
+        amount_i: u128,
+    },
+    /// A multisig threshold of zero or above the number of signers.
+    InvalidThreshold {
+        threshold: u32,
+        signers: usize,
+    },
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionError::MissingField(field) => write!(f, "transaction {field} not set"),
+            TransactionError::AmountBelowMinimum { amount_i } => {
+                write!(
+                    f,
+                    "amount {amount_i} i below minimum {MIN_TRANSACTION_AMOUNT} i"
+                )
+            }
+            TransactionError::InvalidThreshold { threshold, signers } => {
+                write!(f, "multisig threshold {threshold} of {signers} signers")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+/// Transaction as included in a block.
+///
+/// Amounts are in `i` subunits. `final_nonce` is the TNO output recorded by
+/// the block producer; it and the signature are excluded from the hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transaction {
+    /// Public key of the sending account, which must sign the transaction.
+    pub sender_key: Vec<u8>,
+    pub recipient: String,
+    pub kind: TransactionKind,
+    pub amount_i: u128,
+    pub fee: u128,
+    /// Wallet-chosen nonce fed into TNO.
+    pub user_nonce: u64,
+    pub timestamp: u64,
+    /// Last height whose block may include the transaction.
+    pub expiry_height: u64,
+    pub final_nonce: u64,
+    pub signature: Vec<u8>,
+}
+
+impl Transaction {
+    pub fn builder(kind: TransactionKind) -> TransactionBuilder {
+        TransactionBuilder::new(kind)
+    }
+
+    /// H₃ of the unsigned transaction body.
+    pub fn hash(&self) -> Digest32 {
+        triple_layer_hash(HashDomain::Transaction, &self.canonical_bytes())
+    }
+
+    /// [`Transaction::hash`] as hex, the form TNO consumes.
+    pub fn hash_hex(&self) -> String {
+        to_hex(&self.hash())
+    }
+
+    /// Authorizes the transaction, signing the same unsigned body the hash
+    /// covers. Only a signer holding [`Transaction::sender_key`] produces a
+    /// signature that verifies.
+    pub fn sign<S: Signer + ?Sized>(&mut self, signer: &S) {
+        self.signature = signer.sign(&self.canonical_bytes());
+    }
+
+    /// Checks the authorization against the sender key the body commits to.
+    pub fn verify_signature(&self) -> Result<(), SignatureError> {
+        signing::verify(&self.sender_key, &self.canonical_bytes(), &self.signature)
+    }
+
+    /// Whether a block at `height` may still include the transaction.
+    pub fn is_live_at(&self, height: u64) -> bool {
+        height <= self.expiry_height
+    }
+}
+
+impl CanonicalEncode for Transaction {
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut encoder = PreimageEncoder::new(PreimageKind::Transaction);
+        encoder.bytes(&self.sender_key).str(&self.recipient);
+        self.kind.encode(&mut encoder);
+        encoder
+            .u128(self.amount_i)
+            .u128(self.fee)
+            .u64(self.user_nonce)
+            .u64(self.timestamp)
+            .u64(self.expiry_height)
+            .finish()
+    }
+}
+
+/// Wallet-side assembly of a [`Transaction`].
+///
+/// The fee follows from the amount and is not settable. Every other field
+/// must be given, the sender key by [`TransactionBuilder::sign`] if not
+/// before; the user nonce is any value the wallet picks, typically at random.
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    kind: TransactionKind,
+    sender_key: Option<Vec<u8>>,
+    recipient: Option<String>,
+    amount_i: Option<u128>,
+    user_nonce: Option<u64>,
+    timestamp: Option<u64>,
+    expiry_height: Option<u64>,
+}
+
+impl TransactionBuilder {
+    pub fn new(kind: TransactionKind) -> Self {
+        TransactionBuilder {
+            kind,
+            sender_key: None,
+            recipient: None,
+            amount_i: None,
+            user_nonce: None,
+            timestamp: None,
+            expiry_height: None,
+        }
+    }
+
+    pub fn sender_key(mut self, sender_key: impl Into<Vec<u8>>) -> Self {
+        self.sender_key = Some(sender_key.into());
+        self
+    }
+
+    pub fn recipient(mut self, recipient: impl Into<String>) -> Self {
+        self.recipient = Some(recipient.into());
+        self
+    }
+
+    pub fn amount_i(mut self, amount_i: u128) -> Self {
+        self.amount_i = Some(amount_i);
+        self
+    }
+
+    pub fn user_nonce(mut self, user_nonce: u64) -> Self {
+        self.user_nonce = Some(user_nonce);
+        self
+    }
+
+    /// Submission time in milliseconds.
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn expiry_height(mut self, expiry_height: u64) -> Self {
+        self.expiry_height = Some(expiry_height);
+        self
+    }
+
+    /// Expires [`TX_EXPIRY_BLOCKS`] after the chain tip at `height`, or at
+    /// the last representable height if that lies beyond it.
+    pub fn expires_after(self, height: u64) -> Self {
+        self.expiry_height(height.saturating_add(TX_EXPIRY_BLOCKS))
+    }
+
+    /// Unsigned transaction with the iPAC fee for its amount.
+    pub fn build(self) -> Result<Transaction, TransactionError> {
+        let amount_i = self
+            .amount_i
+            .ok_or(TransactionError::MissingField("amount"))?;
+        if amount_i < MIN_TRANSACTION_AMOUNT {
+            return Err(TransactionError::AmountBelowMinimum { amount_i });
+        }
+        self.kind.validate()?;
+
+        Ok(Transaction {
+            sender_key: self
+                .sender_key
+                .ok_or(TransactionError::MissingField("sender key"))?,
+            recipient: self
+                .recipient
+                .ok_or(TransactionError::MissingField("recipient"))?,
+            kind: self.kind,
+            amount_i,
+            fee: calculate_fee(amount_i),
+            user_nonce: self
+                .user_nonce
+                .ok_or(TransactionError::MissingField("user nonce"))?,
+            timestamp: self
+                .timestamp
+                .ok_or(TransactionError::MissingField("timestamp"))?,
+            expiry_height: self
+                .expiry_height
+                .ok_or(TransactionError::MissingField("expiry height"))?,
+            final_nonce: 0,
+            signature: Vec::new(),
+        })
+    }
+
+    /// [`TransactionBuilder::build`] with `signer`'s public key as the
+    /// sender key, then signed by `signer`.
+    pub fn sign<S: Signer + ?Sized>(self, signer: &S) -> Result<Transaction, TransactionError> {
+        let mut tx = self.sender_key(signer.public_key()).build()?;
+        tx.sign(signer);
+        Ok(tx)
+    }
+}
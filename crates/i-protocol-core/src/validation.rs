@@ -0,0 +1,452 @@
+//! End-to-end block validation.
+//!
+//! [`validate_block`] runs the specification's `verify_*` checks against the
+//! chain tip a block claims to extend: producer nonce (DURA range or System
+//! Miner nonce) and proof of work, TNO mapping, fee and sender signature of
+//! every transaction, and the coinbase against the emission schedule.
+//!
+//! Regular ranges come from DURA under the master seed of the closed
+//! commit–reveal round in [`ChainState::round`], over its revealed
+//! identities that are still active, so a miner that withheld its reveal or
+//! has since been deactivated holds no range. Before the
+//! first round closes the public-seed assignment over
+//! [`ChainState::active_miners`] applies.
+//!
+//! A SysBlock's timestamp and failed miner count feed its nonce, so neither
+//! is left to the proposer: the timestamp is fixed to the fallback decision
+//! point t₀ + τ after the parent, and the count cannot exceed the active
+//! miners. A regular block means a miner finished in time, so its failed
+//! miner count must be zero.
+
+use std::fmt;
+
+use crate::block::{Block, BlockProducer};
+use crate::bootstrap::NetworkPhase;
+use crate::commit_reveal::RoundOutcome;
+use crate::difficulty::DifficultyTarget;
+use crate::dura::{assign_ranges, DuraAssignment};
+use crate::emission::block_reward;
+use crate::fees::{calculate_fee, split_fee, MIN_TRANSACTION_AMOUNT};
+use crate::hash::{to_hex, Digest32};
+use crate::identity::MinerIdentity;
+use crate::mempool::sysblock_cutoff;
+use crate::signing::SignatureError;
+use crate::state::BlockchainState;
+use crate::system_miner::system_nonce;
+use crate::tno::execute_tno_mapping;
+use crate::transaction::{Transaction, TransactionError};
+
+/// Chain tip a block is validated against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainState {
+    pub tip_height: u64,
+    pub tip_hash: Digest32,
+    pub tip_timestamp: u64,
+    /// Miners active in the registry at the next height.
+    pub active_miners: Vec<MinerIdentity>,
+    /// Latest closed commit–reveal round. `None` until the first round
+    /// closes.
+    pub round: Option<RoundOutcome>,
+    /// Phase at the next height, which decides the single-range floor.
+    pub phase: NetworkPhase,
+    /// Target a regular block's header hash must meet at the next height.
+    pub target: DifficultyTarget,
+}
+
+impl ChainState {
+    /// Identities holding a DURA range at the next height: the revealed
+    /// identities of [`ChainState::round`] still among
+    /// [`ChainState::active_miners`], or every active miner before the first
+    /// round closes.
+    pub fn range_holders(&self) -> u32 {
+        match &self.round {
+            Some(outcome) => outcome.active_identities(&self.active_miners).len() as u32,
+            None => self.active_miners.len() as u32,
+        }
+    }
+
+    /// Regular miner ranges at the next height, as
+    /// [`BootstrapController::range_count`](crate::bootstrap::BootstrapController::range_count)
+    /// derives them.
+    pub fn range_count(&self) -> u32 {
+        self.phase.range_count(self.range_holders())
+    }
+
+    /// DURA ranges for the next height.
+    pub fn assign_ranges(&self) -> DuraAssignment {
+        let previous_hash = to_hex(&self.tip_hash);
+        match &self.round {
+            Some(outcome) => outcome.assign_active_ranges(&previous_hash, &self.active_miners),
+            None => assign_ranges(&previous_hash, &self.active_miners),
+        }
+    }
+}
+
+/// Reason a block was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockError {
+    HeightMismatch {
+        expected: u64,
+        found: u64,
+    },
+    UnknownParent {
+        expected: Digest32,
+        found: Digest32,
+    },
+    TimestampNotIncreasing {
+        tip: u64,
+        found: u64,
+    },
+    MerkleRootMismatch {
+        expected: Digest32,
+        found: Digest32,
+    },
+    /// `range_owner` holds no DURA range at this height.
+    UnknownRangeOwner {
+        range_owner: Digest32,
+    },
+    /// Regular block nonce outside the producer's DURA range.
+    NonceOutsideRange {
+        nonce: u64,
+        start: u64,
+        end: u64,
+    },
+    /// Regular block reporting failed miners.
+    FailedMinerCountOnRegularBlock {
+        count: u32,
+    },
+    /// Regular block hash above the difficulty target.
+    TargetNotMet {
+        hash: Digest32,
+        target: DifficultyTarget,
+    },
+    /// SysBlock not stamped at the fallback decision point t₀ + τ.
+    SysBlockTimestampMismatch {
+        expected: u64,
+        found: u64,
+    },
+    /// SysBlock reporting more failed miners than hold a range.
+    FailedMinerCountExceeded {
+        count: u32,
+        active: u32,
+    },
+    SystemNonceMismatch {
+        expected: u64,
+        found: u64,
+    },
+    /// Transactions cannot be mapped without regular miner ranges. Bootstrap
+    /// keeps one range, so this only arises in the sustained phase once no
+    /// miner holds a range.
+    NoActiveMiners,
+    AmountBelowMinimum {
+        index: usize,
+        amount: u128,
+    },
+    /// The block lies past the transaction's expiry height.
+    TransactionExpired {
+        index: usize,
+        expiry_height: u64,
+        height: u64,
+    },
+    InvalidTransaction {
+        index: usize,
+        error: TransactionError,
+    },
+    TnoMismatch {
+        index: usize,
+        expected: u64,
+        found: u64,
+    },
+    FeeMismatch {
+        index: usize,
+        expected: u128,
+        found: u128,
+    },
+    /// The signature does not verify under the transaction's sender key.
+    InvalidSignature {
+        index: usize,
+        error: SignatureError,
+    },
+    BlockRewardMismatch {
+        expected: u128,
+        found: u128,
+    },
+    MinerFeesMismatch {
+        expected: u128,
+        found: u128,
+    },
+}
+
+impl fmt::Display for BlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockError::HeightMismatch { expected, found } => {
+                write!(f, "block height {found}, expected {expected}")
+            }
+            BlockError::UnknownParent { expected, found } => write!(
+                f,
+                "previous hash {} does not match tip {}",
+                to_hex(found),
+                to_hex(expected)
+            ),
+            BlockError::TimestampNotIncreasing { tip, found } => {
+                write!(f, "timestamp {found} not after tip timestamp {tip}")
+            }
+            BlockError::MerkleRootMismatch { expected, found } => write!(
+                f,
+                "merkle root {} does not match transactions ({})",
+                to_hex(found),
+                to_hex(expected)
+            ),
+            BlockError::UnknownRangeOwner { range_owner } => {
+                write!(f, "miner {} holds no DURA range", to_hex(range_owner))
+            }
+            BlockError::NonceOutsideRange { nonce, start, end } => {
+                write!(f, "nonce {nonce} outside assigned range [{start}, {end}]")
+            }
+            BlockError::FailedMinerCountOnRegularBlock { count } => {
+                write!(f, "regular block reports {count} failed miners")
+            }
+            BlockError::TargetNotMet { hash, target } => write!(
+                f,
+                "block hash {} above target {:016x}",
+                to_hex(hash),
+                target.threshold()
+            ),
+            BlockError::SysBlockTimestampMismatch { expected, found } => {
+                write!(f, "SysBlock timestamp {found}, expected {expected}")
+            }
+            BlockError::FailedMinerCountExceeded { count, active } => {
+                write!(
+                    f,
+                    "failed miner count {count} exceeds {active} active miners"
+                )
+            }
+            BlockError::SystemNonceMismatch { expected, found } => {
+                write!(f, "system nonce {found}, expected {expected}")
+            }
+            BlockError::NoActiveMiners => {
+                write!(f, "transactions present but no active miner ranges")
+            }
+            BlockError::AmountBelowMinimum { index, amount } => {
+                write!(f, "transaction {index}: amount {amount} below minimum")
+            }
+            BlockError::TransactionExpired {
+                index,
+                expiry_height,
+                height,
+            } => write!(
+                f,
+                "transaction {index}: expired at {expiry_height}, block height {height}"
+            ),
+            BlockError::InvalidTransaction { index, error } => {
+                write!(f, "transaction {index}: {error}")
+            }
+            BlockError::TnoMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "transaction {index}: final nonce {found}, expected {expected}"
+            ),
+            BlockError::FeeMismatch {
+                index,
+                expected,
+                found,
+            } => write!(f, "transaction {index}: fee {found}, expected {expected}"),
+            BlockError::InvalidSignature { index, error } => {
+                write!(f, "transaction {index}: {error}")
+            }
+            BlockError::BlockRewardMismatch { expected, found } => {
+                write!(f, "block reward {found}, expected {expected}")
+            }
+            BlockError::MinerFeesMismatch { expected, found } => {
+                write!(f, "miner fee share {found}, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BlockError {}
+
+/// Validates `block` as the successor of `chain`.
+///
+/// Checks run in order and the first failure is returned.
+pub fn validate_block(block: &Block, chain: &ChainState) -> Result<(), BlockError> {
+    let header = &block.header;
+
+    if header.height != chain.tip_height + 1 {
+        return Err(BlockError::HeightMismatch {
+            expected: chain.tip_height + 1,
+            found: header.height,
+        });
+    }
+    if header.previous_hash != chain.tip_hash {
+        return Err(BlockError::UnknownParent {
+            expected: chain.tip_hash,
+            found: header.previous_hash,
+        });
+    }
+    if header.timestamp <= chain.tip_timestamp {
+        return Err(BlockError::TimestampNotIncreasing {
+            tip: chain.tip_timestamp,
+            found: header.timestamp,
+        });
+    }
+
+    let merkle_root = block.compute_merkle_root();
+    if header.merkle_root != merkle_root {
+        return Err(BlockError::MerkleRootMismatch {
+            expected: merkle_root,
+            found: header.merkle_root,
+        });
+    }
+
+    let state = header.chain_state(chain.range_count());
+    match header.producer {
+        BlockProducer::RegularMiner { range_owner, nonce } => {
+            verify_range_nonce(range_owner, nonce, chain)?;
+            if header.failed_miner_count != 0 {
+                return Err(BlockError::FailedMinerCountOnRegularBlock {
+                    count: header.failed_miner_count,
+                });
+            }
+            let hash = header.hash();
+            if !chain.target.is_met_by(&hash) {
+                return Err(BlockError::TargetNotMet {
+                    hash,
+                    target: chain.target,
+                });
+            }
+        }
+        BlockProducer::SystemMiner {
+            system_nonce: found,
+        } => {
+            let expected = sysblock_cutoff(chain.tip_timestamp);
+            if header.timestamp != expected {
+                return Err(BlockError::SysBlockTimestampMismatch {
+                    expected,
+                    found: header.timestamp,
+                });
+            }
+            let active = chain.range_holders();
+            if header.failed_miner_count > active {
+                return Err(BlockError::FailedMinerCountExceeded {
+                    count: header.failed_miner_count,
+                    active,
+                });
+            }
+            let expected = system_nonce(&state);
+            if found != expected {
+                return Err(BlockError::SystemNonceMismatch { expected, found });
+            }
+        }
+    }
+
+    if !block.transactions.is_empty() && state.active_miners == 0 {
+        return Err(BlockError::NoActiveMiners);
+    }
+    for (index, tx) in block.transactions.iter().enumerate() {
+        verify_transaction(index, tx, &state)?;
+    }
+
+    verify_coinbase(block)
+}
+
+/// `verify_tno_mapping`: the recorded final nonce is the TNO output.
+pub fn verify_tno_mapping(tx: &Transaction, state: &BlockchainState) -> bool {
+    tx.final_nonce == execute_tno_mapping(tx.user_nonce, &tx.hash_hex(), state)
+}
+
+// Regular block: the nonce lies in the range DURA gave `range_owner`
+fn verify_range_nonce(
+    range_owner: Digest32,
+    nonce: u64,
+    chain: &ChainState,
+) -> Result<(), BlockError> {
+    let owner_hex = to_hex(&range_owner);
+    let assignment = chain.assign_ranges();
+    let range = assignment
+        .ranges
+        .iter()
+        .find(|range| range.identity_hash == owner_hex)
+        .ok_or(BlockError::UnknownRangeOwner { range_owner })?;
+
+    if !(range.start_nonce..=range.end_nonce).contains(&nonce) {
+        return Err(BlockError::NonceOutsideRange {
+            nonce,
+            start: range.start_nonce,
+            end: range.end_nonce,
+        });
+    }
+    Ok(())
+}
+
+fn verify_transaction(
+    index: usize,
+    tx: &Transaction,
+    state: &BlockchainState,
+) -> Result<(), BlockError> {
+    if tx.amount_i < MIN_TRANSACTION_AMOUNT {
+        return Err(BlockError::AmountBelowMinimum {
+            index,
+            amount: tx.amount_i,
+        });
+    }
+    if !tx.is_live_at(state.height) {
+        return Err(BlockError::TransactionExpired {
+            index,
+            expiry_height: tx.expiry_height,
+            height: state.height,
+        });
+    }
+    tx.kind
+        .validate()
+        .map_err(|error| BlockError::InvalidTransaction { index, error })?;
+
+    let expected = execute_tno_mapping(tx.user_nonce, &tx.hash_hex(), state);
+    if tx.final_nonce != expected {
+        return Err(BlockError::TnoMismatch {
+            index,
+            expected,
+            found: tx.final_nonce,
+        });
+    }
+
+    let expected = calculate_fee(tx.amount_i);
+    if tx.fee != expected {
+        return Err(BlockError::FeeMismatch {
+            index,
+            expected,
+            found: tx.fee,
+        });
+    }
+
+    tx.verify_signature()
+        .map_err(|error| BlockError::InvalidSignature { index, error })
+}
+
+// Coinbase mints the scheduled reward plus the miners' share of fees
+fn verify_coinbase(block: &Block) -> Result<(), BlockError> {
+    let expected = block_reward(block.header.height);
+    if block.header.coinbase.block_reward != expected {
+        return Err(BlockError::BlockRewardMismatch {
+            expected,
+            found: block.header.coinbase.block_reward,
+        });
+    }
+
+    let expected: u128 = block
+        .transactions
+        .iter()
+        .map(|tx| split_fee(tx.fee).miners)
+        .sum();
+    if block.header.coinbase.miner_fees != expected {
+        return Err(BlockError::MinerFeesMismatch {
+            expected,
+            found: block.header.coinbase.miner_fees,
+        });
+    }
+    Ok(())
+}
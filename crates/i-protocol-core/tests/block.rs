@@ -0,0 +1,139 @@
+use i_protocol_core::block::transactions_root;
+use i_protocol_core::hash::triple_layer_hash;
+use i_protocol_core::merkle::{merkle_root, EMPTY_ROOT};
+use i_protocol_core::system_miner::system_nonce;
+use i_protocol_core::{
+    Block, BlockHeader, BlockProducer, Coinbase, HashDomain, Transaction, TransactionKind,
+};
+
+fn transaction(user_nonce: u64) -> Transaction {
+    Transaction {
+        sender_key: b"alice".to_vec(),
+        recipient: "bob".into(),
+        kind: TransactionKind::Transfer,
+        amount_i: 5_000_000,
+        fee: 50_000,
+        user_nonce,
+        timestamp: 1_700_000_000_000 + user_nonce,
+        expiry_height: 1_000,
+        final_nonce: 0,
+        signature: vec![0xab; 64],
+    }
+}
+
+fn block(producer: BlockProducer) -> Block {
+    let transactions: Vec<Transaction> = (0..5).map(transaction).collect();
+    Block {
+        header: BlockHeader {
+            height: 42,
+            previous_hash: triple_layer_hash(HashDomain::BlockHeader, b"parent"),
+            timestamp: 1_700_000_021_000,
+            merkle_root: transactions_root(&transactions),
+            failed_miner_count: 3,
+            producer,
+            coinbase: Coinbase::default(),
+        },
+        transactions,
+    }
+}
+
+fn regular() -> BlockProducer {
+    BlockProducer::RegularMiner {
+        range_owner: [7; 32],
+        nonce: 123_456,
+    }
+}
+
+#[test]
+fn block_round_trips_through_json() {
+    for producer in [regular(), BlockProducer::SystemMiner { system_nonce: 77 }] {
+        let block = block(producer);
+        let json = serde_json::to_string(&block).unwrap();
+        let decoded: Block = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, block);
+        assert_eq!(decoded.hash(), block.hash());
+    }
+}
+
+#[test]
+fn header_digests_serialize_as_hex() {
+    let header = block(regular()).header;
+    let json = serde_json::to_value(header).unwrap();
+    assert_eq!(json["merkle_root"].as_str().unwrap().len(), 64);
+    assert_eq!(
+        json["producer"]["RegularMiner"]["range_owner"],
+        "07".repeat(32)
+    );
+    assert!(serde_json::from_str::<BlockHeader>(&json.to_string().replace("07", "zz")).is_err());
+}
+
+#[test]
+fn header_hash_commits_to_every_field() {
+    let base = block(regular()).header;
+    let mut variants = vec![base];
+    let mut h = base;
+    h.height += 1;
+    variants.push(h);
+    let mut h = base;
+    h.previous_hash[0] ^= 1;
+    variants.push(h);
+    let mut h = base;
+    h.timestamp += 1;
+    variants.push(h);
+    let mut h = base;
+    h.merkle_root[31] ^= 1;
+    variants.push(h);
+    let mut h = base;
+    h.failed_miner_count += 1;
+    variants.push(h);
+    let mut h = base;
+    h.producer = BlockProducer::SystemMiner {
+        system_nonce: 123_456,
+    };
+    variants.push(h);
+    let mut h = base;
+    h.coinbase.block_reward += 1;
+    variants.push(h);
+
+    let hashes: std::collections::HashSet<_> = variants.iter().map(BlockHeader::hash).collect();
+    assert_eq!(hashes.len(), variants.len());
+}
+
+#[test]
+fn sys_block_exposes_system_nonce() {
+    let mut header = block(regular()).header;
+    assert_eq!(header.system_nonce(), None);
+    assert_eq!(header.nonce(), 123_456);
+
+    let expected = system_nonce(&header.chain_state(10));
+    header.producer = BlockProducer::SystemMiner {
+        system_nonce: expected,
+    };
+    assert!(header.is_sys_block());
+    assert_eq!(header.system_nonce(), Some(expected));
+}
+
+#[test]
+fn merkle_root_tracks_transactions() {
+    let mut block = block(regular());
+    assert!(block.merkle_root_matches());
+    block.transactions.swap(0, 1);
+    assert!(!block.merkle_root_matches());
+
+    assert_eq!(merkle_root(&[]), EMPTY_ROOT);
+    let leaf = [1; 32];
+    assert_ne!(merkle_root(&[leaf]), leaf);
+}
+
+#[test]
+fn transaction_hash_excludes_signature_and_final_nonce() {
+    let tx = transaction(9);
+    let mut signed = tx.clone();
+    signed.signature = vec![1, 2, 3];
+    signed.final_nonce = 99;
+    assert_eq!(tx.hash(), signed.hash());
+
+    let mut other = tx.clone();
+    other.amount_i += 1;
+    assert_ne!(tx.hash(), other.hash());
+}
@@ -0,0 +1,148 @@
+use i_protocol_core::bootstrap::{BootstrapController, NetworkPhase};
+use i_protocol_core::dura::assign_ranges;
+use i_protocol_core::params::{
+    BOOTSTRAP_MIN_MINERS, BOOTSTRAP_SUSTAIN_BLOCKS, NONCES_PER_MINER,
+    REGISTRY_ACTIVATION_DELAY as K,
+};
+use i_protocol_core::registry::{MinerRegistry, RegistryChange, RegistryError};
+use i_protocol_core::MinerIdentity;
+
+fn miner(id: u32) -> MinerIdentity {
+    MinerIdentity::virtual_miner(id, 1_700_000_000)
+}
+
+fn join(id: u32) -> RegistryChange {
+    RegistryChange::Join(miner(id))
+}
+
+fn leave(id: u32) -> RegistryChange {
+    RegistryChange::leave(&miner(id), &MinerIdentity::virtual_signer(id))
+}
+
+// Miners 10·b .. 10·b + 9 join in the block at height 10 · (b + 1).
+fn ramp(miners: u32) -> BootstrapController {
+    let mut controller = BootstrapController::new();
+    for batch in 0..miners.div_ceil(10) {
+        let changes: Vec<_> = (10 * batch..(10 * batch + 10).min(miners))
+            .map(join)
+            .collect();
+        controller
+            .apply_block(10 * (u64::from(batch) + 1), &changes)
+            .unwrap();
+    }
+    controller
+}
+
+#[test]
+fn genesis_starts_with_a_single_range() {
+    let controller = BootstrapController::new();
+    assert_eq!(controller.phase(0), NetworkPhase::Bootstrap);
+    assert_eq!(controller.active_miner_count(1), 0);
+    assert_eq!(controller.range_count(1), 1);
+    assert_eq!(controller.get_active_mining_range(1), NONCES_PER_MINER);
+    assert_eq!(controller.bootstrap_end(), None);
+}
+
+#[test]
+fn registration_follows_the_registry_activation_delay() {
+    let mut controller = BootstrapController::new();
+    controller.apply_block(5, &[join(0), join(1)]).unwrap();
+    assert_eq!(controller.active_miner_count(5 + K - 1), 0);
+    assert_eq!(controller.active_miner_count(5 + K), 2);
+    assert_eq!(
+        controller.get_active_mining_range(5 + K),
+        2 * NONCES_PER_MINER
+    );
+    assert_eq!(
+        controller.active_miners(5 + K),
+        controller.registry().snapshot(5 + K).miners
+    );
+
+    assert_eq!(
+        controller.apply_block(6, &[join(1)]),
+        Err(RegistryError::AlreadyRegistered {
+            identity_hash: miner(1).identity_hash()
+        })
+    );
+}
+
+#[test]
+fn departures_fall_back_to_the_single_range_floor() {
+    let registry = MinerRegistry::with_genesis([miner(0), miner(1)]).unwrap();
+    let mut controller = BootstrapController::with_registry(registry);
+    assert_eq!(controller.range_count(1), 2);
+
+    controller.apply_block(3, &[leave(0)]).unwrap();
+    controller.apply_block(4, &[leave(1)]).unwrap();
+    assert_eq!(controller.range_count(3 + K), 1);
+    assert_eq!(controller.active_miner_count(4 + K), 0);
+    assert_eq!(controller.range_count(4 + K), 1);
+}
+
+#[test]
+fn ramp_to_1500_miners_grows_one_range_per_miner() {
+    let controller = ramp(1_500);
+    let mut previous = 0;
+    for height in 0..=1_500 + K {
+        let miners = controller.active_miner_count(height);
+        let range = controller.get_active_mining_range(height);
+        assert_eq!(range, u64::from(miners.max(1)) * NONCES_PER_MINER);
+        assert!(range >= previous);
+        previous = range;
+    }
+    assert_eq!(controller.active_miner_count(1_500 + K), 1_500);
+    assert_eq!(
+        controller.get_active_mining_range(1_500 + K),
+        1_500 * NONCES_PER_MINER
+    );
+}
+
+#[test]
+fn ramp_matches_dura_assignment() {
+    let controller = ramp(1_500);
+    for height in [10 + K, 100 + K, 750 + K, 1_000 + K, 1_500 + K] {
+        let miners = controller.active_miners(height);
+        let assignment = assign_ranges("prev", &miners);
+        let layout = controller.layout(height);
+        assert_eq!(assignment.ranges.len(), layout.regular_miners());
+
+        let segment = layout.regular_segment().unwrap();
+        assert_eq!(
+            segment.size(),
+            controller.get_active_mining_range(height),
+            "height {height}"
+        );
+        for range in &assignment.ranges {
+            assert_eq!(
+                layout
+                    .regular_range(range.position)
+                    .map(|r| (r.start, r.end)),
+                Some((range.start_nonce, range.end_nonce))
+            );
+        }
+    }
+}
+
+#[test]
+fn bootstrap_ends_after_sustained_participation() {
+    let controller = ramp(1_500);
+    let threshold_height = u64::from(BOOTSTRAP_MIN_MINERS) + K;
+    let end = threshold_height + BOOTSTRAP_SUSTAIN_BLOCKS;
+    assert_eq!(controller.bootstrap_end(), Some(end));
+    assert_eq!(controller.phase(end - 1), NetworkPhase::Bootstrap);
+    assert_eq!(controller.phase(end), NetworkPhase::Sustained);
+
+    let short = ramp(BOOTSTRAP_MIN_MINERS - 1);
+    assert_eq!(short.bootstrap_end(), None);
+    assert_eq!(short.phase(u64::MAX), NetworkPhase::Bootstrap);
+
+    // A departure before the run completes restarts it
+    let mut dipped = ramp(BOOTSTRAP_MIN_MINERS);
+    let dip = threshold_height + 1;
+    dipped.apply_block(dip - K, &[leave(0)]).unwrap();
+    dipped.apply_block(dip + 1 - K, &[join(9_999)]).unwrap();
+    assert_eq!(
+        dipped.bootstrap_end(),
+        Some(dip + 1 + BOOTSTRAP_SUSTAIN_BLOCKS)
+    );
+}
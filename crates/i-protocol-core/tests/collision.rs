@@ -0,0 +1,306 @@
+use std::path::PathBuf;
+
+use i_protocol_core::collision::{
+    Claim, CollisionIndex, CollisionIndexError, Contender, ResolutionOrder,
+};
+use i_protocol_core::params::REGULAR_MINER_RANGE_START;
+use i_protocol_core::{Transaction, TransactionKind};
+
+// Hashes grow with `id`, so under the default order lower ids win
+fn claimant(id: u64) -> Contender {
+    let mut tx_hash = [0; 32];
+    tx_hash[..8].copy_from_slice(&id.to_be_bytes());
+    Contender {
+        tx_hash,
+        submission_timestamp: 1_700_000_000_000,
+    }
+}
+
+fn transfer(sender: &str, user_nonce: u64, timestamp: u64) -> Transaction {
+    Transaction {
+        sender_key: sender.as_bytes().to_vec(),
+        recipient: "bob".into(),
+        kind: TransactionKind::Transfer,
+        amount_i: 1_000,
+        fee: 10,
+        user_nonce,
+        timestamp,
+        expiry_height: 1_000,
+        final_nonce: 0,
+        signature: Vec::new(),
+    }
+}
+
+// Fresh directory per test under the system temp dir
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "i-protocol-collision-{name}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn collisions_are_scoped_to_one_height() {
+    let mut index = CollisionIndex::new();
+    assert_eq!(index.claim(10, 500, claimant(1)).unwrap(), Claim::Claimed);
+    assert_eq!(
+        index.claim(10, 500, claimant(2)).unwrap(),
+        Claim::Collision {
+            holder: claimant(1)
+        }
+    );
+    // The same final nonce one block later is free
+    assert_eq!(index.claim(11, 500, claimant(2)).unwrap(), Claim::Claimed);
+    assert_eq!(index.holder(10, 500), Some(&claimant(1)));
+    assert_eq!(index.holder(11, 500), Some(&claimant(2)));
+    assert_eq!(index.len(), 2);
+}
+
+#[test]
+fn resolution_does_not_depend_on_arrival() {
+    let early = claimant(1);
+    let late = Contender {
+        submission_timestamp: 0,
+        ..claimant(2)
+    };
+    for order in [
+        ResolutionOrder::TransactionHash,
+        ResolutionOrder::LegacyTimestamp,
+    ] {
+        let mut forward = CollisionIndex::with_order(order);
+        let mut backward = CollisionIndex::with_order(order);
+        forward.claim(1, 500, early).unwrap();
+        forward.claim(1, 500, late).unwrap();
+        backward.claim(1, 500, late).unwrap();
+        backward.claim(1, 500, early).unwrap();
+        assert_eq!(forward.holder(1, 500), backward.holder(1, 500));
+    }
+
+    let mut index = CollisionIndex::new();
+    index.claim(1, 500, claimant(2)).unwrap();
+    assert_eq!(
+        index.claim(1, 500, claimant(1)).unwrap(),
+        Claim::Displaced { loser: claimant(2) }
+    );
+    // A repeated claim by the holder is not a contest it can lose
+    assert_eq!(index.claim(1, 500, claimant(1)).unwrap(), Claim::Claimed);
+    assert_eq!(index.holder(1, 500), Some(&claimant(1)));
+    assert_eq!(
+        index.claim(1, 500, claimant(2)).unwrap(),
+        Claim::Collision {
+            holder: claimant(1)
+        }
+    );
+}
+
+#[test]
+fn backdating_cannot_starve_honest_transactions() {
+    // The attacker grinds a transaction onto every honest final nonce each
+    // block and stamps it with time zero. Honest losers retry the same signed
+    // transaction at the next height, where TNO moves it to a fresh final
+    // nonce, and the attacker chases them again with a fresh forgery.
+    const HONEST: u64 = 200;
+    const BLOCKS: u64 = 16;
+
+    let included_after = |order: ResolutionOrder| {
+        let mut index = CollisionIndex::with_order(order);
+        let mut pending: Vec<Transaction> = (0..HONEST)
+            .map(|i| transfer(&format!("honest-{i}"), 0, 1_700_000_000_000 + i))
+            .collect();
+        let mut attempts = 0;
+        let mut included = Vec::new();
+        for height in 1..=BLOCKS {
+            for (slot, tx) in pending.iter().enumerate() {
+                let final_nonce = REGULAR_MINER_RANGE_START + slot as u64;
+                index.claim(height, final_nonce, Contender::of(tx)).unwrap();
+                attempts += 1;
+                let forged = transfer("mallory", attempts, 0);
+                index
+                    .claim(height, final_nonce, Contender::of(&forged))
+                    .unwrap();
+            }
+            let (won, lost): (Vec<_>, Vec<_>) =
+                pending.into_iter().enumerate().partition(|(slot, tx)| {
+                    let final_nonce = REGULAR_MINER_RANGE_START + *slot as u64;
+                    index.holder(height, final_nonce) == Some(&Contender::of(tx))
+                });
+            included.push(won.len());
+            pending = lost.into_iter().map(|(_, tx)| tx).collect();
+        }
+        (included, pending.len())
+    };
+
+    // Legacy: the backdated transaction wins every contest
+    let (included, starved) = included_after(ResolutionOrder::LegacyTimestamp);
+    assert!(included.iter().all(|&count| count == 0));
+    assert_eq!(starved as u64, HONEST);
+
+    // Hash order: each forgery beats an unchanged honest transaction only
+    // with the chance its hash ranks lower, so a transaction ranked at x
+    // survives k blocks of chasing with probability x^k
+    let (included, starved) = included_after(ResolutionOrder::TransactionHash);
+    assert!((60..=140).contains(&included[0]), "{included:?}");
+    assert!(
+        starved as u64 <= HONEST / 8,
+        "{starved} starved: {included:?}"
+    );
+}
+
+#[test]
+fn finalization_prunes_and_closes_heights() {
+    let mut index = CollisionIndex::new();
+    for height in 1..=5 {
+        index.claim(height, 7, claimant(height)).unwrap();
+    }
+    index.finalize(3).unwrap();
+    assert_eq!(index.finalized(), Some(3));
+    assert_eq!(index.open_heights(), 2);
+    assert_eq!(index.holder(3, 7), None);
+    assert_eq!(index.holder(4, 7), Some(&claimant(4)));
+    assert!(matches!(
+        index.claim(3, 8, claimant(9)),
+        Err(CollisionIndexError::Finalized {
+            height: 3,
+            finalized: 3
+        })
+    ));
+
+    // Finalizing backwards changes nothing
+    index.finalize(2).unwrap();
+    assert_eq!(index.finalized(), Some(3));
+    index.finalize(u64::MAX).unwrap();
+    assert!(index.is_empty());
+}
+
+// About a minute in a debug build; run with
+// `cargo test -p i-protocol-core --test collision -- --ignored`
+#[test]
+#[ignore]
+fn ten_million_transactions_stay_within_the_window() {
+    // 10,000 transactions per block over 1,000 heights, each height final
+    // once FINALITY_LAG blocks sit on top of it
+    const HEIGHTS: u64 = 1_000;
+    const PER_HEIGHT: u64 = 10_000;
+    const FINALITY_LAG: u64 = 3;
+
+    let mut index = CollisionIndex::new();
+    let mut peak = 0;
+    for height in 1..=HEIGHTS {
+        for tx in 0..PER_HEIGHT {
+            // Every height reuses the same final nonces; none may collide
+            // with a claim from an earlier block
+            let final_nonce = REGULAR_MINER_RANGE_START + tx;
+            let claim = index.claim(height, final_nonce, claimant(height * PER_HEIGHT + tx));
+            assert_eq!(claim.unwrap(), Claim::Claimed);
+        }
+        peak = peak.max(index.len());
+        if let Some(final_height) = height.checked_sub(FINALITY_LAG) {
+            index.finalize(final_height).unwrap();
+        }
+        assert!(index.open_heights() as u64 <= FINALITY_LAG);
+    }
+    assert_eq!(peak as u64, (FINALITY_LAG + 1) * PER_HEIGHT);
+    assert_eq!(index.len() as u64, FINALITY_LAG * PER_HEIGHT);
+}
+
+#[test]
+fn disk_backend_survives_a_restart() {
+    let dir = scratch_dir("restart");
+    {
+        let mut index = CollisionIndex::open(&dir).unwrap();
+        index.claim(1, 100, claimant(1)).unwrap();
+        index.claim(2, 100, claimant(2)).unwrap();
+        index.claim(2, 200, claimant(3)).unwrap();
+        index.finalize(1).unwrap();
+        index.claim(3, 100, claimant(4)).unwrap();
+    }
+    assert!(!dir.join("1.claims").exists());
+
+    let mut index = CollisionIndex::open(&dir).unwrap();
+    assert_eq!(index.finalized(), Some(1));
+    assert_eq!(index.len(), 3);
+    assert_eq!(
+        index.claim(2, 100, claimant(5)).unwrap(),
+        Claim::Collision {
+            holder: claimant(2)
+        }
+    );
+    assert!(matches!(
+        index.claim(1, 300, claimant(6)),
+        Err(CollisionIndexError::Finalized { .. })
+    ));
+
+    // Claims made after reopening are logged too
+    index.claim(3, 300, claimant(7)).unwrap();
+    assert_eq!(
+        index.claim(2, 100, claimant(0)).unwrap(),
+        Claim::Displaced { loser: claimant(2) }
+    );
+    drop(index);
+    let index = CollisionIndex::open(&dir).unwrap();
+    assert_eq!(index.holder(3, 300), Some(&claimant(7)));
+    // Both claims on the contested nonce are logged; reloading settles it
+    assert_eq!(index.holder(2, 100), Some(&claimant(0)));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn failed_finalization_leaves_the_index_unchanged() {
+    let dir = scratch_dir("finalize-failure");
+    let mut index = CollisionIndex::open(&dir).unwrap();
+    index.claim(1, 100, claimant(1)).unwrap();
+    index.claim(2, 100, claimant(2)).unwrap();
+
+    // A directory where the staged marker goes makes its write fail
+    std::fs::create_dir(dir.join("finalized.tmp")).unwrap();
+    assert!(matches!(index.finalize(1), Err(CollisionIndexError::Io(_))));
+    assert_eq!(index.finalized(), None);
+    assert_eq!(index.holder(1, 100), Some(&claimant(1)));
+    assert_eq!(index.len(), 2);
+    assert!(dir.join("1.claims").exists());
+
+    // Memory still agrees with what a restart reads back
+    let reopened = CollisionIndex::open(&dir).unwrap();
+    assert_eq!(reopened.finalized(), None);
+    assert_eq!(reopened.holder(1, 100), Some(&claimant(1)));
+    drop(reopened);
+
+    std::fs::remove_dir(dir.join("finalized.tmp")).unwrap();
+    index.finalize(1).unwrap();
+    assert_eq!(index.finalized(), Some(1));
+    assert_eq!(index.holder(1, 100), None);
+    assert!(!dir.join("1.claims").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn torn_records_are_cut_on_open() {
+    use std::io::Write;
+
+    let dir = scratch_dir("torn");
+    {
+        let mut index = CollisionIndex::open(&dir).unwrap();
+        index.claim(4, 100, claimant(1)).unwrap();
+    }
+    // A crash midway through the second record
+    let mut log = std::fs::OpenOptions::new()
+        .append(true)
+        .open(dir.join("4.claims"))
+        .unwrap();
+    log.write_all(&[0, 0, 0, 0, 0, 0, 0, 200, 9, 9]).unwrap();
+    drop(log);
+
+    let mut index = CollisionIndex::open(&dir).unwrap();
+    assert_eq!(index.len(), 1);
+    assert_eq!(index.holder(4, 100), Some(&claimant(1)));
+
+    // Later records are not misaligned by the torn one
+    index.claim(4, 300, claimant(2)).unwrap();
+    drop(index);
+    let index = CollisionIndex::open(&dir).unwrap();
+    assert_eq!(index.holder(4, 300), Some(&claimant(2)));
+    assert_eq!(index.len(), 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
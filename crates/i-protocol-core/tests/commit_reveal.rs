@@ -0,0 +1,410 @@
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use i_protocol_core::commit_reveal::{
+    CommitReveal, CommitRevealError, CommitSecret, Phase, RoundMessage, RoundOutcome, RoundWindow,
+};
+use i_protocol_core::dura::{master_seed, RangeTable};
+use i_protocol_core::params::NON_REVEAL_EXCLUSION_ROUNDS;
+use i_protocol_core::registry::MinerRegistry;
+use i_protocol_core::signing::{Ed25519Signer, SignatureError};
+use i_protocol_core::MinerIdentity;
+
+const MINERS: u32 = 6;
+
+fn miner(id: u32) -> MinerIdentity {
+    MinerIdentity::virtual_miner(id, 1_700_000_000)
+}
+
+fn identity_hash(id: u32) -> String {
+    miner(id).identity_hash()
+}
+
+// Miners 0..MINERS, active from genesis
+fn registry() -> MinerRegistry {
+    MinerRegistry::with_genesis((0..MINERS).map(miner)).unwrap()
+}
+
+fn signer(secret: &CommitSecret) -> Ed25519Signer {
+    let id = (0..MINERS)
+        .find(|&id| identity_hash(id) == secret.identity_hash)
+        .expect("secret of a registered miner");
+    MinerIdentity::virtual_signer(id)
+}
+
+fn commit(secret: &CommitSecret) -> RoundMessage {
+    secret.commit_message(&signer(secret))
+}
+
+fn reveal(secret: &CommitSecret) -> RoundMessage {
+    secret.reveal_message(&signer(secret))
+}
+
+fn secrets(ids: &[u32], round: u64, rng: &mut ChaCha20Rng) -> Vec<CommitSecret> {
+    ids.iter()
+        .map(|&id| CommitSecret::generate(&identity_hash(id), round, rng))
+        .collect()
+}
+
+fn secrets_for(previous: &[CommitSecret], round: u64, rng: &mut ChaCha20Rng) -> Vec<CommitSecret> {
+    previous
+        .iter()
+        .map(|s| CommitSecret::generate(&s.identity_hash, round, rng))
+        .collect()
+}
+
+// Commits every secret in the second block of the round and reveals those
+// in `revealing` at the start of its reveal phase
+fn run_round(state: &mut CommitReveal, secrets: &[CommitSecret], revealing: &[&CommitSecret]) {
+    let window = RoundWindow::new(secrets[0].round);
+    let commits: Vec<RoundMessage> = secrets.iter().map(commit).collect();
+    let reveals: Vec<RoundMessage> = revealing.iter().map(|s| reveal(s)).collect();
+    let registry = registry();
+    state
+        .apply_block(window.commit_start + 1, &commits, &registry)
+        .unwrap();
+    state
+        .apply_block(window.reveal_start, &reveals, &registry)
+        .unwrap();
+}
+
+// Applies the first block past the reveal phase
+fn close(state: &mut CommitReveal, round: u64) {
+    state
+        .apply_block(RoundWindow::new(round).reveal_end, &[], &registry())
+        .unwrap();
+}
+
+#[test]
+fn revealed_identities_make_up_the_outcome() {
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+    let secrets = secrets(&[0, 1, 2, 3], 0, &mut rng);
+    let mut state = CommitReveal::new();
+    run_round(&mut state, &secrets, &secrets.iter().collect::<Vec<_>>());
+    assert_eq!(state.outcome(0), None);
+
+    close(&mut state, 0);
+    let outcome = state.outcome(0).unwrap();
+    let mut expected: Vec<String> = secrets.iter().map(|s| s.identity_hash.clone()).collect();
+    expected.sort();
+    assert_eq!(outcome.revealed_identities(), expected);
+    assert!(outcome.withheld.is_empty());
+    for secret in &secrets {
+        assert_eq!(outcome.revealed[&secret.identity_hash], secret.nonce);
+    }
+}
+
+#[test]
+fn master_seed_mixes_in_every_revealed_nonce() {
+    let mut rng = ChaCha20Rng::seed_from_u64(6);
+    let secrets = secrets(&[0, 1, 2], 0, &mut rng);
+    let mut state = CommitReveal::new();
+    run_round(&mut state, &secrets, &secrets.iter().collect::<Vec<_>>());
+    close(&mut state, 0);
+    let outcome = state.outcome(0).unwrap().clone();
+    let seed = outcome.master_seed("prev");
+    assert_eq!(seed.len(), 64);
+    assert_ne!(seed, outcome.master_seed("other"));
+
+    // Same identities, one different nonce: the commitments alone do not
+    // fix the seed
+    let mut redrawn = secrets.clone();
+    redrawn[1] = CommitSecret::generate(&secrets[1].identity_hash, 0, &mut rng);
+    let mut other = CommitReveal::new();
+    run_round(&mut other, &redrawn, &redrawn.iter().collect::<Vec<_>>());
+    close(&mut other, 0);
+    let other = other.outcome(0).unwrap();
+    assert_eq!(other.revealed_identities(), outcome.revealed_identities());
+    assert_ne!(other.master_seed("prev"), seed);
+}
+
+#[test]
+fn only_active_identities_with_their_own_key_take_part() {
+    let mut rng = ChaCha20Rng::seed_from_u64(7);
+    let window = RoundWindow::new(0);
+    let registry = registry();
+    let mut state = CommitReveal::new();
+
+    // Committing in a victim's name to get it excluded
+    let victim = CommitSecret::generate(&identity_hash(0), 0, &mut rng);
+    let forged = victim.commit_message(&MinerIdentity::virtual_signer(1));
+    assert_eq!(
+        state.apply_block(window.commit_start, &[forged], &registry),
+        Err(CommitRevealError::InvalidSignature {
+            identity_hash: victim.identity_hash.clone(),
+            error: SignatureError::Invalid
+        })
+    );
+
+    // A signature does not carry over to another round
+    let mut replayed = commit(&victim);
+    replayed.sign(1, &signer(&victim));
+    assert!(matches!(
+        state.apply_block(window.commit_start, &[replayed], &registry),
+        Err(CommitRevealError::InvalidSignature { .. })
+    ));
+
+    let outsider = MinerIdentity::virtual_miner(MINERS, 1_700_000_000);
+    let secret = CommitSecret::generate(&outsider.identity_hash(), 0, &mut rng);
+    assert_eq!(
+        state.apply_block(
+            window.commit_start,
+            &[secret.commit_message(&MinerIdentity::virtual_signer(MINERS))],
+            &registry
+        ),
+        Err(CommitRevealError::NotActive {
+            identity_hash: outsider.identity_hash(),
+            round: 0
+        })
+    );
+
+    state
+        .apply_block(window.commit_start, &[commit(&victim)], &registry)
+        .unwrap();
+    assert!(!state.is_excluded(&victim.identity_hash, 1));
+}
+
+#[test]
+fn nonces_are_drawn_not_derived() {
+    let hash = identity_hash(5);
+    let mut rng = ChaCha20Rng::seed_from_u64(2);
+    let first = CommitSecret::generate(&hash, 3, &mut rng);
+    let second = CommitSecret::generate(&hash, 3, &mut rng);
+    assert_ne!(first.nonce, second.nonce);
+    assert_ne!(first.commitment(), second.commitment());
+}
+
+#[test]
+fn messages_are_bound_to_their_phase() {
+    let mut rng = ChaCha20Rng::seed_from_u64(3);
+    let secret = CommitSecret::generate(&identity_hash(0), 0, &mut rng);
+    let window = RoundWindow::new(0);
+    let registry = registry();
+    let mut state = CommitReveal::new();
+
+    assert_eq!(
+        state.apply_block(window.commit_start, &[reveal(&secret)], &registry),
+        Err(CommitRevealError::WrongPhase {
+            height: window.commit_start,
+            phase: Phase::Commit
+        })
+    );
+    state
+        .apply_block(window.commit_start, &[commit(&secret)], &registry)
+        .unwrap();
+
+    // No commitments once reveals have begun
+    let late = CommitSecret::generate(&identity_hash(1), 0, &mut rng);
+    assert_eq!(
+        state.apply_block(window.reveal_start, &[commit(&late)], &registry),
+        Err(CommitRevealError::WrongPhase {
+            height: window.reveal_start,
+            phase: Phase::Reveal
+        })
+    );
+
+    // A reveal after the deadline lands in the next round, where its
+    // signature no longer verifies and, re-signed, it is out of phase
+    assert!(matches!(
+        state.apply_block(window.reveal_end, &[reveal(&secret)], &registry),
+        Err(CommitRevealError::InvalidSignature { .. })
+    ));
+    let mut late = reveal(&secret);
+    late.sign(1, &signer(&secret));
+    assert_eq!(
+        state.apply_block(window.reveal_end, &[late], &registry),
+        Err(CommitRevealError::WrongPhase {
+            height: window.reveal_end,
+            phase: Phase::Commit
+        })
+    );
+    assert_eq!(
+        state.apply_block(window.reveal_end - 1, &[], &registry),
+        Ok(())
+    );
+    assert_eq!(
+        state.apply_block(window.reveal_end - 1, &[], &registry),
+        Err(CommitRevealError::HeightNotIncreasing {
+            last: window.reveal_end - 1,
+            found: window.reveal_end - 1
+        })
+    );
+}
+
+#[test]
+fn reveals_must_open_the_commitment() {
+    let mut rng = ChaCha20Rng::seed_from_u64(4);
+    let secret = CommitSecret::generate(&identity_hash(0), 0, &mut rng);
+    let window = RoundWindow::new(0);
+    let registry = registry();
+    let mut state = CommitReveal::new();
+    state
+        .apply_block(window.commit_start, &[commit(&secret)], &registry)
+        .unwrap();
+    assert_eq!(
+        state.apply_block(window.commit_start + 1, &[commit(&secret)], &registry),
+        Err(CommitRevealError::AlreadyCommitted {
+            identity_hash: secret.identity_hash.clone()
+        })
+    );
+
+    let mut guessed = secret.clone();
+    guessed.nonce[0] ^= 1;
+    assert_eq!(
+        state.apply_block(window.reveal_start, &[reveal(&guessed)], &registry),
+        Err(CommitRevealError::CommitmentMismatch {
+            identity_hash: secret.identity_hash.clone()
+        })
+    );
+    let stranger = CommitSecret::generate(&identity_hash(1), 0, &mut rng);
+    assert_eq!(
+        state.apply_block(window.reveal_start, &[reveal(&stranger)], &registry),
+        Err(CommitRevealError::NotCommitted {
+            identity_hash: stranger.identity_hash.clone()
+        })
+    );
+
+    // A rejected block changes nothing
+    assert_eq!(
+        state.apply_block(
+            window.reveal_start,
+            &[reveal(&secret), reveal(&secret)],
+            &registry
+        ),
+        Err(CommitRevealError::AlreadyRevealed {
+            identity_hash: secret.identity_hash.clone()
+        })
+    );
+    state
+        .apply_block(window.reveal_start, &[reveal(&secret)], &registry)
+        .unwrap();
+    close(&mut state, 0);
+    assert_eq!(
+        state.outcome(0).unwrap().revealed_identities(),
+        vec![secret.identity_hash]
+    );
+}
+
+#[test]
+fn withholding_drops_the_identity_and_excludes_it() {
+    // An attacker holding two of five committed identities waits for the
+    // honest reveals, then withholds one to pick between two master seeds
+    let mut rng = ChaCha20Rng::seed_from_u64(5);
+    let secrets = secrets(&[0, 1, 2, 3, 4], 0, &mut rng);
+    let (honest, attacker) = secrets.split_at(3);
+    let withheld = &attacker[1];
+
+    let registry = registry();
+    let mut state = CommitReveal::new();
+    let mut revealing: Vec<&CommitSecret> = honest.iter().collect();
+    revealing.push(&attacker[0]);
+    run_round(&mut state, &secrets, &revealing);
+    close(&mut state, 0);
+
+    let outcome = state.outcome(0).unwrap().clone();
+    assert_eq!(outcome.withheld, vec![withheld.identity_hash.clone()]);
+    assert!(!outcome.revealed.contains_key(&withheld.identity_hash));
+    assert_eq!(outcome.revealed.len(), 4);
+
+    // The seed moves, but the withheld identity pays for it in later rounds
+    let mut full = outcome.clone();
+    full.revealed
+        .insert(withheld.identity_hash.clone(), withheld.nonce);
+    assert_ne!(outcome.master_seed("prev"), full.master_seed("prev"));
+
+    let until_round = 1 + NON_REVEAL_EXCLUSION_ROUNDS;
+    for round in 1..until_round {
+        assert!(state.is_excluded(&withheld.identity_hash, round));
+        let retry = CommitSecret::generate(&withheld.identity_hash, round, &mut rng);
+        assert_eq!(
+            state.apply_block(
+                RoundWindow::new(round).commit_start + 1,
+                &[commit(&retry)],
+                &registry
+            ),
+            Err(CommitRevealError::Excluded {
+                identity_hash: withheld.identity_hash.clone(),
+                until_round
+            })
+        );
+        // Honest miners carry on meanwhile
+        let secrets = secrets_for(honest, round, &mut rng);
+        run_round(&mut state, &secrets, &secrets.iter().collect::<Vec<_>>());
+        close(&mut state, round);
+        let outcome = state.outcome(round).unwrap();
+        assert!(!outcome.revealed.contains_key(&withheld.identity_hash));
+    }
+
+    assert!(!state.is_excluded(&withheld.identity_hash, until_round));
+    let retry = CommitSecret::generate(&withheld.identity_hash, until_round, &mut rng);
+    run_round(&mut state, std::slice::from_ref(&retry), &[&retry]);
+    close(&mut state, until_round);
+    assert_eq!(
+        state.outcome(until_round).unwrap().revealed_identities(),
+        vec![retry.identity_hash]
+    );
+}
+
+#[test]
+fn withholding_only_removes_the_withholder() {
+    // The same commitments twice; in the second run miner 4 withholds.
+    // Miner 5 is registered but never commits
+    let mut rng = ChaCha20Rng::seed_from_u64(8);
+    let secrets = secrets(&[0, 1, 2, 3, 4], 0, &mut rng);
+    let attacker = &secrets[4];
+
+    let mut revealing = CommitReveal::new();
+    run_round(
+        &mut revealing,
+        &secrets,
+        &secrets.iter().collect::<Vec<_>>(),
+    );
+    close(&mut revealing, 0);
+    let mut withholding = CommitReveal::new();
+    run_round(
+        &mut withholding,
+        &secrets,
+        &secrets[..4].iter().collect::<Vec<_>>(),
+    );
+    close(&mut withholding, 0);
+
+    let full = revealing.outcome(0).unwrap();
+    let outcome = withholding.outcome(0).unwrap();
+    let mut honest = full.revealed.clone();
+    honest.remove(&attacker.identity_hash);
+    assert_eq!(outcome.revealed, honest);
+    assert_eq!(outcome.withheld, vec![attacker.identity_hash.clone()]);
+
+    // DURA covers exactly the reveals
+    let owners = |outcome: &RoundOutcome| {
+        let mut owners: Vec<String> = outcome
+            .assign_ranges("prev")
+            .ranges
+            .into_iter()
+            .map(|range| range.identity_hash)
+            .collect();
+        owners.sort();
+        owners
+    };
+    assert_eq!(owners(full), full.revealed_identities());
+    assert_eq!(owners(outcome), outcome.revealed_identities());
+    assert!(!owners(outcome).contains(&identity_hash(5)));
+
+    // Keyed by the revealed nonces, not by the public identity hashes
+    let assignment = outcome.assign_ranges("prev");
+    assert_eq!(assignment.master_seed, outcome.master_seed("prev"));
+    assert_ne!(
+        assignment.master_seed,
+        master_seed("prev", &outcome.revealed_identities())
+    );
+    assert_eq!(
+        RangeTable::for_round("prev", outcome, &(0..MINERS).map(miner).collect::<Vec<_>>()),
+        assignment.range_table()
+    );
+
+    // Exclusion falls on the withholder alone
+    for secret in &secrets[..4] {
+        assert!(!withholding.is_excluded(&secret.identity_hash, 1));
+    }
+    assert!(withholding.is_excluded(&attacker.identity_hash, 1));
+}
@@ -0,0 +1,95 @@
+use i_protocol_core::difficulty::{Difficulty, DifficultyTarget};
+use i_protocol_core::miner::meets_target;
+use i_protocol_core::params::{NONCES_PER_MINER, RETARGET_INTERVAL};
+
+fn fallback_probability(target: DifficultyTarget, miners: u32, work_per_miner: u64) -> f64 {
+    (-target.success_probability() * miners as f64 * work_per_miner as f64).exp()
+}
+
+#[test]
+fn target_leaves_nine_percent_to_the_system_miner() {
+    for miners in [1, 10, 1_500, 100_000] {
+        let target = Difficulty::default().target(miners);
+        let q = fallback_probability(target, miners, NONCES_PER_MINER);
+        assert!((q - 0.09).abs() < 1e-4, "{miners} miners: q = {q}");
+    }
+}
+
+#[test]
+fn target_scales_inversely_with_miner_count() {
+    let one = DifficultyTarget::for_network(100, NONCES_PER_MINER).threshold();
+    let two = DifficultyTarget::for_network(200, NONCES_PER_MINER).threshold();
+    assert!(one / 2 - two <= 1);
+    assert_eq!(
+        DifficultyTarget::for_network(0, NONCES_PER_MINER),
+        DifficultyTarget::for_network(1, NONCES_PER_MINER)
+    );
+}
+
+#[test]
+fn digest_form_agrees_with_threshold() {
+    let target = DifficultyTarget::from_threshold(0x0000_1234_0000_0000);
+    let mut hash = [0u8; 32];
+    hash[..8].copy_from_slice(&0x0000_1234_0000_0000u64.to_be_bytes());
+    hash[31] = 0xff;
+    assert!(target.is_met_by(&hash));
+    assert!(meets_target(&hash, &target.to_digest()));
+
+    hash[7] = 1;
+    assert!(!target.is_met_by(&hash));
+    assert!(!meets_target(&hash, &target.to_digest()));
+}
+
+#[test]
+fn retarget_holds_at_the_target_rate() {
+    let difficulty = Difficulty::default();
+    let next = difficulty.retarget(RETARGET_INTERVAL, 90);
+    let drift = next.work_per_miner.abs_diff(difficulty.work_per_miner);
+    assert!(drift * 100 < difficulty.work_per_miner, "drift {drift}");
+}
+
+#[test]
+fn retarget_moves_toward_the_target_rate() {
+    let difficulty = Difficulty::default();
+    // Too few SysBlocks: the target was too easy, so assume more work
+    let harder = difficulty.retarget(RETARGET_INTERVAL, 30);
+    assert!(harder.work_per_miner > difficulty.work_per_miner);
+    assert!(harder.target(10) < difficulty.target(10));
+
+    let easier = difficulty.retarget(RETARGET_INTERVAL, 300);
+    assert!(easier.work_per_miner < difficulty.work_per_miner);
+
+    // After the correction the observed work reproduces q*
+    let true_work = NONCES_PER_MINER / 2;
+    let q = fallback_probability(difficulty.target(50), 50, true_work);
+    let sys_blocks = (q * RETARGET_INTERVAL as f64).round() as u64;
+    let corrected = difficulty.retarget(RETARGET_INTERVAL, sys_blocks);
+    let q = fallback_probability(corrected.target(50), 50, true_work);
+    assert!((q - 0.09).abs() < 0.01, "q = {q}");
+}
+
+#[test]
+fn retarget_is_bounded() {
+    let difficulty = Difficulty::default();
+    assert_eq!(
+        difficulty
+            .retarget(100 * RETARGET_INTERVAL, 0)
+            .work_per_miner,
+        NONCES_PER_MINER * 4
+    );
+    assert_eq!(
+        difficulty
+            .retarget(RETARGET_INTERVAL, RETARGET_INTERVAL)
+            .work_per_miner,
+        NONCES_PER_MINER / 4
+    );
+    assert_eq!(difficulty.retarget(0, 0), difficulty);
+}
+
+#[test]
+fn retarget_heights() {
+    assert!(!Difficulty::is_retarget_height(0));
+    assert!(!Difficulty::is_retarget_height(RETARGET_INTERVAL - 1));
+    assert!(Difficulty::is_retarget_height(RETARGET_INTERVAL));
+    assert!(Difficulty::is_retarget_height(3 * RETARGET_INTERVAL));
+}
@@ -0,0 +1,117 @@
+use i_protocol_core::dura::{
+    assign_ranges, deterministic_shuffle, master_seed, master_seed_digest, RangeTable,
+};
+use i_protocol_core::hash::from_hex;
+use i_protocol_core::params::{NONCES_PER_MINER, REGULAR_MINER_RANGE_START};
+use i_protocol_core::MinerIdentity;
+
+fn miners(count: u32) -> Vec<MinerIdentity> {
+    (0..count)
+        .map(|id| MinerIdentity::virtual_miner(id, 1_700_000_000))
+        .collect()
+}
+
+#[test]
+fn master_seed_ignores_identity_order() {
+    let hashes: Vec<String> = miners(8).iter().map(MinerIdentity::identity_hash).collect();
+    let mut reversed = hashes.clone();
+    reversed.reverse();
+    assert_eq!(master_seed("prev", &hashes), master_seed("prev", &reversed));
+    assert_ne!(master_seed("prev", &hashes), master_seed("other", &hashes));
+}
+
+#[test]
+fn shuffle_is_a_permutation() {
+    let mut shuffled = deterministic_shuffle(&master_seed_digest("seed", &[]), 100);
+    shuffled.sort_unstable();
+    assert_eq!(shuffled, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn assignment_is_deterministic() {
+    let miners = miners(50);
+    assert_eq!(
+        assign_ranges("block", &miners),
+        assign_ranges("block", &miners)
+    );
+}
+
+#[test]
+fn ranges_are_contiguous_and_disjoint() {
+    let assignment = assign_ranges("block", &miners(50));
+    let mut expected_start = REGULAR_MINER_RANGE_START;
+    for range in &assignment.ranges {
+        assert_eq!(range.start_nonce, expected_start);
+        assert_eq!(range.end_nonce - range.start_nonce + 1, NONCES_PER_MINER);
+        expected_start = range.end_nonce + 1;
+    }
+
+    let mut owners: Vec<u32> = assignment.ranges.iter().map(|r| r.miner_id).collect();
+    owners.sort_unstable();
+    assert_eq!(owners, (0..50).collect::<Vec<_>>());
+}
+
+#[test]
+fn range_table_follows_the_shuffle() {
+    let assignment = assign_ranges("block", &miners(50));
+    let table = assignment.range_table();
+    assert_eq!(table.len(), 50);
+    for range in &assignment.ranges {
+        for nonce in [range.start_nonce, range.start_nonce + 1, range.end_nonce] {
+            assert_eq!(table.range_of(nonce), Some(range));
+            assert_eq!(table.owner_of(nonce), Some(range.identity_hash.as_str()));
+        }
+    }
+    assert_eq!(table.owner_of(REGULAR_MINER_RANGE_START - 1), None);
+    assert_eq!(table.owner_of(u64::MAX), None);
+    assert!(RangeTable::for_block("block", &[]).is_empty());
+}
+
+#[test]
+fn shuffle_matches_published_vectors() {
+    let file: serde_json::Value =
+        serde_json::from_str(include_str!("../test-vectors/dura_shuffle.json")).unwrap();
+    let vectors = file["vectors"].as_array().unwrap();
+    assert!(!vectors.is_empty());
+
+    for vector in vectors {
+        let seed = from_hex(vector["seed"].as_str().unwrap()).unwrap();
+        let miner_count = vector["miner_count"].as_u64().unwrap() as usize;
+        let expected: Vec<usize> = vector["permutation"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|index| index.as_u64().unwrap() as usize)
+            .collect();
+        assert_eq!(
+            deterministic_shuffle(&seed, miner_count),
+            expected,
+            "{vector}"
+        );
+    }
+}
+
+#[test]
+fn shuffle_positions_are_unbiased() {
+    // Every miner of three should land in every position about a third of
+    // the time; a modulo-biased draw over a small state would not.
+    let mut counts = [[0u32; 3]; 3];
+    for round in 0..3_000u32 {
+        let seed = master_seed_digest(&round.to_string(), &[]);
+        for (position, miner) in deterministic_shuffle(&seed, 3).into_iter().enumerate() {
+            counts[miner][position] += 1;
+        }
+    }
+    for row in counts {
+        for count in row {
+            assert!((850..1_150).contains(&count), "{counts:?}");
+        }
+    }
+}
+
+#[test]
+fn master_seed_hex_round_trips() {
+    let digest = master_seed_digest("prev", &[]);
+    assert_eq!(from_hex(&master_seed("prev", &[])), Some(digest));
+    assert_eq!(from_hex("zz"), None);
+}
@@ -0,0 +1,41 @@
+use i_protocol_core::emission::{
+    block_reward, cumulative_emission, EFFECTIVE_TOTAL_SUPPLY, GENESIS_DUST_BURN,
+    GOLDEN_BLOCK_REWARD, HALVING_INTERVAL, HALVING_PERIODS, INITIAL_BLOCK_REWARD,
+    STANDARD_MINING_SUPPLY, SUBUNIT_RATIO, THEORETICAL_SUPPLY_I,
+};
+
+#[test]
+fn supply_constants_are_consistent() {
+    let theoretical = THEORETICAL_SUPPLY_I as u128 * SUBUNIT_RATIO;
+    assert_eq!(theoretical - GENESIS_DUST_BURN, EFFECTIVE_TOTAL_SUPPLY);
+    assert_eq!(
+        EFFECTIVE_TOTAL_SUPPLY - GOLDEN_BLOCK_REWARD,
+        STANDARD_MINING_SUPPLY
+    );
+}
+
+#[test]
+fn reward_halves_every_interval() {
+    assert_eq!(block_reward(0), INITIAL_BLOCK_REWARD);
+    assert_eq!(block_reward(HALVING_INTERVAL - 1), INITIAL_BLOCK_REWARD);
+    assert_eq!(block_reward(HALVING_INTERVAL), INITIAL_BLOCK_REWARD / 2);
+    assert_eq!(block_reward(2 * HALVING_INTERVAL), INITIAL_BLOCK_REWARD / 4);
+    assert_eq!(block_reward(HALVING_PERIODS * HALVING_INTERVAL), 0);
+}
+
+#[test]
+fn emission_never_exceeds_standard_supply() {
+    let total = cumulative_emission(u64::MAX);
+    assert_eq!(
+        total,
+        cumulative_emission(HALVING_PERIODS * HALVING_INTERVAL)
+    );
+    assert!(total <= STANDARD_MINING_SUPPLY);
+}
+
+#[test]
+fn first_period_emits_about_495k() {
+    // Years 1-2 of the emission table: ~495,000 I.
+    let first_period = cumulative_emission(HALVING_INTERVAL) / SUBUNIT_RATIO;
+    assert_eq!(first_period, 494_868);
+}
@@ -0,0 +1,113 @@
+use i_protocol_core::dura::master_seed;
+use i_protocol_core::encoding::{CanonicalEncode, PreimageEncoder, PreimageKind, ENCODING_VERSION};
+use i_protocol_core::system_miner::{system_nonce, system_nonce_preimage};
+use i_protocol_core::tno::tno_preimage;
+use i_protocol_core::{BlockchainState, MinerIdentity};
+
+fn state(height: u64, previous_hash: &str, timestamp: u64) -> BlockchainState {
+    BlockchainState {
+        height,
+        previous_hash: previous_hash.to_string(),
+        timestamp,
+        failed_miner_count: 0,
+        active_miners: 100,
+    }
+}
+
+#[test]
+fn preimage_leads_with_version_and_kind() {
+    let bytes = PreimageEncoder::new(PreimageKind::Commitment)
+        .u64(7)
+        .finish();
+    assert_eq!(bytes[0], ENCODING_VERSION);
+    assert_eq!(bytes[1], PreimageKind::Commitment as u8);
+    assert_eq!(&bytes[2..], &7u64.to_be_bytes());
+}
+
+#[test]
+fn fields_are_length_prefixed() {
+    let bytes = PreimageEncoder::new(PreimageKind::MasterSeed)
+        .str("ab")
+        .strs(&["c"])
+        .finish();
+    assert_eq!(
+        bytes,
+        [1, 6, 0, 0, 0, 2, b'a', b'b', 0, 0, 0, 1, 0, 0, 0, 1, b'c']
+    );
+}
+
+#[test]
+fn kinds_separate_identical_fields() {
+    let a = PreimageEncoder::new(PreimageKind::Commitment)
+        .str("x")
+        .finish();
+    let b = PreimageEncoder::new(PreimageKind::SecretNonce)
+        .str("x")
+        .finish();
+    assert_ne!(a, b);
+}
+
+// Each pair below renders identically under `format!` concatenation.
+
+#[test]
+fn tno_nonce_and_tx_hash_boundary() {
+    let s = state(5, "prev", 0);
+    assert_eq!(format!("{}{}", 12, "3ab"), format!("{}{}", 123, "ab"));
+    assert_ne!(tno_preimage(12, "3ab", &s), tno_preimage(123, "ab", &s));
+}
+
+#[test]
+fn tno_height_and_previous_hash_boundary() {
+    let a = state(1, "23", 0);
+    let b = state(12, "3", 0);
+    assert_ne!(tno_preimage(0, "tx", &a), tno_preimage(0, "tx", &b));
+}
+
+#[test]
+fn system_nonce_previous_hash_and_timestamp_boundary() {
+    let a = state(9, "ab1", 23);
+    let b = state(9, "ab", 123);
+    assert_ne!(system_nonce_preimage(&a), system_nonce_preimage(&b));
+    assert_ne!(system_nonce(&a), system_nonce(&b));
+}
+
+#[test]
+fn blockchain_state_fields_do_not_bleed() {
+    let a = state(1, "23", 4);
+    let b = state(12, "3", 4);
+    assert_ne!(a.canonical_bytes(), b.canonical_bytes());
+    assert_eq!(a.canonical_bytes(), a.clone().canonical_bytes());
+}
+
+#[test]
+fn miner_identity_field_boundaries() {
+    let base = MinerIdentity::virtual_miner(1, 1_700_000_000_000);
+
+    let mut a = base.clone();
+    a.unique_signature = "sig_ab".into();
+    a.salt = "c".into();
+    let mut b = base.clone();
+    b.unique_signature = "sig_a".into();
+    b.salt = "bc".into();
+    assert_ne!(a.canonical_bytes(), b.canonical_bytes());
+    assert_ne!(a.identity_hash(), b.identity_hash());
+
+    let mut c = base.clone();
+    c.salt = "salt_1".into();
+    c.identity_nonce = 23;
+    let mut d = base;
+    d.salt = "salt_12".into();
+    d.identity_nonce = 3;
+    assert_ne!(c.identity_hash(), d.identity_hash());
+}
+
+#[test]
+fn master_seed_list_boundaries() {
+    let a = ["ab".to_string(), "c".to_string()];
+    let b = ["a".to_string(), "bc".to_string()];
+    assert_ne!(master_seed("prev", &a), master_seed("prev", &b));
+    assert_ne!(
+        master_seed("prev", &a),
+        master_seed("pre", &["vab".to_string(), "c".to_string()])
+    );
+}
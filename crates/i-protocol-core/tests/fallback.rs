@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+use i_protocol_core::clock::{Clock, ManualClock, MonotonicClock};
+use i_protocol_core::fallback::{Activation, BlockArrival, FallbackDriver};
+use i_protocol_core::params::REGULAR_MINING_WINDOW_MS;
+
+const TAU: Duration = Duration::from_millis(REGULAR_MINING_WINDOW_MS);
+const NANO: Duration = Duration::from_nanos(1);
+
+fn driver() -> (ManualClock, FallbackDriver<ManualClock>) {
+    let clock = ManualClock::new();
+    let mut driver = FallbackDriver::new(clock.clone());
+    driver.start_round();
+    (clock, driver)
+}
+
+#[test]
+fn fires_exactly_at_tau() {
+    let (clock, mut driver) = driver();
+    assert_eq!(driver.deadline(), Some(TAU));
+
+    clock.advance(TAU - NANO);
+    assert_eq!(driver.poll(), Activation::Pending { remaining: NANO });
+
+    clock.advance(NANO);
+    assert_eq!(driver.poll(), Activation::Fire);
+    assert_eq!(driver.poll(), Activation::Fired);
+    clock.advance(TAU);
+    assert_eq!(driver.poll(), Activation::Fired);
+}
+
+#[test]
+fn block_inside_window_prevents_activation() {
+    let (clock, mut driver) = driver();
+    clock.advance(TAU - NANO);
+    assert_eq!(driver.on_valid_block(), BlockArrival::InTime);
+
+    clock.advance(TAU);
+    assert_eq!(driver.poll(), Activation::BlockReceived);
+}
+
+#[test]
+fn late_block_does_not_cancel_activation() {
+    let (clock, mut driver) = driver();
+    clock.advance(TAU);
+    // Arrives before the node got round to polling.
+    assert_eq!(driver.on_valid_block(), BlockArrival::Late);
+    assert_eq!(driver.poll(), Activation::Fire);
+
+    clock.advance(Duration::from_millis(10));
+    assert_eq!(driver.on_valid_block(), BlockArrival::Late);
+    assert_eq!(driver.poll(), Activation::Fired);
+}
+
+#[test]
+fn slow_node_fires_on_first_poll_past_deadline() {
+    let (clock, mut driver) = driver();
+    let tick = Duration::from_millis(40);
+    let mut polls = 0;
+    let fired_at = loop {
+        clock.advance(tick);
+        polls += 1;
+        if driver.poll() == Activation::Fire {
+            break clock.now();
+        }
+    };
+    assert_eq!(polls, 7);
+    assert_eq!(fired_at, Duration::from_millis(280));
+}
+
+#[test]
+fn rounds_are_independent() {
+    let (clock, mut driver) = driver();
+    clock.advance(TAU);
+    assert_eq!(driver.poll(), Activation::Fire);
+
+    driver.start_round();
+    assert_eq!(driver.deadline(), Some(TAU * 2));
+    clock.advance(TAU / 2);
+    assert_eq!(driver.poll(), Activation::Pending { remaining: TAU / 2 });
+    assert_eq!(driver.on_valid_block(), BlockArrival::InTime);
+}
+
+#[test]
+fn idle_without_round() {
+    let mut driver = FallbackDriver::new(ManualClock::new());
+    assert_eq!(driver.deadline(), None);
+    assert_eq!(driver.poll(), Activation::Idle);
+    assert_eq!(driver.on_valid_block(), BlockArrival::NoRound);
+}
+
+#[test]
+fn drifting_clocks_each_measure_their_own_window() {
+    // Node clocks run at 0.99x, 1x and 1.01x of reference time and start
+    // their rounds at different offsets. Each node fires after τ on its own
+    // clock, whatever the others read.
+    let rates = [99u32, 100, 101];
+    let clocks: Vec<ManualClock> = rates.iter().map(|_| ManualClock::new()).collect();
+    for (i, clock) in clocks.iter().enumerate() {
+        clock.advance(Duration::from_secs(1_000 * i as u64));
+    }
+    let mut drivers: Vec<_> = clocks
+        .iter()
+        .map(|clock| {
+            let mut driver = FallbackDriver::new(clock.clone());
+            driver.start_round();
+            driver
+        })
+        .collect();
+
+    let step = Duration::from_micros(100);
+    let mut fired_at = [None; 3];
+    for reference_tick in 1..=3_000u32 {
+        for (i, clock) in clocks.iter().enumerate() {
+            clock.advance(step * rates[i] / 100);
+            if drivers[i].poll() == Activation::Fire {
+                fired_at[i] = Some(step * reference_tick);
+            }
+        }
+    }
+
+    let fired_at = fired_at.map(Option::unwrap);
+    assert!(fired_at[2] < fired_at[1] && fired_at[1] < fired_at[0]);
+    assert_eq!(fired_at[1], TAU);
+    for (i, driver) in drivers.iter().enumerate() {
+        let start = Duration::from_secs(1_000 * i as u64);
+        assert_eq!(driver.deadline(), Some(start + TAU));
+    }
+}
+
+#[test]
+fn monotonic_clock_does_not_go_backwards() {
+    let clock = MonotonicClock::new();
+    let a = clock.now();
+    let b = clock.now();
+    assert!(b >= a);
+}
+
+#[test]
+#[should_panic(expected = "backwards")]
+fn manual_clock_rejects_going_backwards() {
+    let clock = ManualClock::new();
+    clock.advance(TAU);
+    clock.advance_to(TAU - NANO);
+}
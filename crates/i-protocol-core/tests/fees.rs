@@ -0,0 +1,55 @@
+use i_protocol_core::fees::{calculate_fee, split_fee, FeeSplit, MAX_FEE};
+
+#[test]
+fn fee_behaviour_table() {
+    // Rows of the "Fee Behavior Examples" table in the tokenomics specification.
+    let table = [
+        (10_000, 10_000),
+        (500_000, 10_000),
+        (999_999, 10_000),
+        (1_000_000, 10_000),
+        (10_000_000, 100_000),
+        (1_000_000_000_000, 10_000_000_000),
+        (10_000_000_000_000, 10_000_000_000),
+    ];
+    for (amount, fee) in table {
+        assert_eq!(calculate_fee(amount), fee, "amount {amount}");
+    }
+}
+
+#[test]
+fn amounts_below_minimum_pay_nothing() {
+    assert_eq!(calculate_fee(0), 0);
+    assert_eq!(calculate_fee(9_999), 0);
+}
+
+#[test]
+fn fee_never_exceeds_cap() {
+    assert_eq!(calculate_fee(u128::MAX), MAX_FEE);
+}
+
+#[test]
+fn split_is_fifty_thirty_twenty() {
+    assert_eq!(
+        split_fee(10_000),
+        FeeSplit {
+            miners: 5_000,
+            development_fund: 3_000,
+            burn: 2_000
+        }
+    );
+}
+
+#[test]
+fn split_burns_rounding_dust() {
+    let split = split_fee(7);
+    assert_eq!(split.miners + split.development_fund + split.burn, 7);
+    assert_eq!(
+        split,
+        FeeSplit {
+            miners: 3,
+            development_fund: 2,
+            burn: 2
+        }
+    );
+}
@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+
+use i_protocol_core::fork_choice::{compare, ForkChoice, ForkEvent};
+use i_protocol_core::hash::Digest32;
+use i_protocol_core::{BlockHeader, BlockProducer, Coinbase};
+
+const ROOT: Digest32 = [0xaa; 32];
+
+fn header(height: u64, parent: Digest32, producer: BlockProducer, seed: u8) -> BlockHeader {
+    BlockHeader {
+        height,
+        previous_hash: parent,
+        timestamp: 1_700_000_000_000 + height * 500 + seed as u64,
+        merkle_root: [seed; 32],
+        failed_miner_count: 0,
+        producer,
+        coinbase: Coinbase::default(),
+    }
+}
+
+fn regular(nonce: u64) -> BlockProducer {
+    BlockProducer::RegularMiner {
+        range_owner: [1; 32],
+        nonce,
+    }
+}
+
+fn sys(system_nonce: u64) -> BlockProducer {
+    BlockProducer::SystemMiner { system_nonce }
+}
+
+// Two competing blocks ordered (winner, loser)
+fn ranked(a: BlockHeader, b: BlockHeader) -> (BlockHeader, BlockHeader) {
+    match compare(&a, &b) {
+        Ordering::Less => (a, b),
+        _ => (b, a),
+    }
+}
+
+#[test]
+fn regular_block_beats_sys_block() {
+    let regular = header(1, ROOT, regular(20_000), 1);
+    let sys = header(1, ROOT, sys(5), 2);
+    assert_eq!(compare(&regular, &sys), Ordering::Less);
+    assert_eq!(compare(&sys, &regular), Ordering::Greater);
+    assert_eq!(compare(&sys, &sys), Ordering::Equal);
+}
+
+#[test]
+fn outcome_is_independent_of_arrival_order() {
+    let blocks = [
+        header(1, ROOT, sys(5), 1),
+        header(1, ROOT, sys(5), 2),
+        header(1, ROOT, regular(20_000), 3),
+        header(1, ROOT, regular(30_000), 4),
+    ];
+    let orders = [[0, 1, 2, 3], [3, 2, 1, 0], [1, 3, 0, 2], [2, 0, 3, 1]];
+    let tips: Vec<_> = orders
+        .iter()
+        .map(|order| {
+            let mut fork_choice = ForkChoice::new(ROOT, 0);
+            for &i in order {
+                fork_choice.insert(blocks[i]);
+            }
+            assert_eq!(fork_choice.candidates(1).len(), blocks.len());
+            fork_choice.tip()
+        })
+        .collect();
+    assert!(tips.iter().all(|tip| *tip == tips[0]));
+}
+
+#[test]
+fn late_regular_block_reorgs_sys_block_chain() {
+    let mut fork_choice = ForkChoice::new(ROOT, 0);
+    let sys1 = header(1, ROOT, sys(5), 1);
+    let sys2 = header(2, sys1.hash(), sys(6), 1);
+    assert_eq!(
+        fork_choice.insert(sys1),
+        ForkEvent::Extended {
+            height: 1,
+            hash: sys1.hash()
+        }
+    );
+    fork_choice.insert(sys2);
+    assert_eq!(fork_choice.tip(), (2, sys2.hash()));
+
+    let late = header(1, ROOT, regular(20_000), 9);
+    let ForkEvent::Reorg(reorg) = fork_choice.insert(late) else {
+        panic!("expected reorg");
+    };
+    assert_eq!(reorg.height, 1);
+    assert_eq!(reorg.disconnected, vec![sys1.hash(), sys2.hash()]);
+    assert_eq!(reorg.connected, vec![late.hash()]);
+    assert_eq!(fork_choice.tip(), (1, late.hash()));
+
+    assert_eq!(fork_choice.insert(late), ForkEvent::Duplicate);
+}
+
+#[test]
+fn losing_sys_block_is_stored_and_branch_reconnects() {
+    let (winner, loser) = ranked(header(1, ROOT, sys(5), 1), header(1, ROOT, sys(5), 2));
+    let mut fork_choice = ForkChoice::new(ROOT, 0);
+    fork_choice.insert(loser);
+    let loser_child = header(2, loser.hash(), sys(7), 3);
+    fork_choice.insert(loser_child);
+    assert_eq!(fork_choice.tip(), (2, loser_child.hash()));
+
+    // Partition heals: the SCRF winner arrives and takes height 1
+    assert!(matches!(fork_choice.insert(winner), ForkEvent::Reorg(_)));
+    assert_eq!(fork_choice.tip(), (1, winner.hash()));
+    assert_eq!(
+        fork_choice.insert(header(3, loser_child.hash(), sys(8), 4)),
+        ForkEvent::Stored
+    );
+
+    let orphan = header(5, [0x55; 32], sys(1), 1);
+    assert_eq!(fork_choice.insert(orphan), ForkEvent::Orphan);
+}
@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use i_protocol_core::hash::{digest_prefix_u64, to_hex, triple_layer_hash, HashDomain};
+
+#[test]
+fn triple_layer_hash_is_deterministic() {
+    let input = b"I_PROTOCOL_SYSTEM_MINER_SALT_2024";
+    for domain in HashDomain::ALL {
+        assert_eq!(
+            triple_layer_hash(domain, input),
+            triple_layer_hash(domain, input)
+        );
+    }
+}
+
+#[test]
+fn domains_are_separated() {
+    let input = b"identical preimage";
+    let digests: HashSet<_> = HashDomain::ALL
+        .iter()
+        .map(|&domain| triple_layer_hash(domain, input))
+        .collect();
+    assert_eq!(digests.len(), HashDomain::ALL.len());
+}
+
+#[test]
+fn matches_layered_reference() {
+    use sha2::Digest as _;
+
+    let input = b"block 42";
+    let layer1 = blake3::Hasher::new_derive_key(HashDomain::Tno.context())
+        .update(input)
+        .finalize();
+    let layer2 = sha2::Sha256::digest(layer1.as_bytes());
+    let layer3 = sha3::Sha3_256::digest(layer2);
+    assert_eq!(
+        triple_layer_hash(HashDomain::Tno, input),
+        <[u8; 32]>::from(layer3)
+    );
+}
+
+#[test]
+fn known_answer_vector() {
+    assert_eq!(
+        to_hex(&triple_layer_hash(HashDomain::SystemNonce, b"")),
+        "20de159e7ab13c89a6b4df1eea7d26bb973d3535456fa651b6d83c59aaa2666f"
+    );
+}
+
+#[test]
+fn prefix_is_big_endian() {
+    let mut digest = [0u8; 32];
+    digest[7] = 1;
+    assert_eq!(digest_prefix_u64(&digest), 1);
+}
@@ -0,0 +1,162 @@
+use i_protocol_core::encoding::{CanonicalEncode, PreimageEncoder, PreimageKind};
+use i_protocol_core::identity::{IdentityComponent, IdentityError, IDENTITY_VERSION, SALT_LEN};
+use i_protocol_core::MinerIdentity;
+
+type Mutation = fn(&mut MinerIdentity);
+
+fn miner(id: u32) -> MinerIdentity {
+    MinerIdentity::virtual_miner(id, 1_700_000_000)
+}
+
+#[test]
+fn virtual_miners_pass_every_validator() {
+    for id in 0..100 {
+        assert_eq!(miner(id).validate(), Ok(()));
+    }
+}
+
+#[test]
+fn canonical_encoding_round_trips() {
+    let identity = miner(7);
+    let bytes = identity.canonical_bytes();
+    assert_eq!(bytes[2], IDENTITY_VERSION);
+    assert_eq!(MinerIdentity::from_canonical_bytes(&bytes), Ok(identity));
+}
+
+#[test]
+fn each_component_has_a_validator() {
+    let cases: Vec<(Mutation, IdentityComponent)> = vec![
+        (|m| m.public_key.truncate(31), IdentityComponent::PublicKey),
+        (
+            |m| m.wallet_address.clear(),
+            IdentityComponent::WalletAddress,
+        ),
+        (
+            |m| m.wallet_address.push(' '),
+            IdentityComponent::WalletAddress,
+        ),
+        (
+            |m| m.creation_timestamp = 0,
+            IdentityComponent::CreationTimestamp,
+        ),
+        (
+            |m| m.unique_signature.truncate(63),
+            IdentityComponent::UniqueSignature,
+        ),
+        (|m| m.salt = vec![0; SALT_LEN], IdentityComponent::Salt),
+        (|m| m.salt.push(1), IdentityComponent::Salt),
+        (
+            |m| m.submission_timestamp = m.creation_timestamp - 1,
+            IdentityComponent::SubmissionTimestamp,
+        ),
+    ];
+
+    for (mutate, component) in cases {
+        let mut identity = miner(3);
+        mutate(&mut identity);
+        let error = identity.validate().unwrap_err();
+        assert_eq!(error.component(), Some(component), "{error}");
+        assert_eq!(
+            MinerIdentity::from_canonical_bytes(&identity.canonical_bytes()),
+            Err(error)
+        );
+    }
+
+    // Any nonce is valid once signed
+    let base = miner(3);
+    let identity = MinerIdentity::new(
+        &MinerIdentity::virtual_signer(3),
+        base.wallet_address,
+        base.creation_timestamp,
+        base.salt,
+        u64::MAX,
+        base.submission_timestamp,
+    );
+    assert_eq!(identity.validate(), Ok(()));
+}
+
+#[test]
+fn signature_length_follows_the_key_scheme() {
+    let mut identity = miner(1);
+    identity.public_key = vec![1; 1952];
+    assert_eq!(
+        identity.validate(),
+        Err(IdentityError::SignatureLength {
+            expected: 3309,
+            found: 64
+        })
+    );
+    // Right length, but no ML-DSA key signed it
+    identity.unique_signature = vec![2; 3309];
+    assert!(matches!(
+        identity.validate(),
+        Err(IdentityError::Signature(_))
+    ));
+}
+
+#[test]
+fn legacy_private_key_layout_is_rejected() {
+    // H₃(sk ‖ addr ‖ ts ‖ sig ‖ salt ‖ nonce ‖ ts₂) as the verification
+    // programs first encoded it, with no layout version
+    let legacy = PreimageEncoder::new(PreimageKind::MinerIdentity)
+        .str("priv_key_00000001")
+        .str("addr_00000001")
+        .u64(1_700_000_000)
+        .str("sig_00000001")
+        .str("salt_00000001")
+        .u64(1)
+        .u64(1_700_000_500)
+        .finish();
+    assert_eq!(
+        MinerIdentity::from_canonical_bytes(&legacy),
+        Err(IdentityError::UnsupportedVersion { found: 0 })
+    );
+}
+
+#[test]
+fn component_identity_layouts_are_rejected() {
+    // Hardware, network and Merkle branch components from the retired
+    // ComponentIdentity layout
+    let component = PreimageEncoder::new(PreimageKind::ComponentIdentity)
+        .str("I0000000000000001")
+        .str("hw_fedcba9876543210")
+        .str("net_0123456789abcdef")
+        .u64(1_700_000_000)
+        .str("branch1")
+        .str("branch2")
+        .str("commit")
+        .str("reveal")
+        .finish();
+    assert_eq!(
+        MinerIdentity::from_canonical_bytes(&component),
+        Err(IdentityError::UnexpectedHeader {
+            version: 1,
+            kind: PreimageKind::ComponentIdentity as u8
+        })
+    );
+
+    let mut future = miner(1).canonical_bytes();
+    future[2] = IDENTITY_VERSION + 1;
+    assert_eq!(
+        MinerIdentity::from_canonical_bytes(&future),
+        Err(IdentityError::UnsupportedVersion {
+            found: IDENTITY_VERSION + 1
+        })
+    );
+}
+
+#[test]
+fn truncated_and_padded_encodings_are_malformed() {
+    let bytes = miner(2).canonical_bytes();
+    assert_eq!(
+        MinerIdentity::from_canonical_bytes(&bytes[..bytes.len() - 1]),
+        Err(IdentityError::Malformed)
+    );
+
+    let mut padded = bytes;
+    padded.push(0);
+    assert_eq!(
+        MinerIdentity::from_canonical_bytes(&padded),
+        Err(IdentityError::Malformed)
+    );
+}
@@ -0,0 +1,54 @@
+use i_protocol_core::dura::assign_ranges;
+use i_protocol_core::layout::{NonceRange, NonceSegment, RangeLayout};
+use i_protocol_core::params::{
+    NONCES_PER_MINER, REGULAR_MINER_RANGE_START, SYSTEM_MINER_RANGE_END,
+};
+use i_protocol_core::MinerIdentity;
+
+#[test]
+fn regular_ranges_follow_system_segment() {
+    let layout = RangeLayout::new(3);
+    let system = RangeLayout::system_range();
+    let first = layout.regular_range(0).unwrap();
+    assert_eq!(first.start, SYSTEM_MINER_RANGE_END + 1);
+    assert_eq!(first.size(), NONCES_PER_MINER);
+    assert!(!first.overlaps(&system));
+    assert_eq!(layout.regular_range(3), None);
+    assert_eq!(
+        layout.regular_segment(),
+        Some(NonceRange {
+            start: REGULAR_MINER_RANGE_START,
+            end: SYSTEM_MINER_RANGE_END + 3 * NONCES_PER_MINER,
+        })
+    );
+    assert_eq!(RangeLayout::new(0).regular_segment(), None);
+}
+
+#[test]
+fn segment_of_covers_boundaries() {
+    let layout = RangeLayout::new(2);
+    assert_eq!(layout.segment_of(0), None);
+    assert_eq!(layout.segment_of(1), Some(NonceSegment::System));
+    assert_eq!(layout.segment_of(10_000), Some(NonceSegment::System));
+    assert_eq!(layout.segment_of(10_001), Some(NonceSegment::Regular(0)));
+    assert_eq!(layout.segment_of(260_000), Some(NonceSegment::Regular(0)));
+    assert_eq!(layout.segment_of(260_001), Some(NonceSegment::Regular(1)));
+    assert_eq!(layout.segment_of(510_000), Some(NonceSegment::Regular(1)));
+    assert_eq!(layout.segment_of(510_001), None);
+}
+
+#[test]
+fn dura_ranges_never_touch_system_segment() {
+    let miners: Vec<MinerIdentity> = (0..64)
+        .map(|id| MinerIdentity::virtual_miner(id, 1_700_000_000))
+        .collect();
+    let layout = RangeLayout::new(miners.len());
+    for range in assign_ranges("block", &miners).ranges {
+        let nonces = NonceRange {
+            start: range.start_nonce,
+            end: range.end_nonce,
+        };
+        assert!(!nonces.overlaps(&RangeLayout::system_range()));
+        assert_eq!(layout.regular_range(range.position), Some(nonces));
+    }
+}
@@ -0,0 +1,301 @@
+use std::sync::mpsc::Receiver;
+
+use i_protocol_core::collision::{Claim, CollisionIndex, Contender};
+use i_protocol_core::lifecycle::{LifecycleError, StatusUpdate, TxStatus, TxTracker};
+use i_protocol_core::params::TX_EXPIRY_BLOCKS;
+use i_protocol_core::tno::execute_tno_mapping;
+use i_protocol_core::{BlockchainState, Digest32, Transaction, TransactionKind};
+
+fn transfer(user_nonce: u64) -> Transaction {
+    Transaction {
+        sender_key: b"alice".to_vec(),
+        recipient: "bob".into(),
+        kind: TransactionKind::Transfer,
+        amount_i: 1_000,
+        fee: 10,
+        user_nonce,
+        timestamp: 1_700_000_000_000,
+        expiry_height: 1_000,
+        final_nonce: 0,
+        signature: Vec::new(),
+    }
+}
+
+fn state(height: u64) -> BlockchainState {
+    BlockchainState {
+        height,
+        previous_hash: format!("{:064x}", height.wrapping_mul(0x9e37_79b9_7f4a_7c15)),
+        active_miners: 1,
+        ..Default::default()
+    }
+}
+
+fn statuses(updates: &Receiver<StatusUpdate>) -> Vec<TxStatus> {
+    updates.try_iter().map(|update| update.status).collect()
+}
+
+#[test]
+fn transactions_move_from_pending_to_included() {
+    let tx = transfer(1).hash();
+    let mut tracker = TxTracker::new(10);
+    let all = tracker.subscribe_all();
+    tracker.submit(tx, 10 + TX_EXPIRY_BLOCKS).unwrap();
+    let updates = tracker.subscribe(tx).unwrap();
+
+    assert_eq!(tracker.due(), vec![tx]);
+    tracker.record_claim(tx, 42_000, &Claim::Claimed).unwrap();
+    assert!(tracker.due().is_empty());
+    tracker.include(tx).unwrap();
+
+    let mapped = TxStatus::Mapped {
+        height: 10,
+        final_nonce: 42_000,
+    };
+    let included = TxStatus::Included { height: 10 };
+    // A subscription opens with the current status
+    assert_eq!(
+        statuses(&updates),
+        vec![TxStatus::Pending, mapped, included]
+    );
+    assert_eq!(statuses(&all), vec![TxStatus::Pending, mapped, included]);
+    assert_eq!(tracker.status(&tx), Some(included));
+}
+
+#[test]
+fn losers_retry_at_the_next_height() {
+    let mut index = CollisionIndex::new();
+    let mut tracker = TxTracker::new(5);
+    let txs = [transfer(1), transfer(2), transfer(3)];
+    let hashes: Vec<Digest32> = txs.iter().map(Transaction::hash).collect();
+    for hash in &hashes {
+        tracker.submit(*hash, 5 + TX_EXPIRY_BLOCKS).unwrap();
+    }
+
+    // All three contend for one final nonce; the lowest hash keeps it
+    let mut winner = hashes[0];
+    for (tx, hash) in txs.iter().zip(&hashes) {
+        let claim = index.claim(5, 77_777, Contender::of(tx)).unwrap();
+        tracker.record_claim(*hash, 77_777, &claim).unwrap();
+        if let Claim::Displaced { .. } = claim {
+            winner = *hash;
+        }
+    }
+    assert_eq!(*hashes.iter().min().unwrap(), winner);
+    for hash in hashes.iter().filter(|hash| **hash != winner) {
+        assert_eq!(tracker.status(hash), Some(TxStatus::Retrying { height: 6 }));
+    }
+    // No second attempt within the height
+    assert!(tracker.due().is_empty());
+    assert!(matches!(
+        tracker.record_claim(hashes[1], 88_888, &Claim::Claimed),
+        Err(LifecycleError::NotDue { .. })
+    ));
+
+    tracker.include(winner).unwrap();
+    tracker.advance(6).unwrap();
+    let mut retrying: Vec<Digest32> = hashes.iter().copied().filter(|h| *h != winner).collect();
+    retrying.sort();
+    assert_eq!(tracker.due(), retrying);
+
+    // The unchanged transaction maps elsewhere at the next height
+    let tx = &txs[hashes.iter().position(|h| *h == retrying[0]).unwrap()];
+    let hash = tx.hash_hex();
+    assert_ne!(
+        execute_tno_mapping(tx.user_nonce, &hash, &state(5)),
+        execute_tno_mapping(tx.user_nonce, &hash, &state(6))
+    );
+}
+
+#[test]
+fn displacement_does_not_reopen_included_transactions() {
+    // A replayed index hands the nonce to a lower hash after the holder was
+    // already included; the holder's status must not move backwards
+    let txs = [transfer(1), transfer(2)];
+    let (low, high) = if txs[0].hash() < txs[1].hash() {
+        (&txs[0], &txs[1])
+    } else {
+        (&txs[1], &txs[0])
+    };
+    let mut tracker = TxTracker::new(5);
+    tracker.submit(high.hash(), 5 + TX_EXPIRY_BLOCKS).unwrap();
+    tracker.submit(low.hash(), 5 + TX_EXPIRY_BLOCKS).unwrap();
+    tracker
+        .record_claim(high.hash(), 77_777, &Claim::Claimed)
+        .unwrap();
+    tracker.include(high.hash()).unwrap();
+    let updates = tracker.subscribe(high.hash()).unwrap();
+
+    let displaced = Claim::Displaced {
+        loser: Contender::of(high),
+    };
+    tracker
+        .record_claim(low.hash(), 77_777, &displaced)
+        .unwrap();
+    let included = TxStatus::Included { height: 5 };
+    assert_eq!(tracker.status(&high.hash()), Some(included));
+    assert_eq!(statuses(&updates), vec![included]);
+    assert_eq!(
+        tracker.status(&low.hash()),
+        Some(TxStatus::Mapped {
+            height: 5,
+            final_nonce: 77_777
+        })
+    );
+}
+
+#[test]
+fn unincluded_mappings_retry_and_expire() {
+    let tx = transfer(1).hash();
+    let mut tracker = TxTracker::new(1);
+    tracker.submit(tx, 3).unwrap();
+    let updates = tracker.subscribe(tx).unwrap();
+
+    // Mapped each height but left out of every block
+    for height in 1..=3 {
+        assert_eq!(tracker.due(), vec![tx]);
+        tracker.record_claim(tx, 10_001, &Claim::Claimed).unwrap();
+        tracker.advance(height + 1).unwrap();
+    }
+    assert_eq!(tracker.status(&tx), Some(TxStatus::Expired { height: 3 }));
+    assert!(tracker.due().is_empty());
+    assert_eq!(
+        statuses(&updates),
+        vec![
+            TxStatus::Pending,
+            TxStatus::Mapped {
+                height: 1,
+                final_nonce: 10_001
+            },
+            TxStatus::Retrying { height: 2 },
+            TxStatus::Mapped {
+                height: 2,
+                final_nonce: 10_001
+            },
+            TxStatus::Retrying { height: 3 },
+            TxStatus::Mapped {
+                height: 3,
+                final_nonce: 10_001
+            },
+            TxStatus::Expired { height: 3 },
+        ]
+    );
+    assert!(matches!(
+        tracker.include(tx),
+        Err(LifecycleError::NotMapped { .. })
+    ));
+}
+
+#[test]
+fn finality_drops_settled_transactions() {
+    let [kept, included, expired] = [transfer(1).hash(), transfer(2).hash(), transfer(3).hash()];
+    let mut tracker = TxTracker::new(1);
+    tracker.submit(kept, 100).unwrap();
+    tracker.submit(included, 100).unwrap();
+    tracker.submit(expired, 1).unwrap();
+    tracker
+        .record_claim(included, 10_001, &Claim::Claimed)
+        .unwrap();
+    tracker.include(included).unwrap();
+    let updates = tracker.subscribe(included).unwrap();
+    tracker.advance(2).unwrap();
+
+    tracker.finalize(0);
+    assert_eq!(tracker.len(), 3);
+    tracker.finalize(1);
+    assert_eq!(tracker.len(), 1);
+    assert_eq!(tracker.status(&included), None);
+    assert_eq!(tracker.status(&expired), None);
+    assert_eq!(tracker.status(&kept), Some(TxStatus::Pending));
+
+    // The subscription ends after the last update
+    assert_eq!(statuses(&updates), vec![TxStatus::Included { height: 1 }]);
+    assert!(updates.recv().is_err());
+}
+
+#[test]
+fn invalid_events_are_rejected() {
+    let tx = transfer(1).hash();
+    let stranger = transfer(2).hash();
+    let mut tracker = TxTracker::new(10);
+    assert_eq!(
+        tracker.submit(tx, 9),
+        Err(LifecycleError::ExpiryPassed {
+            tx_hash: tx,
+            expiry_height: 9
+        })
+    );
+    tracker.submit(tx, 10).unwrap();
+    assert_eq!(
+        tracker.submit(tx, 10),
+        Err(LifecycleError::AlreadyTracked { tx_hash: tx })
+    );
+    assert_eq!(
+        tracker.record_claim(stranger, 10_001, &Claim::Claimed),
+        Err(LifecycleError::Untracked { tx_hash: stranger })
+    );
+    assert_eq!(
+        tracker.subscribe(stranger).err(),
+        Some(LifecycleError::Untracked { tx_hash: stranger })
+    );
+    assert_eq!(
+        tracker.include(tx),
+        Err(LifecycleError::NotMapped {
+            tx_hash: tx,
+            status: TxStatus::Pending
+        })
+    );
+    assert_eq!(
+        tracker.advance(10),
+        Err(LifecycleError::HeightNotIncreasing {
+            last: 10,
+            found: 10
+        })
+    );
+}
+
+#[test]
+fn every_transaction_settles_under_load() {
+    // 5,000 transactions against a single miner's 250,000 nonces collide
+    // about fifty times in the first block
+    const TRANSACTIONS: u64 = 5_000;
+    const START: u64 = 1_000;
+
+    let txs: Vec<Transaction> = (0..TRANSACTIONS).map(transfer).collect();
+    let by_hash: std::collections::HashMap<Digest32, &Transaction> =
+        txs.iter().map(|tx| (tx.hash(), tx)).collect();
+    let mut index = CollisionIndex::new();
+    let mut tracker = TxTracker::new(START);
+    for hash in by_hash.keys() {
+        tracker.submit(*hash, START + TX_EXPIRY_BLOCKS).unwrap();
+    }
+
+    let mut retries = Vec::new();
+    let mut height = START;
+    while !tracker.due().is_empty() {
+        let state = state(height);
+        let due = tracker.due();
+        for hash in &due {
+            let tx = by_hash[hash];
+            let final_nonce = execute_tno_mapping(tx.user_nonce, &tx.hash_hex(), &state);
+            let claim = index.claim(height, final_nonce, Contender::of(tx)).unwrap();
+            tracker.record_claim(*hash, final_nonce, &claim).unwrap();
+        }
+        for hash in &due {
+            if let Some(TxStatus::Mapped { .. }) = tracker.status(hash) {
+                tracker.include(*hash).unwrap();
+            }
+        }
+        height += 1;
+        tracker.advance(height).unwrap();
+        retries.push(tracker.due().len());
+    }
+
+    assert!(retries[0] > 0, "no collisions to retry");
+    assert!(retries.len() < 5, "retries per block: {retries:?}");
+    for hash in by_hash.keys() {
+        assert!(matches!(
+            tracker.status(hash),
+            Some(TxStatus::Included { .. })
+        ));
+    }
+}
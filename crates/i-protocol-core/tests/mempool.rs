@@ -0,0 +1,61 @@
+use i_protocol_core::block::transactions_root;
+use i_protocol_core::dura::{deterministic_shuffle, master_seed_digest};
+use i_protocol_core::mempool::{dmof_key, sysblock_cutoff, Mempool};
+use i_protocol_core::{Transaction, TransactionKind};
+
+const T0: u64 = 1_700_000_000_000;
+
+fn transaction(i: u64) -> Transaction {
+    Transaction {
+        sender_key: format!("sender_{i}").into_bytes(),
+        recipient: "recipient".into(),
+        kind: TransactionKind::Transfer,
+        amount_i: 1_000_000 + i as u128,
+        fee: 10_000,
+        user_nonce: i * 7,
+        timestamp: T0 + i % 400,
+        expiry_height: 1_000,
+        final_nonce: 0,
+        signature: (i as u32).to_be_bytes().to_vec(),
+    }
+}
+
+#[test]
+fn canonical_set_applies_cutoff_and_dmof_order() {
+    let mut mempool = Mempool::new();
+    for i in 0..200 {
+        assert!(mempool.insert(transaction(i)));
+    }
+    assert!(!mempool.insert(transaction(0)));
+
+    let cutoff = sysblock_cutoff(T0);
+    let set = mempool.canonical_sysblock_set(cutoff);
+    assert_eq!(
+        set.len(),
+        mempool.iter().filter(|tx| tx.timestamp <= cutoff).count()
+    );
+    assert!(set.iter().all(|tx| tx.timestamp <= cutoff));
+    assert!(set
+        .windows(2)
+        .all(|pair| dmof_key(&pair[0]) <= dmof_key(&pair[1])));
+}
+
+#[test]
+fn fifty_nodes_agree_on_sysblock_merkle_root() {
+    let transactions: Vec<Transaction> = (0..200).map(transaction).collect();
+    let cutoff = sysblock_cutoff(T0);
+
+    let roots: Vec<_> = (0..50u32)
+        .map(|node| {
+            // Each node hears the gossip in its own order
+            let seed = master_seed_digest(&format!("node_{node}"), &[]);
+            let mut mempool = Mempool::new();
+            for index in deterministic_shuffle(&seed, transactions.len()) {
+                mempool.insert(transactions[index].clone());
+            }
+            transactions_root(&mempool.canonical_sysblock_set(cutoff))
+        })
+        .collect();
+
+    assert!(roots.iter().all(|root| *root == roots[0]));
+}
@@ -0,0 +1,136 @@
+use i_protocol_core::merkle::{
+    leaf_hash, merkle_root, node_hash, MerkleProof, MerkleProofError, MerkleTree, EMPTY_ROOT,
+};
+use i_protocol_core::{Block, BlockHeader, BlockProducer, Coinbase, Transaction, TransactionKind};
+
+fn leaves(count: u8) -> Vec<[u8; 32]> {
+    (0..count).map(|i| [i; 32]).collect()
+}
+
+#[test]
+fn tree_root_matches_merkle_root() {
+    for count in 0..=17 {
+        let leaves = leaves(count);
+        let tree = MerkleTree::new(&leaves);
+        assert_eq!(tree.root(), merkle_root(&leaves), "{count} leaves");
+        assert_eq!(tree.len(), count as usize);
+    }
+    assert_eq!(MerkleTree::new(&[]).root(), EMPTY_ROOT);
+
+    // Three leaves: the third is promoted past the first level
+    let [a, b, c] = [[1; 32], [2; 32], [3; 32]];
+    let expected = node_hash(&node_hash(&leaf_hash(&a), &leaf_hash(&b)), &leaf_hash(&c));
+    assert_eq!(merkle_root(&[a, b, c]), expected);
+}
+
+#[test]
+fn every_leaf_has_a_verifying_proof() {
+    for count in 1..=17 {
+        let leaves = leaves(count);
+        let tree = MerkleTree::new(&leaves);
+        let root = tree.root();
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(proof.verify(&root, leaf), "leaf {index} of {count}");
+            assert!(!proof.verify(&root, &[0xff; 32]));
+            assert_eq!(MerkleProof::from_bytes(&proof.to_bytes()), Ok(proof));
+        }
+        assert_eq!(tree.proof(count as usize), None);
+    }
+}
+
+#[test]
+fn proofs_do_not_transfer() {
+    let leaves = leaves(6);
+    let tree = MerkleTree::new(&leaves);
+    let root = tree.root();
+    let proof = tree.proof(2).unwrap();
+
+    // Same siblings claimed for another position
+    let mut moved = proof.clone();
+    moved.leaf_index = 3;
+    assert!(!moved.verify(&root, &leaves[2]));
+    assert!(!moved.verify(&root, &leaves[3]));
+
+    let mut tampered = proof.clone();
+    tampered.siblings[1][0] ^= 1;
+    assert!(!tampered.verify(&root, &leaves[2]));
+
+    let mut short = proof.clone();
+    short.siblings.pop();
+    assert_eq!(short.compute_root(&leaves[2]), None);
+
+    // An interior node is not accepted as a leaf
+    let interior = node_hash(&leaf_hash(&leaves[0]), &leaf_hash(&leaves[1]));
+    let upper = MerkleProof {
+        leaf_count: 3,
+        leaf_index: 0,
+        siblings: proof.siblings[1..].to_vec(),
+    };
+    assert!(!upper.verify(&root, &interior));
+}
+
+#[test]
+fn compact_encoding_is_checked() {
+    let proof = MerkleTree::new(&leaves(5)).proof(4).unwrap();
+    let bytes = proof.to_bytes();
+    // Leaf 4 of 5 is promoted twice and paired once
+    assert_eq!(bytes.len(), 8 + 32);
+    assert_eq!(&bytes[..8], &[0, 0, 0, 5, 0, 0, 0, 4]);
+
+    assert_eq!(
+        MerkleProof::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(MerkleProofError::Length {
+            expected: 40,
+            found: 39
+        })
+    );
+    assert_eq!(
+        MerkleProof::from_bytes(&[0, 0, 0, 2, 0, 0, 0, 2]),
+        Err(MerkleProofError::IndexOutOfRange { index: 2, count: 2 })
+    );
+    assert_eq!(
+        MerkleProof::from_bytes(&[0, 0, 0]),
+        Err(MerkleProofError::Length {
+            expected: 8,
+            found: 3
+        })
+    );
+}
+
+#[test]
+fn transaction_proofs_verify_against_the_header() {
+    let transactions: Vec<Transaction> = (0..7)
+        .map(|user_nonce| Transaction {
+            sender_key: b"alice".to_vec(),
+            recipient: "bob".into(),
+            kind: TransactionKind::Transfer,
+            amount_i: 1_000,
+            fee: 10,
+            user_nonce,
+            timestamp: 1_700_000_000_000,
+            expiry_height: 1_000,
+            final_nonce: 0,
+            signature: Vec::new(),
+        })
+        .collect();
+    let mut block = Block {
+        header: BlockHeader {
+            height: 1,
+            previous_hash: [0; 32],
+            timestamp: 1_700_000_000_500,
+            merkle_root: EMPTY_ROOT,
+            failed_miner_count: 0,
+            producer: BlockProducer::SystemMiner { system_nonce: 1 },
+            coinbase: Coinbase::default(),
+        },
+        transactions,
+    };
+    block.header.merkle_root = block.compute_merkle_root();
+
+    for (index, tx) in block.transactions.iter().enumerate() {
+        let proof = block.transaction_proof(index).unwrap();
+        assert!(proof.verify(&block.header.merkle_root, &tx.hash()));
+    }
+    assert_eq!(block.transaction_proof(7), None);
+}
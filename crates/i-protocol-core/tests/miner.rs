@@ -0,0 +1,207 @@
+use std::sync::atomic::AtomicBool;
+
+use i_protocol_core::bootstrap::NetworkPhase;
+use i_protocol_core::difficulty::DifficultyTarget;
+use i_protocol_core::dura::MinerRange;
+use i_protocol_core::emission::block_reward;
+use i_protocol_core::hash::{from_hex, triple_layer_hash};
+use i_protocol_core::miner::{meets_target, mine_range, mine_range_parallel, MiningOutcome};
+use i_protocol_core::validation::{validate_block, ChainState};
+use i_protocol_core::{Block, BlockHeader, BlockProducer, Coinbase, HashDomain, MinerIdentity};
+
+// Roughly one header in 256 qualifies.
+const EASY_TARGET: [u8; 32] = {
+    let mut target = [0xff; 32];
+    target[0] = 0;
+    target
+};
+
+fn chain() -> ChainState {
+    ChainState {
+        tip_height: 41,
+        tip_hash: triple_layer_hash(HashDomain::BlockHeader, b"tip"),
+        tip_timestamp: 1_700_000_000_000,
+        active_miners: (0..4)
+            .map(|id| MinerIdentity::virtual_miner(id, 1_700_000_000))
+            .collect(),
+        round: None,
+        phase: NetworkPhase::Sustained,
+        target: DifficultyTarget::from_threshold(u64::MAX >> 8),
+    }
+}
+
+fn template(chain: &ChainState) -> BlockHeader {
+    BlockHeader {
+        height: chain.tip_height + 1,
+        previous_hash: chain.tip_hash,
+        timestamp: chain.tip_timestamp + 500,
+        merkle_root: [0; 32],
+        failed_miner_count: 0,
+        producer: BlockProducer::SystemMiner { system_nonce: 0 },
+        coinbase: Coinbase {
+            block_reward: block_reward(chain.tip_height + 1),
+            miner_fees: 0,
+        },
+    }
+}
+
+fn range(chain: &ChainState, position: usize) -> MinerRange {
+    chain.assign_ranges().ranges[position].clone()
+}
+
+#[test]
+fn found_header_meets_target_and_validates() {
+    let chain = chain();
+    let range = range(&chain, 2);
+    let report = mine_range(
+        &template(&chain),
+        &range,
+        &EASY_TARGET,
+        &AtomicBool::new(false),
+    );
+
+    let header = *report.header().expect("easy target is met");
+    assert!(meets_target(&header.hash(), &EASY_TARGET));
+    assert_eq!(header.nonce(), range.start_nonce + report.hashes - 1);
+    assert_eq!(
+        header.producer,
+        BlockProducer::RegularMiner {
+            range_owner: from_hex(&range.identity_hash).unwrap(),
+            nonce: header.nonce(),
+        }
+    );
+
+    let block = Block {
+        header,
+        transactions: Vec::new(),
+    };
+    assert_eq!(validate_block(&block, &chain), Ok(()));
+}
+
+#[test]
+fn first_qualifying_nonce_wins() {
+    let chain = chain();
+    let range = range(&chain, 0);
+    let template = template(&chain);
+    let report = mine_range(&template, &range, &EASY_TARGET, &AtomicBool::new(false));
+    let winner = report.header().unwrap().nonce();
+
+    for nonce in range.start_nonce..winner {
+        let mut header = *report.header().unwrap();
+        header.producer = BlockProducer::RegularMiner {
+            range_owner: from_hex(&range.identity_hash).unwrap(),
+            nonce,
+        };
+        assert!(!meets_target(&header.hash(), &EASY_TARGET));
+    }
+}
+
+#[test]
+fn exhausts_range_without_a_hit() {
+    let chain = chain();
+    let mut range = range(&chain, 1);
+    range.end_nonce = range.start_nonce + 499;
+    let report = mine_range(&template(&chain), &range, &[0; 32], &AtomicBool::new(false));
+    assert_eq!(report.outcome, MiningOutcome::Exhausted);
+    assert_eq!(report.hashes, 500);
+    assert!(report.hashes_per_sec() > 0.0);
+}
+
+#[test]
+fn stops_when_cancelled() {
+    let chain = chain();
+    let report = mine_range(
+        &template(&chain),
+        &range(&chain, 3),
+        &[0; 32],
+        &AtomicBool::new(true),
+    );
+    assert_eq!(report.outcome, MiningOutcome::Cancelled);
+    assert_eq!(report.hashes, 0);
+}
+
+#[test]
+fn parallel_workers_find_a_valid_header() {
+    let chain = chain();
+    let range = range(&chain, 2);
+    let report = mine_range_parallel(
+        &template(&chain),
+        &range,
+        &EASY_TARGET,
+        &AtomicBool::new(false),
+        4,
+    );
+
+    assert_eq!(report.workers.len(), 4);
+    let header = *report.header().expect("easy target is met");
+    assert!(meets_target(&header.hash(), &EASY_TARGET));
+    assert!((range.start_nonce..=range.end_nonce).contains(&header.nonce()));
+    let block = Block {
+        header,
+        transactions: Vec::new(),
+    };
+    assert_eq!(validate_block(&block, &chain), Ok(()));
+}
+
+#[test]
+fn single_worker_matches_sequential_search() {
+    let chain = chain();
+    let range = range(&chain, 0);
+    let cancel = AtomicBool::new(false);
+    let sequential = mine_range(&template(&chain), &range, &EASY_TARGET, &cancel);
+    let parallel = mine_range_parallel(&template(&chain), &range, &EASY_TARGET, &cancel, 1);
+    assert_eq!(parallel.outcome, sequential.outcome);
+    assert_eq!(parallel.hashes(), sequential.hashes);
+}
+
+#[test]
+fn parallel_slices_cover_the_range_once() {
+    let chain = chain();
+    let mut range = range(&chain, 1);
+    range.end_nonce = range.start_nonce + 499;
+    let report = mine_range_parallel(
+        &template(&chain),
+        &range,
+        &[0; 32],
+        &AtomicBool::new(false),
+        3,
+    );
+    assert_eq!(report.outcome, MiningOutcome::Exhausted);
+    assert_eq!(report.hashes(), 500);
+
+    range.end_nonce = range.start_nonce + 1;
+    let report = mine_range_parallel(
+        &template(&chain),
+        &range,
+        &[0; 32],
+        &AtomicBool::new(false),
+        8,
+    );
+    assert_eq!(report.workers.len(), 2);
+    assert_eq!(report.hashes(), 2);
+}
+
+#[test]
+fn parallel_search_stops_when_cancelled() {
+    let chain = chain();
+    let report = mine_range_parallel(
+        &template(&chain),
+        &range(&chain, 3),
+        &[0; 32],
+        &AtomicBool::new(true),
+        4,
+    );
+    assert_eq!(report.outcome, MiningOutcome::Cancelled);
+    assert_eq!(report.hashes(), 0);
+}
+
+#[test]
+fn target_is_big_endian() {
+    let mut low = [0; 32];
+    low[31] = 0xff;
+    let mut high = [0; 32];
+    high[0] = 1;
+    assert!(meets_target(&low, &high));
+    assert!(!meets_target(&high, &low));
+    assert!(meets_target(&high, &high));
+}
@@ -0,0 +1,129 @@
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use i_protocol_core::dura::{assign_ranges, RangeTable};
+use i_protocol_core::layout::{NonceSegment, RangeLayout};
+use i_protocol_core::params::{
+    NONCES_PER_MINER, REGULAR_MINER_RANGE_START, SYSTEM_MINER_RANGE_END, SYSTEM_MINER_RANGE_START,
+};
+use i_protocol_core::system_miner::{is_system_nonce, system_nonce};
+use i_protocol_core::tno::{assigned_miner, execute_tno_mapping, wide_reduce};
+use i_protocol_core::{BlockchainState, MinerIdentity};
+
+fn state(height: u64) -> BlockchainState {
+    BlockchainState {
+        height,
+        previous_hash: format!("block_{height}"),
+        timestamp: 1_700_000_000_000 + height * 500,
+        failed_miner_count: (height % 7) as u32,
+        active_miners: 200,
+    }
+}
+
+#[test]
+fn system_nonce_stays_in_reserved_range() {
+    for height in 0..2_000 {
+        let nonce = system_nonce(&state(height));
+        assert!(is_system_nonce(nonce), "height {height}: {nonce}");
+    }
+    assert!(!is_system_nonce(SYSTEM_MINER_RANGE_START - 1));
+    assert!(!is_system_nonce(SYSTEM_MINER_RANGE_END + 1));
+}
+
+#[test]
+fn system_nonce_depends_on_failure_count() {
+    let mut other = state(10);
+    other.failed_miner_count += 1;
+    assert_ne!(system_nonce(&state(10)), system_nonce(&other));
+}
+
+#[test]
+fn tno_mapping_is_deterministic_and_bounded() {
+    let state = state(42);
+    for user_nonce in 0..2_000 {
+        let final_nonce = execute_tno_mapping(user_nonce, "tx", &state);
+        assert_eq!(final_nonce, execute_tno_mapping(user_nonce, "tx", &state));
+        let segment = RangeLayout::new(state.active_miners as usize)
+            .regular_segment()
+            .unwrap();
+        assert!(segment.contains(final_nonce), "{final_nonce}");
+    }
+}
+
+#[test]
+fn wide_reduce_is_the_scaled_integer_part() {
+    for x in [0u64, 1, 0x1234_5678_9abc_def0, u64::MAX - 1, u64::MAX] {
+        for size in [1u64, 3, 7, 250_000, 1_499 * NONCES_PER_MINER, u64::MAX] {
+            let expected = ((x as u128 * size as u128) >> 64) as u64;
+            assert_eq!(wide_reduce(&x.to_be_bytes(), size), expected);
+        }
+    }
+    // The extremes of a digest land on the ends of the range
+    assert_eq!(wide_reduce(&[0; 32], 750_000), 0);
+    assert_eq!(wide_reduce(&[0xff; 32], 750_000), 749_999);
+}
+
+#[test]
+fn reduction_bias_is_at_most_one_input_per_miner() {
+    // Exhaustive over 16-bit inputs: every miner is hit by ⌊2¹⁶/n⌋ or
+    // ⌈2¹⁶/n⌉ of them, the same bound the 256-bit digest obeys
+    for miners in [3u64, 7, 200, 1_499] {
+        let size = miners * NONCES_PER_MINER;
+        let layout = RangeLayout::new(miners as usize);
+        let mut hits = vec![0u64; miners as usize];
+        for x in 0..=u16::MAX {
+            let final_nonce = REGULAR_MINER_RANGE_START + wide_reduce(&x.to_be_bytes(), size);
+            match layout.segment_of(final_nonce) {
+                Some(NonceSegment::Regular(position)) => hits[position] += 1,
+                other => panic!("{final_nonce} mapped to {other:?}"),
+            }
+        }
+        let floor = (1 << 16) / miners;
+        assert!(
+            hits.iter().all(|&h| h == floor || h == floor + 1),
+            "{miners} miners: {:?}..{:?}",
+            hits.iter().min(),
+            hits.iter().max()
+        );
+    }
+}
+
+#[test]
+fn tno_assignment_agrees_with_dura() {
+    let pool: Vec<MinerIdentity> = (0..40)
+        .map(|id| MinerIdentity::virtual_miner(id, 1_700_000_000))
+        .collect();
+    let mut rng = ChaCha20Rng::seed_from_u64(21);
+
+    for _ in 0..1_000 {
+        let miners = &pool[..1 + rng.next_u32() as usize % pool.len()];
+        let state = BlockchainState {
+            height: rng.next_u64() % 1_000_000,
+            previous_hash: format!("{:016x}", rng.next_u64()),
+            timestamp: 1_700_000_000_000,
+            failed_miner_count: 0,
+            active_miners: miners.len() as u32,
+        };
+        let assignment = assign_ranges(&state.previous_hash, miners);
+        let table = RangeTable::for_block(&state.previous_hash, miners);
+
+        let final_nonce = execute_tno_mapping(rng.next_u64(), "tx", &state);
+        let owner = assignment
+            .ranges
+            .iter()
+            .find(|range| (range.start_nonce..=range.end_nonce).contains(&final_nonce))
+            .unwrap();
+        assert_eq!(
+            assigned_miner(final_nonce, &table),
+            Some(owner.identity_hash.as_str())
+        );
+    }
+
+    // The System Miner segment belongs to no regular miner
+    let table = RangeTable::for_block("prev", &pool[..3]);
+    assert_eq!(table.owner_of(SYSTEM_MINER_RANGE_END), None);
+    assert_eq!(
+        table.owner_of(REGULAR_MINER_RANGE_START + 3 * NONCES_PER_MINER),
+        None
+    );
+}
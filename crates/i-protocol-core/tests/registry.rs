@@ -0,0 +1,202 @@
+use i_protocol_core::identity::IdentityError;
+use i_protocol_core::params::REGISTRY_ACTIVATION_DELAY as K;
+use i_protocol_core::registry::{MinerRegistry, RegistryChange, RegistryError};
+use i_protocol_core::signing::SignatureError;
+use i_protocol_core::MinerIdentity;
+
+fn miner(id: u32) -> MinerIdentity {
+    MinerIdentity::virtual_miner(id, 1_700_000_000)
+}
+
+fn join(id: u32) -> RegistryChange {
+    RegistryChange::Join(miner(id))
+}
+
+fn leave(id: u32) -> RegistryChange {
+    RegistryChange::leave(&miner(id), &MinerIdentity::virtual_signer(id))
+}
+
+#[test]
+fn join_takes_effect_after_the_delay() {
+    let mut registry = MinerRegistry::with_genesis([miner(0)]).unwrap();
+    registry.apply_block(5, &[join(1)]).unwrap();
+
+    assert_eq!(registry.snapshot(5 + K - 1).miners, vec![miner(0)]);
+    assert_eq!(registry.snapshot(5 + K).miners.len(), 2);
+    assert!(registry.is_registered(&miner(1).identity_hash()));
+}
+
+#[test]
+fn leave_takes_effect_after_the_delay() {
+    let mut registry = MinerRegistry::with_genesis([miner(0), miner(1)]).unwrap();
+    registry.apply_block(3, &[leave(0)]).unwrap();
+
+    assert_eq!(registry.snapshot(3 + K - 1).miners.len(), 2);
+    assert_eq!(registry.snapshot(3 + K).miners, vec![miner(1)]);
+    // Departed identities stay known
+    assert_eq!(
+        registry.identity(&miner(0).identity_hash()),
+        Some(&miner(0))
+    );
+}
+
+#[test]
+fn leave_must_be_signed_by_the_departing_identity() {
+    let mut registry = MinerRegistry::with_genesis([miner(0), miner(1)]).unwrap();
+    let identity_hash = miner(0).identity_hash();
+
+    // Miner 1 tries to retire miner 0
+    let forged = RegistryChange::leave(&miner(0), &MinerIdentity::virtual_signer(1));
+    assert_eq!(
+        registry.apply_block(1, &[forged]),
+        Err(RegistryError::InvalidLeaveSignature {
+            identity_hash: identity_hash.clone(),
+            error: SignatureError::Invalid
+        })
+    );
+    let RegistryChange::Leave { signature, .. } = leave(1) else {
+        unreachable!()
+    };
+    let moved = RegistryChange::Leave {
+        identity_hash: identity_hash.clone(),
+        signature,
+    };
+    assert!(matches!(
+        registry.apply_block(1, &[moved]),
+        Err(RegistryError::InvalidLeaveSignature { .. })
+    ));
+    assert_eq!(registry.snapshot(1 + K).miners.len(), 2);
+
+    registry.apply_block(1, &[leave(0)]).unwrap();
+    assert_eq!(registry.snapshot(1 + K).miners, vec![miner(1)]);
+}
+
+#[test]
+fn snapshots_are_sorted_by_identity_hash() {
+    let mut a = MinerRegistry::new();
+    let mut b = MinerRegistry::new();
+    a.apply_block(1, &[join(0), join(1), join(2), join(3)])
+        .unwrap();
+    b.apply_block(1, &[join(3), join(1), join(0)]).unwrap();
+    b.apply_block(2, &[join(2)]).unwrap();
+
+    let snapshot = a.snapshot(2 + K);
+    let hashes = snapshot.identity_hashes();
+    let mut sorted = hashes.clone();
+    sorted.sort();
+    assert_eq!(hashes, sorted);
+
+    assert_eq!(snapshot.miners, b.snapshot(2 + K).miners);
+    assert_eq!(
+        snapshot.assign_ranges("prev"),
+        b.snapshot(2 + K).assign_ranges("prev")
+    );
+}
+
+#[test]
+fn rejects_invalid_changes_atomically() {
+    let mut registry = MinerRegistry::with_genesis([miner(0)]).unwrap();
+    assert_eq!(
+        registry.apply_block(1, &[join(1), join(0)]),
+        Err(RegistryError::AlreadyRegistered {
+            identity_hash: miner(0).identity_hash()
+        })
+    );
+    assert!(!registry.is_registered(&miner(1).identity_hash()));
+
+    let mut unsalted = miner(2);
+    unsalted.salt.clear();
+    assert_eq!(
+        registry.apply_block(1, &[RegistryChange::Join(unsalted.clone())]),
+        Err(RegistryError::InvalidIdentity {
+            identity_hash: unsalted.identity_hash(),
+            error: IdentityError::SaltLength { len: 0 }
+        })
+    );
+
+    assert_eq!(
+        registry.apply_block(1, &[leave(9)]),
+        Err(RegistryError::NotRegistered {
+            identity_hash: miner(9).identity_hash()
+        })
+    );
+
+    registry.apply_block(1, &[leave(0)]).unwrap();
+    assert_eq!(
+        registry.apply_block(2, &[leave(0)]),
+        Err(RegistryError::AlreadyLeft {
+            identity_hash: miner(0).identity_hash()
+        })
+    );
+    assert_eq!(
+        registry.apply_block(2, &[join(0)]),
+        Err(RegistryError::AlreadyRegistered {
+            identity_hash: miner(0).identity_hash()
+        })
+    );
+    assert_eq!(
+        registry.apply_block(1, &[]),
+        Err(RegistryError::HeightNotIncreasing { last: 1, found: 1 })
+    );
+}
+
+#[test]
+fn genesis_rejects_invalid_and_duplicate_identities() {
+    let mut forged = miner(1);
+    forged.identity_nonce += 1;
+    assert!(matches!(
+        MinerRegistry::with_genesis([miner(0), forged.clone()]),
+        Err(RegistryError::InvalidIdentity { identity_hash, error: IdentityError::Signature(_) })
+            if identity_hash == forged.identity_hash()
+    ));
+
+    assert_eq!(
+        MinerRegistry::with_genesis([miner(0), miner(1), miner(0)]).err(),
+        Some(RegistryError::AlreadyRegistered {
+            identity_hash: miner(0).identity_hash()
+        })
+    );
+}
+
+#[test]
+fn churn_matches_a_replayed_active_set() {
+    // Deterministic churn: every block admits two new miners and retires the
+    // oldest remaining one from block 20 on.
+    let mut registry = MinerRegistry::new();
+    let mut log: Vec<(u64, u32, bool)> = Vec::new();
+    let mut next_id = 0;
+    let mut oldest = 0;
+    for height in 1..=100u64 {
+        let mut changes = vec![join(next_id), join(next_id + 1)];
+        log.push((height, next_id, true));
+        log.push((height, next_id + 1, true));
+        next_id += 2;
+        if height >= 20 {
+            changes.push(leave(oldest));
+            log.push((height, oldest, false));
+            oldest += 1;
+        }
+        registry.apply_block(height, &changes).unwrap();
+    }
+
+    for height in 0..=100 + K {
+        let mut expected: Vec<String> = Vec::new();
+        for &(at, id, joined) in &log {
+            if at + K > height {
+                continue;
+            }
+            let hash = miner(id).identity_hash();
+            if joined {
+                expected.push(hash);
+            } else {
+                expected.retain(|h| *h != hash);
+            }
+        }
+        expected.sort();
+        assert_eq!(
+            registry.snapshot(height).identity_hashes(),
+            expected,
+            "height {height}"
+        );
+    }
+}
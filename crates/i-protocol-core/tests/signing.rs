@@ -0,0 +1,247 @@
+use i_protocol_core::identity::IdentityError;
+use i_protocol_core::registry::{MinerRegistry, RegistryChange, RegistryError};
+use i_protocol_core::signing::{self, Ed25519Signer, SignatureError, SignatureScheme, Signer};
+use i_protocol_core::{MinerIdentity, Transaction, TransactionKind};
+
+fn hex_field(vector: &serde_json::Value, field: &str) -> Vec<u8> {
+    hex::decode(vector[field].as_str().unwrap()).unwrap()
+}
+
+fn transaction(sender_key: Vec<u8>) -> Transaction {
+    Transaction {
+        sender_key,
+        recipient: "bob".into(),
+        kind: TransactionKind::Transfer,
+        amount_i: 5_000_000,
+        fee: 1_000,
+        user_nonce: 42,
+        timestamp: 1_700_000_000,
+        expiry_height: 1_000,
+        final_nonce: 0,
+        signature: Vec::new(),
+    }
+}
+
+#[test]
+fn ed25519_matches_rfc_8032_vectors() {
+    let file: serde_json::Value =
+        serde_json::from_str(include_str!("../test-vectors/ed25519.json")).unwrap();
+    let vectors = file["vectors"].as_array().unwrap();
+    assert!(!vectors.is_empty());
+
+    for vector in vectors {
+        let seed: [u8; 32] = hex_field(vector, "secret_key").try_into().unwrap();
+        let public_key = hex_field(vector, "public_key");
+        let message = hex_field(vector, "message");
+        let signature = hex_field(vector, "signature");
+
+        let signer = Ed25519Signer::from_seed(&seed);
+        assert_eq!(signer.public_key(), public_key, "{vector}");
+        assert_eq!(signer.sign(&message), signature, "{vector}");
+        assert_eq!(signing::verify(&public_key, &message, &signature), Ok(()));
+    }
+}
+
+// Vectors from a second, independent ML-DSA implementation, in the ACVP
+// keyGen / sigGen / sigVer layout
+#[cfg(feature = "ml-dsa")]
+#[test]
+fn ml_dsa_matches_known_answer_vectors() {
+    use i_protocol_core::signing::MlDsaSigner;
+
+    let file: serde_json::Value =
+        serde_json::from_str(include_str!("../test-vectors/ml_dsa_65.json")).unwrap();
+    let seed =
+        |vector: &serde_json::Value| -> [u8; 32] { hex_field(vector, "seed").try_into().unwrap() };
+
+    for vector in file["keyGen"].as_array().unwrap() {
+        let signer = MlDsaSigner::from_seed(&seed(vector));
+        assert_eq!(signer.public_key(), hex_field(vector, "pk"), "{vector}");
+    }
+
+    for vector in file["sigGen"].as_array().unwrap() {
+        let signer = MlDsaSigner::from_seed(&seed(vector));
+        let message = hex_field(vector, "message");
+        let signature = signer.sign(&message);
+        assert_eq!(signature.len(), SignatureScheme::MlDsa65.signature_len());
+        assert_eq!(signature, hex_field(vector, "signature"), "{vector}");
+    }
+
+    let sig_ver = file["sigVer"].as_array().unwrap();
+    assert!(sig_ver.iter().any(|vector| vector["testPassed"] == false));
+    for vector in sig_ver {
+        let result = signing::verify(
+            &hex_field(vector, "pk"),
+            &hex_field(vector, "message"),
+            &hex_field(vector, "signature"),
+        );
+        assert_eq!(
+            result.is_ok(),
+            vector["testPassed"].as_bool().unwrap(),
+            "{}",
+            vector["reason"]
+        );
+    }
+}
+
+#[test]
+fn forged_ed25519_signatures_are_rejected() {
+    let signer = Ed25519Signer::from_seed(&[7; 32]);
+    let other = Ed25519Signer::from_seed(&[8; 32]);
+    let public_key = signer.public_key();
+    let signature = signer.sign(b"message");
+
+    for bit in [0, 100, 511] {
+        let mut flipped = signature.clone();
+        flipped[bit / 8] ^= 1 << (bit % 8);
+        assert_eq!(
+            signing::verify(&public_key, b"message", &flipped),
+            Err(SignatureError::Invalid)
+        );
+    }
+    assert_eq!(
+        signing::verify(&public_key, b"massage", &signature),
+        Err(SignatureError::Invalid)
+    );
+    assert_eq!(
+        signing::verify(&other.public_key(), b"message", &signature),
+        Err(SignatureError::Invalid)
+    );
+    assert_eq!(
+        signing::verify(&public_key, b"message", &signature[..63]),
+        Err(SignatureError::Invalid)
+    );
+}
+
+#[test]
+fn keys_outside_a_scheme_are_rejected() {
+    assert_eq!(
+        signing::verify(&[1; 33], b"message", &[0; 64]),
+        Err(SignatureError::UnknownScheme { key_len: 33 })
+    );
+    // y = 2 has no square root for x, so this is not a curve point
+    let mut off_curve = [0; 32];
+    off_curve[0] = 2;
+    assert_eq!(
+        signing::verify(&off_curve, b"message", &[0; 64]),
+        Err(SignatureError::MalformedKey)
+    );
+
+    let ml_dsa_key = vec![1; SignatureScheme::MlDsa65.public_key_len()];
+    let result = signing::verify(&ml_dsa_key, b"message", &[0; 3309]);
+    if SignatureScheme::MlDsa65.is_enabled() {
+        assert_eq!(result, Err(SignatureError::Invalid));
+    } else {
+        assert_eq!(
+            result,
+            Err(SignatureError::SchemeDisabled(SignatureScheme::MlDsa65))
+        );
+    }
+}
+
+#[cfg(feature = "ml-dsa")]
+#[test]
+fn forged_ml_dsa_signatures_are_rejected() {
+    use i_protocol_core::signing::MlDsaSigner;
+
+    let signer = MlDsaSigner::from_seed(&[7; 32]);
+    let public_key = signer.public_key();
+    let signature = signer.sign(b"message");
+    assert_eq!(signing::verify(&public_key, b"message", &signature), Ok(()));
+
+    let mut flipped = signature.clone();
+    flipped[1000] ^= 0x10;
+    assert_eq!(
+        signing::verify(&public_key, b"message", &flipped),
+        Err(SignatureError::Invalid)
+    );
+    assert_eq!(
+        signing::verify(&public_key, b"massage", &signature),
+        Err(SignatureError::Invalid)
+    );
+    assert_eq!(
+        signing::verify(
+            &MlDsaSigner::from_seed(&[8; 32]).public_key(),
+            b"message",
+            &signature
+        ),
+        Err(SignatureError::Invalid)
+    );
+
+    // A signature of the other scheme never verifies
+    let ed25519 = Ed25519Signer::from_seed(&[7; 32]);
+    assert_eq!(
+        signing::verify(&public_key, b"message", &ed25519.sign(b"message")),
+        Err(SignatureError::Invalid)
+    );
+    assert_eq!(
+        signing::verify(&ed25519.public_key(), b"message", &signature),
+        Err(SignatureError::Invalid)
+    );
+}
+
+#[test]
+fn transactions_verify_against_the_sender_key() {
+    let signer = Ed25519Signer::from_seed(&[3; 32]);
+    let mut tx = transaction(signer.public_key());
+    tx.sign(&signer);
+    assert_eq!(tx.signature.len(), SignatureScheme::Ed25519.signature_len());
+    assert_eq!(tx.verify_signature(), Ok(()));
+
+    // An impostor can neither spend from the sender's account nor move the
+    // sender's signature onto its own key
+    let impostor = Ed25519Signer::from_seed(&[4; 32]);
+    let mut forged = transaction(signer.public_key());
+    forged.sign(&impostor);
+    assert_eq!(forged.verify_signature(), Err(SignatureError::Invalid));
+    let mut rekeyed = tx.clone();
+    rekeyed.sender_key = impostor.public_key();
+    assert_eq!(rekeyed.verify_signature(), Err(SignatureError::Invalid));
+
+    let mut tampered = tx.clone();
+    tampered.amount_i += 1;
+    assert_eq!(tampered.verify_signature(), Err(SignatureError::Invalid));
+    let mut keyless = tx.clone();
+    keyless.sender_key = b"alice".to_vec();
+    assert_eq!(
+        keyless.verify_signature(),
+        Err(SignatureError::UnknownScheme { key_len: 5 })
+    );
+
+    // The final nonce is outside the signed body
+    let signed = tx.clone();
+    tx.final_nonce = 99;
+    assert_eq!(tx.verify_signature(), Ok(()));
+    assert_eq!(tx.signature, signed.signature);
+}
+
+#[test]
+fn forged_identities_are_refused() {
+    let miner = |id| MinerIdentity::virtual_miner(id, 1_700_000_000);
+
+    // Signed by a key other than the one registered
+    let mut stolen = miner(1);
+    stolen.public_key = MinerIdentity::virtual_signer(2).public_key();
+    assert_eq!(
+        stolen.validate(),
+        Err(IdentityError::Signature(SignatureError::Invalid))
+    );
+
+    // A valid signature does not carry over to other components
+    let mut rewritten = miner(1);
+    rewritten.wallet_address = "addr_attacker".into();
+    assert_eq!(
+        rewritten.validate(),
+        Err(IdentityError::Signature(SignatureError::Invalid))
+    );
+
+    let mut registry = MinerRegistry::with_genesis([miner(0)]).unwrap();
+    assert_eq!(
+        registry.apply_block(1, &[RegistryChange::Join(rewritten.clone())]),
+        Err(RegistryError::InvalidIdentity {
+            identity_hash: rewritten.identity_hash(),
+            error: IdentityError::Signature(SignatureError::Invalid)
+        })
+    );
+    assert!(!registry.is_registered(&rewritten.identity_hash()));
+}
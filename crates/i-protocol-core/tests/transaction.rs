@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use i_protocol_core::fees::{FLAT_FEE, MAX_FEE, MIN_TRANSACTION_AMOUNT};
+use i_protocol_core::params::TX_EXPIRY_BLOCKS;
+use i_protocol_core::signing::{Ed25519Signer, SignatureError, Signer};
+use i_protocol_core::transaction::{TransactionBuilder, TransactionError};
+use i_protocol_core::{Transaction, TransactionKind};
+
+fn builder(kind: TransactionKind) -> TransactionBuilder {
+    Transaction::builder(kind)
+        .sender_key(b"alice".to_vec())
+        .recipient("bob")
+        .amount_i(5_000_000)
+        .user_nonce(42)
+        .timestamp(1_700_000_000_000)
+        .expires_after(100)
+}
+
+fn every_kind() -> Vec<TransactionKind> {
+    vec![
+        TransactionKind::Transfer,
+        TransactionKind::SmartContract {
+            function: "transfer".into(),
+            call_data: vec![1, 2, 3],
+        },
+        TransactionKind::Staking,
+        TransactionKind::Governance {
+            proposal: 7,
+            approve: true,
+        },
+        TransactionKind::CrossChain {
+            chain: "ethereum".into(),
+        },
+        TransactionKind::TokenMint {
+            token: "USD".into(),
+        },
+        TransactionKind::TokenBurn {
+            token: "USD".into(),
+        },
+        TransactionKind::MultiSig {
+            signers: vec!["alice".into(), "carol".into()],
+            threshold: 2,
+        },
+        TransactionKind::Atomic {
+            counter_token: "USD".into(),
+            counter_amount_i: 1_000_000,
+        },
+        TransactionKind::Oracle {
+            feed: "ETH/USD".into(),
+            value: 3_000_000_000,
+        },
+    ]
+}
+
+#[test]
+fn builder_charges_the_ipac_fee() {
+    for (amount_i, fee) in [
+        (MIN_TRANSACTION_AMOUNT, FLAT_FEE),
+        (10_000_000, 100_000),
+        (u128::MAX / 2, MAX_FEE),
+    ] {
+        let tx = builder(TransactionKind::Transfer)
+            .amount_i(amount_i)
+            .build()
+            .unwrap();
+        assert_eq!((tx.amount_i, tx.fee), (amount_i, fee));
+    }
+
+    let tx = builder(TransactionKind::Staking).build().unwrap();
+    assert_eq!(tx.expiry_height, 100 + TX_EXPIRY_BLOCKS);
+    let tx = builder(TransactionKind::Staking)
+        .expires_after(u64::MAX - 1)
+        .build()
+        .unwrap();
+    assert_eq!(tx.expiry_height, u64::MAX);
+    assert_eq!((tx.final_nonce, tx.signature.len()), (0, 0));
+}
+
+#[test]
+fn builder_rejects_incomplete_and_invalid_transactions() {
+    assert_eq!(
+        Transaction::builder(TransactionKind::Transfer)
+            .amount_i(MIN_TRANSACTION_AMOUNT)
+            .recipient("bob")
+            .build(),
+        Err(TransactionError::MissingField("sender key"))
+    );
+    assert_eq!(
+        Transaction::builder(TransactionKind::Transfer).build(),
+        Err(TransactionError::MissingField("amount"))
+    );
+    assert_eq!(
+        builder(TransactionKind::Transfer)
+            .amount_i(MIN_TRANSACTION_AMOUNT - 1)
+            .build(),
+        Err(TransactionError::AmountBelowMinimum {
+            amount_i: MIN_TRANSACTION_AMOUNT - 1
+        })
+    );
+    for (signers, threshold) in [(2, 0), (2, 3), (0, 1)] {
+        let kind = TransactionKind::MultiSig {
+            signers: (0..signers).map(|i| format!("signer-{i}")).collect(),
+            threshold,
+        };
+        assert_eq!(
+            builder(kind).build(),
+            Err(TransactionError::InvalidThreshold { threshold, signers })
+        );
+    }
+}
+
+#[test]
+fn hash_covers_the_unsigned_body() {
+    let tx = builder(TransactionKind::Transfer).build().unwrap();
+    let hash = tx.hash();
+
+    let mut sealed = tx.clone();
+    sealed.signature = vec![0xab; 64];
+    sealed.final_nonce = 123_456;
+    assert_eq!(sealed.hash(), hash);
+    assert_eq!(tx.hash_hex().len(), 64);
+
+    let mut later = tx.clone();
+    later.expiry_height += 1;
+    assert_ne!(later.hash(), hash);
+    let mut repriced = tx;
+    repriced.fee += 1;
+    assert_ne!(repriced.hash(), hash);
+
+    // The kind payload is committed, not only its tag
+    let vote = |approve| {
+        builder(TransactionKind::Governance {
+            proposal: 7,
+            approve,
+        })
+        .build()
+        .unwrap()
+        .hash()
+    };
+    assert_ne!(vote(true), vote(false));
+}
+
+#[test]
+fn every_kind_has_its_own_tag_and_hash() {
+    let kinds = every_kind();
+    let tags: HashSet<u8> = kinds.iter().map(TransactionKind::tag).collect();
+    assert_eq!(tags, (1..=10).collect());
+    let names: HashSet<&str> = kinds.iter().map(TransactionKind::name).collect();
+    assert_eq!(names.len(), 10);
+    assert_eq!(kinds[7].name(), "MultiSig");
+
+    let hashes: HashSet<_> = kinds
+        .into_iter()
+        .map(|kind| builder(kind).build().unwrap().hash())
+        .collect();
+    assert_eq!(hashes.len(), 10);
+}
+
+#[test]
+fn signed_transactions_verify_against_the_sender_key() {
+    let signer = Ed25519Signer::from_seed(&[7; 32]);
+    let other = Ed25519Signer::from_seed(&[8; 32]);
+    for kind in every_kind() {
+        // Signing through the builder commits to the signer's key
+        let tx = builder(kind).sign(&signer).unwrap();
+        assert_eq!(tx.sender_key, signer.public_key());
+        assert_eq!(tx.verify_signature(), Ok(()));
+        let mut claimed = tx.clone();
+        claimed.sender_key = other.public_key();
+        assert_eq!(claimed.verify_signature(), Err(SignatureError::Invalid));
+
+        // Recording the final nonce leaves the signature valid
+        let mut mapped = tx.clone();
+        mapped.final_nonce = 10_001;
+        assert_eq!(mapped.verify_signature(), Ok(()));
+        let mut altered = tx;
+        altered.amount_i += 1;
+        assert_eq!(altered.verify_signature(), Err(SignatureError::Invalid));
+    }
+}
@@ -0,0 +1,463 @@
+use i_protocol_core::block::transactions_root;
+use i_protocol_core::bootstrap::NetworkPhase;
+use i_protocol_core::commit_reveal::RoundOutcome;
+use i_protocol_core::difficulty::DifficultyTarget;
+use i_protocol_core::emission::block_reward;
+use i_protocol_core::fees::{calculate_fee, split_fee, MIN_TRANSACTION_AMOUNT};
+use i_protocol_core::hash::{from_hex, to_hex, triple_layer_hash};
+use i_protocol_core::params::REGULAR_MINING_WINDOW_MS;
+use i_protocol_core::signing::{Ed25519Signer, SignatureError, Signer};
+use i_protocol_core::system_miner::system_nonce;
+use i_protocol_core::tno::execute_tno_mapping;
+use i_protocol_core::transaction::TransactionError;
+use i_protocol_core::validation::{validate_block, BlockError, ChainState};
+use i_protocol_core::{
+    Block, BlockHeader, BlockProducer, Coinbase, HashDomain, MinerIdentity, Transaction,
+    TransactionKind,
+};
+
+fn chain() -> ChainState {
+    ChainState {
+        tip_height: 99,
+        tip_hash: triple_layer_hash(HashDomain::BlockHeader, b"tip"),
+        tip_timestamp: 1_700_000_000_000,
+        active_miners: (0..8)
+            .map(|id| MinerIdentity::virtual_miner(id, 1_700_000_000))
+            .collect(),
+        round: None,
+        phase: NetworkPhase::Sustained,
+        target: DifficultyTarget::MAX,
+    }
+}
+
+fn transactions(header: &BlockHeader, chain: &ChainState) -> Vec<Transaction> {
+    let state = header.chain_state(chain.range_count());
+    (0..4)
+        .map(|i| {
+            let amount = 2_000_000 * (i + 1);
+            let sender = Ed25519Signer::from_seed(&[i as u8 + 1; 32]);
+            let mut tx = Transaction {
+                sender_key: sender.public_key(),
+                recipient: "recipient".into(),
+                kind: TransactionKind::Transfer,
+                amount_i: amount,
+                fee: calculate_fee(amount),
+                user_nonce: 1_000 + i as u64,
+                timestamp: header.timestamp - 100,
+                expiry_height: header.height,
+                final_nonce: 0,
+                signature: Vec::new(),
+            };
+            tx.final_nonce = execute_tno_mapping(tx.user_nonce, &tx.hash_hex(), &state);
+            tx.sign(&sender);
+            tx
+        })
+        .collect()
+}
+
+fn seal(mut header: BlockHeader, chain: &ChainState) -> Block {
+    let transactions = transactions(&header, chain);
+    header.merkle_root = transactions_root(&transactions);
+    header.coinbase = Coinbase {
+        block_reward: block_reward(header.height),
+        miner_fees: transactions.iter().map(|tx| split_fee(tx.fee).miners).sum(),
+    };
+    Block {
+        header,
+        transactions,
+    }
+}
+
+fn template(chain: &ChainState) -> BlockHeader {
+    BlockHeader {
+        height: chain.tip_height + 1,
+        previous_hash: chain.tip_hash,
+        timestamp: chain.tip_timestamp + 500,
+        merkle_root: [0; 32],
+        failed_miner_count: 0,
+        producer: BlockProducer::SystemMiner { system_nonce: 0 },
+        coinbase: Coinbase::default(),
+    }
+}
+
+fn regular_block(chain: &ChainState) -> Block {
+    let range = chain.assign_ranges().ranges[3].clone();
+    let mut header = template(chain);
+    header.producer = BlockProducer::RegularMiner {
+        range_owner: from_hex(&range.identity_hash).unwrap(),
+        nonce: range.start_nonce + 17,
+    };
+    seal(header, chain)
+}
+
+fn sys_block(chain: &ChainState) -> Block {
+    let mut header = template(chain);
+    header.timestamp = chain.tip_timestamp + REGULAR_MINING_WINDOW_MS;
+    header.failed_miner_count = chain.range_holders();
+    header.producer = BlockProducer::SystemMiner {
+        system_nonce: system_nonce(&header.chain_state(chain.range_count())),
+    };
+    seal(header, chain)
+}
+
+// `block` with its system nonce recomputed, as a proposer grinding the
+// nonce inputs would
+fn renonce(mut block: Block, chain: &ChainState) -> Block {
+    block.header.producer = BlockProducer::SystemMiner {
+        system_nonce: system_nonce(&block.header.chain_state(chain.range_count())),
+    };
+    block
+}
+
+#[test]
+fn accepts_valid_blocks() {
+    let chain = chain();
+    assert_eq!(validate_block(&regular_block(&chain), &chain), Ok(()));
+    assert_eq!(validate_block(&sys_block(&chain), &chain), Ok(()));
+}
+
+#[test]
+fn rejects_wrong_parent_and_height() {
+    let chain = chain();
+    let mut block = regular_block(&chain);
+    block.header.height += 1;
+    assert!(matches!(
+        validate_block(&block, &chain),
+        Err(BlockError::HeightMismatch {
+            expected: 100,
+            found: 101
+        })
+    ));
+
+    let mut block = regular_block(&chain);
+    block.header.previous_hash[0] ^= 1;
+    assert!(matches!(
+        validate_block(&block, &chain),
+        Err(BlockError::UnknownParent { .. })
+    ));
+}
+
+#[test]
+fn rejects_timestamp_not_after_tip() {
+    let chain = chain();
+    let mut block = regular_block(&chain);
+    block.header.timestamp = chain.tip_timestamp;
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::TimestampNotIncreasing {
+            tip: chain.tip_timestamp,
+            found: chain.tip_timestamp
+        })
+    );
+}
+
+#[test]
+fn rejects_nonce_outside_producer_range() {
+    let chain = chain();
+    let mut block = regular_block(&chain);
+    let BlockProducer::RegularMiner { nonce, .. } = &mut block.header.producer else {
+        unreachable!()
+    };
+    *nonce += 250_000;
+    assert!(matches!(
+        validate_block(&block, &chain),
+        Err(BlockError::NonceOutsideRange { .. })
+    ));
+
+    let mut block = regular_block(&chain);
+    block.header.producer = BlockProducer::RegularMiner {
+        range_owner: [9; 32],
+        nonce: 10_001,
+    };
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::UnknownRangeOwner {
+            range_owner: [9; 32]
+        })
+    );
+}
+
+#[test]
+fn rejects_failed_miners_on_regular_blocks() {
+    let chain = chain();
+    let mut block = regular_block(&chain);
+    block.header.failed_miner_count = 1;
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::FailedMinerCountOnRegularBlock { count: 1 })
+    );
+}
+
+// Closed round in which every active miner but `withheld` revealed
+fn round(chain: &ChainState, withheld: &[String]) -> RoundOutcome {
+    let revealed = chain
+        .active_miners
+        .iter()
+        .map(MinerIdentity::identity_hash)
+        .filter(|hash| !withheld.contains(hash))
+        .enumerate()
+        .map(|(i, hash)| (hash, [i as u8; 32]))
+        .collect();
+    RoundOutcome {
+        round: 0,
+        revealed,
+        withheld: withheld.to_vec(),
+    }
+}
+
+#[test]
+fn rejects_blocks_from_round_withholders() {
+    // The owner of the block's public-seed range commits and withholds its
+    // reveal; every other active miner reveals
+    let mut chain = chain();
+    let block = regular_block(&chain);
+    let BlockProducer::RegularMiner { range_owner, .. } = block.header.producer else {
+        unreachable!()
+    };
+    chain.round = Some(round(&chain, &[to_hex(&range_owner)]));
+
+    assert_eq!(chain.range_count(), 7);
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::UnknownRangeOwner { range_owner })
+    );
+    // Ranges under the round's master seed are accepted
+    assert_eq!(validate_block(&regular_block(&chain), &chain), Ok(()));
+    assert_eq!(validate_block(&sys_block(&chain), &chain), Ok(()));
+}
+
+#[test]
+fn rejects_blocks_from_deactivated_revealers() {
+    // Every miner reveals, then the block's producer leaves the registry
+    // before the block
+    let mut chain = chain();
+    chain.round = Some(round(&chain, &[]));
+    let block = regular_block(&chain);
+    assert_eq!(validate_block(&block, &chain), Ok(()));
+    let BlockProducer::RegularMiner { range_owner, .. } = block.header.producer else {
+        unreachable!()
+    };
+    chain
+        .active_miners
+        .retain(|miner| miner.identity_hash() != to_hex(&range_owner));
+
+    assert_eq!(chain.range_count(), 7);
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::UnknownRangeOwner { range_owner })
+    );
+    assert_eq!(validate_block(&regular_block(&chain), &chain), Ok(()));
+}
+
+#[test]
+fn rejects_regular_block_above_target() {
+    let mut chain = chain();
+    let block = regular_block(&chain);
+    chain.target = DifficultyTarget::from_threshold(0);
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::TargetNotMet {
+            hash: block.header.hash(),
+            target: chain.target,
+        })
+    );
+
+    // SysBlocks carry no proof of work
+    assert_eq!(validate_block(&sys_block(&chain), &chain), Ok(()));
+}
+
+#[test]
+fn rejects_wrong_system_nonce() {
+    let chain = chain();
+    let mut block = sys_block(&chain);
+    let expected = block.header.system_nonce().unwrap();
+    block.header.producer = BlockProducer::SystemMiner {
+        system_nonce: expected % 10_000 + 1,
+    };
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::SystemNonceMismatch {
+            expected,
+            found: expected % 10_000 + 1
+        })
+    );
+}
+
+#[test]
+fn rejects_proposer_chosen_system_nonce_inputs() {
+    let chain = chain();
+
+    let mut block = sys_block(&chain);
+    block.header.timestamp += 1;
+    assert_eq!(
+        validate_block(&renonce(block, &chain), &chain),
+        Err(BlockError::SysBlockTimestampMismatch {
+            expected: chain.tip_timestamp + REGULAR_MINING_WINDOW_MS,
+            found: chain.tip_timestamp + REGULAR_MINING_WINDOW_MS + 1
+        })
+    );
+
+    let mut block = sys_block(&chain);
+    block.header.failed_miner_count = 9;
+    assert_eq!(
+        validate_block(&renonce(block, &chain), &chain),
+        Err(BlockError::FailedMinerCountExceeded {
+            count: 9,
+            active: 8
+        })
+    );
+}
+
+#[test]
+fn bootstrap_floor_range_carries_transactions() {
+    // Before the first miner registers, SysBlocks map into the unowned range
+    let mut chain = chain();
+    chain.active_miners.clear();
+    chain.phase = NetworkPhase::Bootstrap;
+    assert_eq!(chain.range_count(), 1);
+    let block = sys_block(&chain);
+    assert_eq!(block.transactions.len(), 4);
+    assert_eq!(validate_block(&block, &chain), Ok(()));
+
+    chain.phase = NetworkPhase::Sustained;
+    assert_eq!(
+        validate_block(&renonce(block, &chain), &chain),
+        Err(BlockError::NoActiveMiners)
+    );
+}
+
+#[test]
+fn rejects_bad_transactions() {
+    let chain = chain();
+
+    let mut block = regular_block(&chain);
+    block.transactions[2].final_nonce += 1;
+    block.header.merkle_root = block.compute_merkle_root();
+    assert!(matches!(
+        validate_block(&block, &chain),
+        Err(BlockError::TnoMismatch { index: 2, .. })
+    ));
+
+    let mut block = regular_block(&chain);
+    block.transactions.swap(0, 1);
+    assert!(matches!(
+        validate_block(&block, &chain),
+        Err(BlockError::MerkleRootMismatch { .. })
+    ));
+
+    // Included one block after its expiry height
+    let mut block = regular_block(&chain);
+    let height = block.header.height;
+    block.transactions[1].expiry_height = height - 1;
+    block.header.merkle_root = block.compute_merkle_root();
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::TransactionExpired {
+            index: 1,
+            expiry_height: height - 1,
+            height
+        })
+    );
+
+    let mut block = regular_block(&chain);
+    block.transactions[3].kind = TransactionKind::MultiSig {
+        signers: vec!["alice".into()],
+        threshold: 2,
+    };
+    block.header.merkle_root = block.compute_merkle_root();
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::InvalidTransaction {
+            index: 3,
+            error: TransactionError::InvalidThreshold {
+                threshold: 2,
+                signers: 1
+            }
+        })
+    );
+}
+
+#[test]
+fn rejects_amount_below_minimum() {
+    let chain = chain();
+    let mut block = regular_block(&chain);
+    block.transactions[1].amount_i = MIN_TRANSACTION_AMOUNT - 1;
+    block.header.merkle_root = block.compute_merkle_root();
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::AmountBelowMinimum {
+            index: 1,
+            amount: MIN_TRANSACTION_AMOUNT - 1
+        })
+    );
+}
+
+#[test]
+fn rejects_fee_not_matching_schedule() {
+    let chain = chain();
+    let mut block = regular_block(&chain);
+    let state = block.header.chain_state(chain.range_count());
+    let tx = &mut block.transactions[2];
+    let expected = calculate_fee(tx.amount_i);
+    tx.fee = expected - 1;
+    tx.final_nonce = execute_tno_mapping(tx.user_nonce, &tx.hash_hex(), &state);
+    block.header.merkle_root = block.compute_merkle_root();
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::FeeMismatch {
+            index: 2,
+            expected,
+            found: expected - 1
+        })
+    );
+}
+
+#[test]
+fn rejects_forged_and_tampered_signatures() {
+    let chain = chain();
+    let impostor = Ed25519Signer::from_seed(&[0xee; 32]);
+
+    // Signed by a key other than the sender's
+    let mut block = regular_block(&chain);
+    block.transactions[0].sign(&impostor);
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::InvalidSignature {
+            index: 0,
+            error: SignatureError::Invalid
+        })
+    );
+
+    // Redirected after signing, with the final nonce and root redone
+    let mut block = regular_block(&chain);
+    let state = block.header.chain_state(chain.range_count());
+    let tx = &mut block.transactions[2];
+    tx.recipient = "mallory".into();
+    tx.final_nonce = execute_tno_mapping(tx.user_nonce, &tx.hash_hex(), &state);
+    block.header.merkle_root = block.compute_merkle_root();
+    assert_eq!(
+        validate_block(&block, &chain),
+        Err(BlockError::InvalidSignature {
+            index: 2,
+            error: SignatureError::Invalid
+        })
+    );
+}
+
+#[test]
+fn rejects_wrong_coinbase() {
+    let chain = chain();
+    let mut block = regular_block(&chain);
+    block.header.coinbase.block_reward += 1;
+    assert!(matches!(
+        validate_block(&block, &chain),
+        Err(BlockError::BlockRewardMismatch { .. })
+    ));
+
+    let mut block = sys_block(&chain);
+    block.header.coinbase.miner_fees = 0;
+    assert!(matches!(
+        validate_block(&block, &chain),
+        Err(BlockError::MinerFeesMismatch { .. })
+    ));
+}